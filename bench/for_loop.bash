@@ -0,0 +1,19 @@
+#!/usr/bin/env sush
+# a loop whose body has many branches, of which only one runs
+
+n=0
+for (( j = 0; j < 20000; j++ )); do
+	case $(( j % 10 )) in
+	0) n=$(( n + 1 )) ;;
+	1) n=$(( n + 2 )) ;;
+	2) n=$(( n + 3 )) ;;
+	3) n=$(( n + 4 )) ;;
+	4) n=$(( n + 5 )) ;;
+	5) n=$(( n + 6 )) ;;
+	6) n=$(( n + 7 )) ;;
+	7) n=$(( n + 8 )) ;;
+	8) [[ $n == *0 ]] && n=$(( n / 2 )) ;;
+	*) for w in a b; do n=$(( n - 1 )); done ;;
+	esac
+done
+echo $n
@@ -0,0 +1,17 @@
+#!/usr/bin/env sush
+# recursive helper functions with long bodies
+
+fib() {
+	local n=$1
+	if (( n < 2 )); then
+		ret=$n
+		return
+	fi
+	fib $(( n - 1 ))
+	local a=$ret
+	fib $(( n - 2 ))
+	ret=$(( a + ret ))
+}
+
+fib 18
+echo $ret
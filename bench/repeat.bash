@@ -0,0 +1,13 @@
+#!/usr/bin/env sush
+# repeat and a helper function called from a loop body
+
+count=0
+add () {
+	count=$(( count + $1 ))
+	if (( count > 1000000 )); then
+		count=0
+	fi
+}
+
+repeat 20000 add 3
+echo $count
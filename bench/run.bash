@@ -0,0 +1,15 @@
+#!/bin/bash
+# usage: bench/run.bash [sush binary] ...
+# runs every benchmark script with each binary and prints the real time
+
+cd "$(dirname "$0")"
+[ $# -eq 0 ] && set -- ../target/release/sush
+TIMEFORMAT=%3R
+
+for script in *.bash; do
+	[ "$script" = run.bash ] && continue
+	for sush in "$@"; do
+		sec=$( { time "$sush" "$script" > /dev/null; } 2>&1 )
+		printf "%-24s %-32s %8ss\n" "$script" "$sush" "$sec"
+	done
+done
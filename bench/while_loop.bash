@@ -0,0 +1,19 @@
+#!/usr/bin/env sush
+# a tight loop whose body is a sizeable script
+
+i=0
+sum=0
+while (( i < 20000 )); do
+	if (( i % 3 == 0 )); then
+		sum=$(( sum + i ))
+	elif (( i % 3 == 1 )); then
+		sum=$(( sum - 1 ))
+	else
+		case $i in
+		*0) sum=$(( sum + 2 )) ;;
+		*) : ;;
+		esac
+	fi
+	i=$(( i + 1 ))
+done
+echo $sum
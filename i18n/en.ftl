@@ -10,11 +10,12 @@ options =
         -l, --login               unsuported
         -r                        unsuported
         -s                        unsuported
-        -D                        unsuported
+        -D, --dump-strings        Print the $"..." strings in the script and exit
         -O, +O                    unsuported
         --                        unsuported
         --debugger                unsuported
-        --dimp-po-strings         unsuported
+        --dump-po-strings         Same as -D but in the PO format (without a script,
+                                  the messages of the shell itself)
         --help                    Display this help message and exit
         --init-file FILE          unsuported
         --rcfile FILE             unsuported
@@ -243,3 +244,856 @@ text-version =
 
     THIS SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
     EXPRESS OR IMPLIED, TO THE EXTENT PERMITTED BY LAW.
+
+## Diagnostics
+
+internal-error = SUSH INTERNAL ERROR: { $msg }
+invalid-option = { $opt }: invalid option
+no-such-file = { $name }: No such file or directory
+io-error = { $name }: { $err }
+at-line = line { $lineno }
+no-resources-for-language = No resources found for language
+broken-ftl-entries = { $file }: broken entries ignored
+compat-test-mode = THIS IS BASH COMPATIBILITY TEST MODE
+signaled = Pid: { $pid }, Signal: { $signal }
+signaled-core-dumped = Pid: { $pid }, Signal: { $signal } (core dumped)
+stopped = Stopped Pid: { $pid }, Signal: { $signal }
+failed-to-execute = Failed to execute. { $err }
+
+exec-internal = INTERNAL ERROR
+exec-ambiguous-redirect = { $name }: ambiguous redirect
+exec-bad-array-subscript = [{ $name }]: bad array subscript
+exec-arg-list-too-long = { $name }: Arg list too long
+exec-bad-substitution = `{ $word }': bad substitution
+exec-bad-fd = { $fd }: bad file descriptor
+exec-cannot-overwrite = { $file }: cannot overwrite existing file
+exec-circular-nameref = { $name }: circular name reference
+exec-command-not-found = { $name }: command not found
+exec-invalid-indirect-expansion = { $name }: invalid indirect expansion
+exec-invalid-identifier = `{ $name }': not a valid identifier
+exec-invalid-nameref = `{ $name }': invalid variable name for name reference
+exec-interrupted = interrupted
+exec-only-in-function = can only be used in a function
+exec-readonly-variable = { $name }: readonly variable
+exec-permission-denied = { $name }: Permission denied
+exec-nameref-self-reference = { $name }: nameref variable self references not allowed
+exec-syntax-error = syntax error near unexpected token `{ $token }'
+exec-restricted = { $name }: restricted
+exec-nameref-array = { $name }: reference variable cannot be an array
+exec-substring-minus = { $num }: substring expression < 0
+exec-unsupported-wait-status = Unsupported wait status: { $status }
+exec-unbound-variable = { $name }: unbound variable
+exec-system-error = system error { $errno }
+exec-bug = INTERNAL BUG: { $msg }
+
+parse-unexpected-token = syntax error near unexpected token: { $token }
+parse-wrong-alias = Someting wrong alias: { $msg }
+
+input-unexpected-eof = syntax error: unexpected end of file
+
+arith-assignment-to-non-variable = attempted assignment to non-variable (error token is "{ $token }")
+arith-division-by-zero = division by 0 (error token is "{ $token }")
+arith-exponent-less-than-zero = exponent less than 0 (error token is "{ $token }")
+arith-colon-expected = `:' expected for conditional expression (error token is "{ $token }")
+arith-expression-expected = expression expected (error token is "{ $token }")
+arith-invalid-base = invalid arithmetic base (error token is "{ $token }")
+arith-value-too-great-for-base = value too great for base (error token is "{ $token }")
+arith-invalid-number = invalid number (error token is "{ $token }")
+arith-invalid-integer-constant = invalid integer constant (error token is "{ $token }")
+arith-invalid-operator = invalid arithmetic operator (error token is "{ $token }")
+arith-operand-expected = syntax error: operand expected (error token is "{ $token }")
+arith-recursion = expression recursion level exceeded (error token is "{ $token }")
+arith-syntax-error = syntax error in expression (error token is "{ $token }")
+syntax-error-near = syntax error near `{ $token }'
+cond-unexpected-token = syntax error in conditional expression: unexpected token `{ $token }'
+must-use-subscript = { $name }: { $word }: must use subscript when assigning associative array
+cannot-assign-list = { $name }: cannot assign list to array member
+restricted-slash-in-command = { $name }: restricted: cannot specify `/' in command names
+restricted-redirect = { $name }: restricted: cannot redirect output
+heredoc-delimited-by-eof = warning: here-document at line { $lineno } delimited by end-of-file (wanted `{ $word }')
+coproc-still-exists = warning: execute_coproc: coproc [{ $pid }:{ $name }] still exists
+invalid-timeformat-char = TIMEFORMAT: `{ $char }': invalid format character
+cannot-retrieve-cwd = pwd: error retrieving current directory: { $err }
+
+restricted = restricted
+too-many-arguments = too many arguments
+expression-expected = expression expected
+not-implemented = still not implemented
+not-found = { $name }: not found
+not-found-plain = not found
+not-set = { $name } not set
+not-supported-yet = { $name }: not supported yet
+still-unsupported = { $name }: still unsupported
+option-requires-argument = { $opt }: option requires an argument
+numeric-argument-required = { $arg }: numeric argument required
+invalid-argument = { $arg }: invalid argument
+invalid-number = { $num }: invalid number
+invalid-fd = { $fd }: invalid file descriptor: Bad file descriptor
+invalid-fd-spec = { $fd }: invalid file descriptor specification
+invalid-variable = { $name }: invalid variable
+invalid-option-name = { $name }: invalid option name
+invalid-shell-option = { $name }: invalid shell option name
+is-a-directory = { $name }: is a directory
+filename-required = filename argument required
+arg-list-too-long = Arg list too long
+cannot-execute = cannot execute: Permission denied
+no-such-file-or-directory = No such file or directory
+not-a-shell-builtin = { $name }: not a shell builtin
+subst-command-unsupported = substitution command are not supported
+no-completion-spec = { $name }: no completion specification
+return-outside-function = can only `return' from a function or sourced script
+only-meaningful-in-loop = only meaningful in a `for', `while', or `until' loop
+loop-count-out-of-range = { $count }: loop count out of range
+shift-count-out-of-range = { $count }: shift count out of range
+invalid-timeout = { $spec }: invalid timeout specification
+invalid-line-count = { $num }: invalid line count
+invalid-array-origin = { $num }: invalid array origin
+invalid-callback-quantum = { $num }: invalid callback quantum
+invalid-signal-spec = { $sig }: invalid signal specification
+octal-out-of-range = { $mode }: octal number out of range
+invalid-mode-operator = `{ $char }': invalid symbolic mode operator
+invalid-mode-character = `{ $char }': invalid symbolic mode character
+directory-stack-empty = directory stack empty
+directory-stack-out-of-range = { $index }: directory stack index out of range
+no-other-directory = no other directory
+trap-forbidden-signal = { $sig }: forbidden signal for trap
+invalid-char = Invalid char: { $char }
+empty-param-stack = empty param stack
+
+int-empty = cannot parse integer from empty string
+int-invalid-digit = invalid digit found in string
+int-overflow = number too large to fit in target type
+not-integer = { $expr }: Not integer. { $num }
+no-second-operand = no operand 2
+float-unsupported-operator = { $op }: not supported operator for float numbers
+float-division-by-zero = divided by 0
+float-condition-not-permitted = float condition is not permitted
+no-operand-to-negate = no operand to negate
+unknown-operand = unknown operand
+invalid-operand = Invalid operand
+non-integer-unsupported = non integer number is not supported
+unsupported-option = unsupported option
+evaluation-error = evaluation error
+unknown-symbol = unknown symbol
+parse-error = parse error
+unknown-error = unknown error
+no-array-and-no-index = no array and no index
+
+not-a-single-variable = not a single variable
+not-an-array = not an array
+not-an-assoc = not an associative table
+undefined-call = Undefined call { $name }
+no-last-input = No last input
+no-entry = No entry
+invalid-index = invalid index
+cannot-get-process-group = cannot get process group
+dup2-error = dup2 error
+negative-fd = minus fd number
+
+completion-position-error = pos error
+no-completion-candidate = no completion cand
+empty-completion-list = empty list
+
+no-job-control = no job control
+no-such-job = { $spec }: no such job
+ambiguous-job-spec = { $spec }: ambiguous job spec
+job-without-job-control = job { $id } started without job control
+job-already-in-background = job { $id } already in background
+invalid-job-position = invalpos jobpos
+called-from-subshell = called from subshell
+cannot-suspend-login-shell = cannot suspend a login shell
+cannot-suspend-no-job-control = cannot suspend: no job control
+
+event-not-found = { $event }: event not found
+bad-word-specifier = { $spec }: bad word specifier
+substitution-failed = { $spec }: substitution failed
+unrecognized-history-modifier = { $modifier }: unrecognized history modifier
+history-expansion-failed = { $arg }: history expansion failed
+history-position-out-of-range = { $pos }: history position out of range
+history-spec-out-of-range = history specification out of range
+cannot-use-more-than-one = cannot use more than one of { $opts }
+cannot-access-history-file = { $file }: cannot access history file
+invalid-history-file = invalid history file
+no-command-found = no command found
+cannot-open-temp-file = { $file }: cannot open temp file
+
+## Help
+
+help-header =
+    These shell commands are defined internally. Type `help' to see this list.
+    Type `help name' to find out more about the function `name'.
+    Use `man -k' or `info' to find out more about commands not in this list.
+help-matching = Shell commands matching keyword `{ $pattern }'
+help-no-topics = no help topics match `{ $pattern }'.  Try `help help' or `man -k { $pattern }' or `info { $pattern }'.
+
+help-colon = Null command.
+help-colon-long =
+    No effect; the command does nothing.
+
+    Exit Status:
+    Always succeeds.
+help-alias = Define or display aliases.
+help-alias-long =
+    Without arguments, `alias' prints the list of aliases in the reusable
+    form `alias NAME=VALUE' on standard output. Otherwise, an alias is
+    defined for each NAME whose VALUE is given.
+
+    Options:
+      -p        print all defined aliases in a reusable format
+
+    Exit Status:
+    Returns true unless a NAME is supplied for which no alias is defined.
+help-bg = Move jobs to the background.
+help-bg-long =
+    Resumes each suspended job JOB_SPEC in the background, as if it had
+    been started with `&'. Without JOB_SPEC, the current job is used.
+
+    Exit Status:
+    Returns success unless job control is not enabled or an error occurs.
+help-bind = Set Readline key bindings and variables.
+help-bind-long =
+    Accepted for compatibility. The line editor of sush does not read
+    the Readline configuration, so this command does nothing.
+
+    Exit Status:
+    Always succeeds.
+help-break = Exit for, while, or until loops.
+help-break-long =
+    Exits a for, while or until loop. If N is specified, breaks N
+    enclosing loops.
+
+    Exit Status:
+    The exit status is 0 unless N is not greater than or equal to 1.
+help-builtin = Execute shell builtins.
+help-builtin-long =
+    Executes SHELL-BUILTIN with arguments ARGs without performing command
+    lookup. This is useful when a builtin is redefined as a function.
+
+    Exit Status:
+    Returns the exit status of SHELL-BUILTIN, or false if SHELL-BUILTIN
+    is not a shell builtin.
+help-caller = Return the context of the current subroutine call.
+help-caller-long =
+    Without EXPR, prints the line number and the source file name of the
+    current subroutine call. With EXPR, prints the line number, the
+    subroutine name and the source file of the EXPR-th frame of the call
+    stack.
+
+    Exit Status:
+    Returns 0 unless the shell is not executing a shell function or EXPR
+    is invalid.
+help-cd = Change the shell working directory.
+help-cd-long =
+    Changes the current directory to DIR. The default DIR is the value of
+    the HOME shell variable. If DIR is `-', it is converted to $OLDPWD.
+
+    The variable CDPATH defines the search path for the directory
+    containing DIR. If DIR begins with a slash, CDPATH is not used.
+
+    Options:
+      -L        force symbolic links to be followed
+      -P        use the physical directory structure without following
+                symbolic links
+      -e        with -P, exit with a non-zero status if the current
+                working directory cannot be determined
+
+    Exit Status:
+    Returns 0 if the directory is changed; non-zero otherwise.
+help-command = Execute a simple command or display information about commands.
+help-command-long =
+    Runs COMMAND with ARGS suppressing shell function lookup, or displays
+    information about the specified COMMANDs.
+
+    Options:
+      -p        use a default value for PATH
+      -v        print a single word indicating the command or file name
+      -V        print a more verbose description of each COMMAND
+
+    Exit Status:
+    Returns the exit status of COMMAND, or failure if COMMAND is not found.
+help-compgen = Display possible completions depending on the options.
+help-compgen-long =
+    Generates the possible completion matches for WORD with the same
+    options as `complete' and prints them on standard output.
+
+    Exit Status:
+    Returns success unless an invalid option is supplied or an error occurs.
+help-complete = Specify how arguments are to be completed.
+help-complete-long =
+    For each NAME, specifies how arguments are to be completed. Without
+    options or NAMEs, the existing completion specifications are printed.
+
+    Options:
+      -p        print existing completion specifications in a reusable format
+      -r        remove a completion specification for each NAME
+      -A ACTION the kind of words to generate
+      -F FUNC   call the shell function FUNC to generate the words
+      -W LIST   split LIST and use the words
+
+    Exit Status:
+    Returns success unless an invalid option is supplied or an error occurs.
+help-compopt = Modify or display completion options.
+help-compopt-long =
+    Modifies the completion options for each NAME, or for the completion
+    currently being executed if no NAMEs are supplied.
+
+    Options:
+      -o OPT    set completion option OPT for each NAME
+      +o OPT    unset completion option OPT for each NAME
+
+    Exit Status:
+    Returns success unless an invalid option is supplied or NAME does not
+    have a completion specification defined.
+help-continue = Resume for, while, or until loops.
+help-continue-long =
+    Resumes the next iteration of the enclosing for, while or until loop.
+    If N is specified, resumes the N-th enclosing loop.
+
+    Exit Status:
+    The exit status is 0 unless N is not greater than or equal to 1.
+help-debug = Do nothing.
+help-debug-long =
+    A placeholder for debugging the shell itself.
+
+    Exit Status:
+    Always succeeds.
+help-declare = Set variable values and attributes.
+help-declare-long =
+    Declares variables and gives them attributes. If no NAMEs are given,
+    displays the attributes and values of all variables.
+
+    Options:
+      -p        display the attributes and value of each NAME
+      -a        to make NAMEs indexed arrays
+      -A        to make NAMEs associative arrays
+      -i        to make NAMEs have the `integer' attribute
+      -l        to convert the value of each NAME to lower case on assignment
+      -u        to convert the value of each NAME to upper case on assignment
+      -r        to make NAMEs readonly
+      -x        to make NAMEs export
+
+    Using `+' instead of `-' turns off the given attribute. When used in a
+    function, `declare' makes NAMEs local, as with the `local' command.
+    `typeset' is a synonym for `declare'.
+
+    Exit Status:
+    Returns success unless an invalid option is supplied or a variable
+    assignment error occurs.
+help-dirs = Display directory stack.
+help-dirs-long =
+    Displays the list of currently remembered directories. Directories
+    are added with `pushd' and removed with `popd'.
+
+    Options:
+      -c        clear the directory stack by deleting all of the elements
+      -l        do not abbreviate the home directory with a tilde
+      -p        print the directory stack with one entry per line
+      -v        print the directory stack with one entry per line prefixed
+                with its position in the stack
+
+    Arguments:
+      +N        displays the N-th entry counting from the left
+      -N        displays the N-th entry counting from the right
+
+    Exit Status:
+    Returns success unless an invalid option is supplied or an error occurs.
+help-disown = Remove jobs from current shell.
+help-disown-long =
+    Removes each JOBSPEC from the table of active jobs. Without JOBSPEC,
+    the current job is used.
+
+    Options:
+      -a        remove all jobs if JOBSPEC is not supplied
+      -h        mark each JOBSPEC so that SIGHUP is not sent to the job if
+                the shell receives a SIGHUP
+      -r        remove only running jobs
+
+    Exit Status:
+    Returns success unless an invalid option or JOBSPEC is given.
+help-echo = Write arguments to the standard output.
+help-echo-long =
+    Displays the ARGs, separated by a single space character and followed
+    by a newline, on the standard output.
+
+    Options:
+      -n        do not append a newline
+      -e        enable interpretation of backslash escapes
+      -E        explicitly suppress interpretation of backslash escapes
+
+    Exit Status:
+    Returns success unless a write error occurs.
+help-eval = Execute arguments as a shell command.
+help-eval-long =
+    Combines ARGs into a single string, uses the result as input to the
+    shell, and executes the resulting commands.
+
+    Exit Status:
+    Returns the exit status of the command or success if the command is null.
+help-exec = Replace the shell with the given command.
+help-exec-long =
+    Executes COMMAND, replacing this shell with the specified program.
+    If COMMAND is not specified, any redirections take effect in the
+    current shell.
+
+    Options:
+      -a NAME   pass NAME as the zeroth argument to COMMAND
+      -c        execute COMMAND with an empty environment
+      -l        place a dash in the zeroth argument to COMMAND
+
+    Exit Status:
+    Returns success unless COMMAND is not found or a redirection error
+    occurs.
+help-exit = Exit the shell.
+help-exit-long =
+    Exits the shell with a status of N. If N is omitted, the exit status
+    is that of the last command executed.
+help-export = Set export attribute for shell variables.
+help-export-long =
+    Marks each NAME for automatic export to the environment of
+    subsequently executed commands. If VALUE is supplied, assigns VALUE
+    before exporting.
+
+    Options:
+      -n        remove the export property from each NAME
+      -p        display a list of all exported variables
+
+    Exit Status:
+    Returns success unless an invalid option is given or NAME is invalid.
+help-false = Return an unsuccessful result.
+help-false-long =
+    Exit Status:
+    Always fails.
+help-fc = Display or execute commands from the history list.
+help-fc-long =
+    Lists, or edits and re-executes, commands from the history list.
+    FIRST and LAST can be numbers specifying the range, or FIRST can be a
+    string, which means the most recent command beginning with that string.
+
+    Options:
+      -e ENAME  select which editor to use. Default is FCEDIT, then EDITOR,
+                then vi
+      -l        list lines instead of editing
+      -n        omit line numbers when listing
+      -r        reverse the order of the lines (newest listed first)
+
+    With the `fc -s [pat=rep ...] [command]' format, COMMAND is
+    re-executed after the substitution OLD=NEW is performed.
+
+    Exit Status:
+    Returns success or status of executed command; non-zero if an error
+    occurs.
+help-fg = Move job to the foreground.
+help-fg-long =
+    Places the job identified by JOB_SPEC in the foreground, making it the
+    current job. If JOB_SPEC is not present, the current job is used.
+
+    Exit Status:
+    Status of command placed in foreground, or failure if an error occurs.
+help-getopts = Parse option arguments.
+help-getopts-long =
+    Getopts is used by shell procedures to parse positional parameters
+    as options. OPTSTRING contains the option letters to be recognized;
+    a letter followed by a colon takes an argument, which is placed
+    into OPTARG. The index of the next argument is placed into OPTIND.
+
+    Exit Status:
+    Returns success if an option is found; fails if the end of options is
+    encountered or an error occurs.
+help-hash = Remember or display program locations.
+help-hash-long =
+    Determines and remembers the full pathname of each command NAME. If
+    no arguments are given, information about remembered commands is
+    displayed.
+
+    Options:
+      -d        forget the remembered location of each NAME
+      -l        display in a format that may be reused as input
+      -p PATH   use PATH as the full pathname of NAME
+      -r        forget all remembered locations
+      -t        print the remembered location of each NAME
+
+    Exit Status:
+    Returns success unless NAME is not found or an invalid option is given.
+help-help = Display information about builtin commands.
+help-help-long =
+    Displays brief summaries of builtin commands. If PATTERN is
+    specified, gives detailed help on all commands matching PATTERN,
+    otherwise the list of help topics is printed.
+
+    Options:
+      -d        output short description for each topic
+      -m        display usage in pseudo-manpage format
+      -s        output only a short usage synopsis for each topic matching
+                PATTERN
+
+    Exit Status:
+    Returns success unless PATTERN is not found or an invalid option is given.
+help-history = Display or manipulate the history list.
+help-history-long =
+    Displays the history list with line numbers. An argument of N lists
+    only the last N entries.
+
+    Options:
+      -c        clear the history list by deleting all of the entries
+      -d OFFSET delete the history entry at position OFFSET
+      -a        append history lines from this session to the history file
+      -n        read all history lines not already read from the history file
+      -r        read the history file and append the contents to the history
+                list
+      -w        write the current history to the history file
+      -p        perform history expansion on each ARG and display the result
+      -s        append the ARGs to the history list as a single entry
+
+    If HISTTIMEFORMAT is set and not null, its value is used as a format
+    string for strftime(3) to print the time stamp of each entry.
+
+    Exit Status:
+    Returns success unless an invalid option is given or an error occurs.
+help-jobs = Display status of jobs.
+help-jobs-long =
+    Lists the active jobs. JOBSPEC restricts output to that job.
+
+    Options:
+      -l        lists process IDs in addition to the normal information
+      -n        lists only processes that have changed status since the last
+                notification
+      -p        lists process IDs only
+      -r        restrict output to running jobs
+      -s        restrict output to stopped jobs
+
+    Exit Status:
+    Returns success unless an invalid option is given or an error occurs.
+help-kill = Send a signal to a job.
+help-kill-long =
+    Sends the processes identified by PID or JOBSPEC the signal named by
+    SIGSPEC or SIGNUM. If neither is present, SIGTERM is assumed.
+
+    Options:
+      -s SIG    SIG is a signal name
+      -n SIG    SIG is a signal number
+      -l        list the signal names
+
+    Exit Status:
+    Returns success unless an invalid option is given or an error occurs.
+help-let = Evaluate arithmetic expressions.
+help-let-long =
+    Evaluates each ARG as an arithmetic expression.
+
+    Exit Status:
+    If the last ARG evaluates to 0, let returns 1; let returns 0 otherwise.
+help-local = Define local variables.
+help-local-long =
+    Creates a local variable called NAME, and gives it VALUE. OPTION can
+    be any option accepted by `declare'. Local variables can only be used
+    within a function.
+
+    Exit Status:
+    Returns success unless an invalid option is supplied, a variable
+    assignment error occurs, or the shell is not executing a function.
+help-mapfile = Read lines from the standard input into an indexed array variable.
+help-mapfile-long =
+    Reads lines from the standard input into the indexed array variable
+    ARRAY, or from file descriptor FD if the -u option is supplied. The
+    variable MAPFILE is the default ARRAY. `readarray' is a synonym for
+    `mapfile'.
+
+    Options:
+      -d DELIM  use DELIM to terminate lines, instead of newline
+      -n COUNT  copy at most COUNT lines
+      -O ORIGIN begin assigning to ARRAY at index ORIGIN
+      -s COUNT  discard the first COUNT lines read
+      -t        remove a trailing DELIM from each line read
+      -u FD     read lines from file descriptor FD
+      -C CALL   evaluate CALL each time QUANTUM lines are read
+      -c QUANT  specify the number of lines read between each call to CALL
+
+    Exit Status:
+    Returns success unless an invalid option is given or ARRAY is readonly
+    or not an indexed array.
+help-popd = Remove directories from stack.
+help-popd-long =
+    Removes entries from the directory stack. With no arguments, removes
+    the top directory from the stack, and changes to the new top directory.
+
+    Options:
+      -n        suppress the change of directory
+
+    Arguments:
+      +N        removes the N-th entry counting from the left
+      -N        removes the N-th entry counting from the right
+
+    Exit Status:
+    Returns success unless an invalid argument is supplied or the directory
+    change fails.
+help-printf = Formats and prints ARGUMENTS under control of the FORMAT.
+help-printf-long =
+    FORMAT is a character string which contains plain characters, escape
+    sequences and format specifications as in printf(1). In addition,
+    %b expands backslash escapes in the argument and %q quotes the
+    argument in a way that can be reused as shell input.
+
+    Options:
+      -v VAR    assign the output to shell variable VAR rather than display
+                it on the standard output
+
+    Exit Status:
+    Returns success unless an invalid option is given or a write or
+    assignment error occurs.
+help-pushd = Add directories to stack.
+help-pushd-long =
+    Adds a directory to the top of the directory stack, or rotates the
+    stack, making the new top of the stack the current working directory.
+    With no arguments, exchanges the top two directories.
+
+    Options:
+      -n        suppress the change of directory
+
+    Arguments:
+      +N        rotates the stack so that the N-th directory from the left
+                is at the top
+      -N        rotates the stack so that the N-th directory from the right
+                is at the top
+      DIR       adds DIR to the directory stack at the top
+
+    Exit Status:
+    Returns success unless an invalid argument is supplied or the directory
+    change fails.
+help-pwd = Print the name of the current working directory.
+help-pwd-long =
+    Options:
+      -L        print the value of $PWD if it names the current working
+                directory
+      -P        print the physical directory, without any symbolic links
+
+    Exit Status:
+    Returns 0 unless an invalid option is given or the current directory
+    cannot be read.
+help-read = Read a line from the standard input and split it into fields.
+help-read-long =
+    Reads a single line from the standard input, or from file descriptor
+    FD if the -u option is supplied. The line is split into fields as
+    with word splitting, and each field is assigned to a NAME. The last
+    NAME gets the remaining words. Without NAMEs, the line is stored in
+    the REPLY variable.
+
+    Options:
+      -a ARRAY  assign the words to sequential indices of the array ARRAY
+      -d DELIM  continue until the first character of DELIM is read
+      -e        use the line editor to obtain the line
+      -i TEXT   use TEXT as the initial text for the line editor
+      -n NCHARS return after reading NCHARS characters
+      -N NCHARS return only after reading exactly NCHARS characters
+      -p PROMPT output the string PROMPT without a trailing newline
+      -r        do not allow backslashes to escape any characters
+      -s        do not echo input coming from a terminal
+      -t TIMEOUT time out and return failure after TIMEOUT seconds
+      -u FD     read from file descriptor FD
+
+    Exit Status:
+    The return code is zero, unless end-of-file is encountered, read times
+    out, or an invalid file descriptor is supplied.
+help-readonly = Mark shell variables as unchangeable.
+help-readonly-long =
+    Marks each NAME as read-only; the values of these NAMEs may not be
+    changed by subsequent assignment. If VALUE is supplied, assigns VALUE
+    before marking as read-only.
+
+    Options:
+      -a        refer to indexed array variables
+      -A        refer to associative array variables
+      -p        display a list of all readonly variables
+
+    Exit Status:
+    Returns success unless an invalid option is given or NAME is invalid.
+help-return = Return from a shell function.
+help-return-long =
+    Causes a function or sourced script to exit with the return value
+    specified by N. If N is omitted, the return status is that of the
+    last command executed.
+
+    Exit Status:
+    Returns N, or failure if the shell is not executing a function or script.
+help-set = Set or unset values of shell options and positional parameters.
+help-set-long =
+    Changes the value of shell attributes and positional parameters, or
+    displays the names and values of shell variables.
+
+    Options:
+      -e        exit immediately if a command exits with a non-zero status
+      -m        job control is enabled
+      -n        read commands but do not execute them
+      -o NAME   set the option corresponding to NAME, such as pipefail
+      -u        treat unset variables as an error when substituting
+      -x        print commands and their arguments as they are executed
+      -B        the shell will perform brace expansion
+      -H        enable ! style history substitution
+      --        assign any remaining arguments to the positional parameters
+
+    Using + rather than - causes these flags to be turned off.
+
+    Exit Status:
+    Returns success unless an invalid option is given.
+help-shift = Shift positional parameters.
+help-shift-long =
+    Renames the positional parameters $N+1,$N+2 ... to $1,$2 ... If N is
+    not given, it is assumed to be 1.
+
+    Exit Status:
+    Returns success unless N is negative or greater than $#.
+help-shopt = Set and unset shell options.
+help-shopt-long =
+    Changes the setting of each shell option OPTNAME. Without any option
+    arguments, lists each supplied OPTNAME, or all shell options if no
+    OPTNAMEs are given, with an indication of whether or not each is set.
+
+    Options:
+      -s        enable (set) each OPTNAME
+      -u        disable (unset) each OPTNAME
+
+    Exit Status:
+    Returns success if OPTNAME is enabled; fails if an invalid option is
+    given or OPTNAME is disabled.
+help-source = Execute commands from a file in the current shell.
+help-source-long =
+    Reads and executes commands from FILENAME in the current shell. If
+    any ARGUMENTS are supplied, they become the positional parameters
+    when FILENAME is executed.
+
+    Exit Status:
+    Returns the status of the last command executed in FILENAME; fails if
+    FILENAME cannot be read.
+help-suspend = Suspend shell execution.
+help-suspend-long =
+    Suspends the execution of this shell until it receives a SIGCONT
+    signal. Login shells cannot be suspended unless forced.
+
+    Options:
+      -f        force the suspend, even if the shell is a login shell
+
+    Exit Status:
+    Returns success unless job control is not enabled or an error occurs.
+help-test = Evaluate conditional expression.
+help-test-long =
+    Exits with a status of 0 (true) or 1 (false) depending on the
+    evaluation of EXPR. The `[' command is a synonym for `test', but the
+    last argument must be a literal `]', to match the opening `['.
+
+    File operators such as -e, -f and -d, string operators such as -z,
+    -n, = and !=, and arithmetic operators such as -eq and -lt are
+    available, as with the `[[' compound command.
+
+    Exit Status:
+    Returns success if EXPR evaluates to true; fails if EXPR evaluates to
+    false or an invalid argument is given.
+help-times = Display process times.
+help-times-long =
+    Prints the accumulated user and system times for the shell and all of
+    its child processes.
+
+    Exit Status:
+    Always succeeds.
+help-trap = Trap signals and other events.
+help-trap-long =
+    Defines and activates handlers to be run when the shell receives
+    signals or other conditions. ARG is a command to be read and executed
+    when the shell receives the signal(s) SIGNAL_SPEC. If ARG is absent
+    or `-', each specified signal is reset to its original value. If ARG
+    is the null string, each SIGNAL_SPEC is ignored.
+
+    If a SIGNAL_SPEC is EXIT (0) ARG is executed on exit from the shell.
+    If a SIGNAL_SPEC is DEBUG, ARG is executed before every simple
+    command. ERR and RETURN are also accepted.
+
+    Options:
+      -l        print a list of signal names and their corresponding numbers
+      -p        display the trap commands associated with each SIGNAL_SPEC
+
+    Exit Status:
+    Returns success unless a SIGSPEC is invalid or an invalid option is given.
+help-true = Return a successful result.
+help-true-long =
+    Exit Status:
+    Always succeeds.
+help-type = Display information about command type.
+help-type-long =
+    For each NAME, indicate how it would be interpreted if used as a
+    command name.
+
+    Options:
+      -a        display all locations containing an executable named NAME
+      -f        suppress shell function lookup
+      -P        force a PATH search for each NAME
+      -p        returns the name of the disk file that would be executed
+      -t        output a single word: alias, keyword, function, builtin,
+                file or nothing
+
+    Exit Status:
+    Returns success if all of the NAMEs are found; fails if any are not found.
+help-ulimit = Modify shell resource limits.
+help-ulimit-long =
+    Provides control over the resources available to the shell and
+    processes it creates, on systems that allow such control.
+
+    Options:
+      -S        use the `soft' resource limit
+      -H        use the `hard' resource limit
+      -a        all current limits are reported
+      -c        the maximum size of core files created
+      -f        the maximum size of files written by the shell and its children
+      -n        the maximum number of open file descriptors
+      -s        the maximum stack size
+      -t        the maximum amount of cpu time in seconds
+      -u        the maximum number of user processes
+      -v        the size of virtual memory
+
+    Exit Status:
+    Returns success unless an invalid option is supplied or an error occurs.
+help-umask = Display or set file mode mask.
+help-umask-long =
+    Sets the user file-creation mask to MODE. If MODE is omitted, prints
+    the current value of the mask. MODE can be an octal number or a
+    symbolic mode string like that accepted by chmod(1).
+
+    Options:
+      -p        if MODE is omitted, output in a form that may be reused as input
+      -S        makes the output symbolic; otherwise an octal number is output
+
+    Exit Status:
+    Returns success unless MODE is invalid or an invalid option is given.
+help-unalias = Remove each NAME from the list of defined aliases.
+help-unalias-long =
+    Options:
+      -a        remove all alias definitions
+
+    Exit Status:
+    Returns success unless a NAME is not an existing alias.
+help-unset = Unset values and attributes of shell variables and functions.
+help-unset-long =
+    For each NAME, remove the corresponding variable or function.
+
+    Options:
+      -f        treat each NAME as a shell function
+      -v        treat each NAME as a shell variable
+      -n        treat each NAME as a name reference and unset the variable
+                itself rather than the variable it references
+
+    Without options, unset first tries to unset a variable, and if that
+    fails, tries to unset a function.
+
+    Exit Status:
+    Returns success unless an invalid option is given or a NAME is read-only.
+help-wait = Wait for job completion and return exit status.
+help-wait-long =
+    Waits for each process identified by an ID, which may be a process ID
+    or a job specification, and reports its termination status. If ID is
+    not given, waits for all currently active child processes.
+
+    Options:
+      -n        waits for a single job from the list of IDs, or, if no IDs
+                are supplied, for the next job to complete
+      -p VAR    the process or job identifier of the job is assigned to VAR
+      -f        wait for each ID to terminate rather than to change status
+
+    Exit Status:
+    Returns the status of the last ID; fails if ID is invalid or an invalid
+    option is given.
@@ -10,11 +10,12 @@ options =
         -l, --login               unsuported
         -r                        unsuported
         -s                        unsuported
-        -D                        unsuported
+        -D, --dump-strings        Print the $"..." strings in the script and exit
         -O, +O                    unsuported
         --                        unsuported
         --debugger                unsuported
-        --dimp-po-strings         unsuported
+        --dump-po-strings         Same as -D but in the PO format (without a script,
+                                  the messages of the shell itself)
         --help                    Display this help message and exit
         --init-file FILE          unsuported
         --rcfile FILE             unsuported
@@ -243,3 +244,29 @@ text-version =
 
     本ソフトウェアは、法律で許される範囲において、
     明示的または黙示的ないかなる保証もなく「現状のまま」提供されます。
+
+## Diagnostics
+
+invalid-option = { $opt }: 無効なオプションです
+no-such-file = { $name }: そのようなファイルやディレクトリはありません
+exec-command-not-found = { $name }: コマンドが見つかりません
+exec-invalid-identifier = `{ $name }': 有効な識別子ではありません
+exec-readonly-variable = { $name }: 読み取り専用の変数です
+exec-unbound-variable = { $name }: 未割り当ての変数です
+exec-syntax-error = 予期しないトークン `{ $token }' 周辺に構文エラーがあります
+too-many-arguments = 引数が多すぎます
+numeric-argument-required = { $arg }: 数字の引数が必要です
+not-set = { $name } が設定されていません
+arith-division-by-zero = 0 による除算です (エラーのあるトークンは "{ $token }")
+
+## Help
+
+help-header =
+    これらのシェルコマンドは内部で定義されています。`help' と入力すると一覧が表示されます。
+    `help name' と入力すると `name' という関数のより詳しい説明が得られます。
+    この一覧にないコマンドについては `man -k' または `info' を使用してください。
+help-matching = キーワード `{ $pattern }' に一致するシェルコマンド
+help-no-topics = `{ $pattern }' に一致するヘルプ項目はありません。`help help'、`man -k { $pattern }' または `info { $pattern }' を試してください。
+help-cd = シェルの作業ディレクトリを変更します。
+help-help = 組み込みコマンドの情報を表示します。
+help-pwd = 現在の作業ディレクトリの名前を表示します。
@@ -10,11 +10,12 @@ options =
         -l, --login               unsuported
         -r                        unsuported
         -s                        unsuported
-        -D                        unsuported
+        -D, --dump-strings        Print the $"..." strings in the script and exit
         -O, +O                    unsuported
         --                        unsuported
         --debugger                unsuported
-        --dimp-po-strings         unsuported
+        --dump-po-strings         Same as -D but in the PO format (without a script,
+                                  the messages of the shell itself)
         --help                    Display this help message and exit
         --init-file FILE          unsuported
         --rcfile FILE             unsuported
@@ -8,6 +8,7 @@ pub mod database;
 pub mod history;
 pub mod jobtable;
 pub mod options;
+mod prompt;
 mod file_descs;
 
 use self::completion::{Completion, CompletionEntry};
@@ -16,23 +17,30 @@ use self::options::Options;
 use self::file_descs::FileDescriptors;
 use crate::core::jobtable::JobEntry;
 use crate::elements::substitution::Substitution;
+use crate::i18n::{fl, fl_args};
+use crate::i18n::gettext::DumpFormat;
 use crate::{error, proc_ctrl, signal};
-use nix::sys::signal::Signal;
+use nix::sys::signal::{SigAction, Signal};
 use nix::sys::time::{TimeSpec, TimeVal};
+use nix::time::{clock_gettime, ClockId};
 use nix::unistd::Pid;
 use std::collections::HashMap;
 use std::os::fd::RawFd;
 //use std::os::fd::{FromRawFd, OwnedFd};
 use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
-use std::{env, io, path};
+use std::os::unix::fs::MetadataExt;
+use std::{env, fs, io, path};
 use crate::error::exec::ExecError;
 use crate::file_check;
+use crate::utils::{c_string, file};
 
 pub struct MeasuredTime {
     pub real: TimeSpec,
     pub user: TimeVal,
     pub sys: TimeVal,
+    pub posix: bool,
+    pub shell_start: TimeSpec,
 }
 
 impl Default for MeasuredTime {
@@ -41,6 +49,8 @@ impl Default for MeasuredTime {
             real: TimeSpec::new(0, 0),
             user: TimeVal::new(0, 0),
             sys: TimeVal::new(0, 0),
+            posix: false,
+            shell_start: clock_gettime(ClockId::CLOCK_MONOTONIC).unwrap(),
         }
     }
 }
@@ -52,13 +62,17 @@ type SubstBuiltinFn = fn(&mut ShellCore, &[String], &mut [Substitution]) -> i32;
 pub struct ShellCore {
     pub db: DataBase,
     pub alias_memo: Vec<(String, String)>,
-    pub rewritten_history: HashMap<usize, String>,
     pub history: Vec<String>,
+    pub history_times: Vec<Option<i64>>,
+    pub history_new_lines: usize,
+    pub history_file_lines: usize,
+    pub history_subst: Option<(String, String)>,
+    pub kill_ring: Vec<String>,
     pub builtins: HashMap<String, BuiltinFn>,
     pub subst_builtins: HashMap<String, SubstBuiltinFn>,
     pub sigint: Arc<AtomicBool>,
-    pub trapped: Vec<(Arc<AtomicBool>, String)>,
-    pub traplist: Vec<(i32, String)>,
+    pub traps: HashMap<i32, String>,
+    pub trap_default_actions: HashMap<i32, SigAction>,
     pub is_subshell: bool,
     pub source_function_level: i32,
     pub source_files: Vec<String>,
@@ -68,6 +82,8 @@ pub struct ShellCore {
     pub continue_counter: i32,
     pub return_flag: bool,
     pub compat_bash: bool,
+    pub dump_strings: Option<DumpFormat>,
+    pub command_number: usize,
     pub fds: FileDescriptors,
     pub tty_fd: Option<RawFd>,
     //pub tty_fd: Option<OwnedFd>,
@@ -82,6 +98,11 @@ pub struct ShellCore {
     pub script_name: String,
     pub exit_script: String,
     pub exit_script_run: bool,
+    pub err_script: String,
+    pub debug_script: String,
+    pub return_script: String,
+    pub pseudo_trap_running: bool,
+    pub err_trap_fired: bool,
     pub valid_assoc_expand_once: bool,
     //pub process_sub: Vec<(Pid, RawFd)>,
     pub proc_sub_pid: Vec<Pid>,
@@ -95,11 +116,13 @@ impl ShellCore {
         self.set_builtins();
         signal::ignore(Signal::SIGPIPE);
         signal::ignore(Signal::SIGTSTP);
+        signal::init_dispatcher(self)?;
 
         let _ = self.db.set_param("PS4", "+ ", None);
 
         if file_check::is_tty(0) && self.script_name == "-" {
             self.db.flags += "himH";
+            let _ = self.options.set("history", true);
             let _ = self.db.set_param("PS1", "🍣 ", None);
             let _ = self.db.set_param("PS2", "> ", None);
             self.tty_fd = Some(self.fds.dupfd_cloexec(0, 255)?);
@@ -112,10 +135,11 @@ impl ShellCore {
             .db
             .set_param("HISTFILE", &(home + "/.sush_history"), None);
         let _ = self.db.set_param("HISTFILESIZE", "2000", None);
+        let _ = self.db.set_param("HISTSIZE", "2000", None);
 
         if let Ok("1") = env::var("SUSH_COMPAT_TEST_MODE").as_deref() {
             if self.db.flags.contains('i') {
-                eprintln!("THIS IS BASH COMPATIBILITY TEST MODE");
+                eprintln!("{}", fl("compat-test-mode"));
             }
             self.compat_bash = true;
         };
@@ -152,6 +176,7 @@ impl ShellCore {
         self.set_builtins();
         signal::ignore(Signal::SIGPIPE);
         signal::ignore(Signal::SIGTSTP);
+        signal::init_dispatcher(self)?;
 
         if let Ok("1") = env::var("SUSH_COMPAT_TEST_MODE").as_deref() {
             self.compat_bash = true
@@ -229,6 +254,7 @@ impl ShellCore {
     }
 
     pub fn initialize_as_subshell(&mut self, pid: Pid, pgid: Pid) {
+        signal::init_dispatcher_as_subshell(self);
         signal::restore(Signal::SIGINT);
         signal::restore(Signal::SIGTSTP);
         signal::restore(Signal::SIGPIPE);
@@ -239,13 +265,26 @@ impl ShellCore {
         //self.job_table.clear();
 
         self.exit_script.clear();
+        if !self.options.query("errtrace") {
+            self.err_script.clear();
+        }
+        if !self.options.query("functrace") {
+            self.debug_script.clear();
+            self.return_script.clear();
+        }
     }
 
     pub fn init_current_directory(&mut self) {
         match env::current_dir() {
-            Ok(path) => self.current_dir = Some(path),
+            Ok(mut path) => {
+                if let Some(logical) = Self::logical_pwd(&path) {
+                    path = logical;
+                }
+                self.sync_dirstack(&path);
+                self.current_dir = Some(path);
+            }
             Err(err) => {
-                let msg = format!("pwd: error retrieving current directory: {err:?}");
+                let msg = fl_args("cannot-retrieve-cwd", &[("err", &format!("{err:?}"))]);
                 error::print(&msg, self);
             }
         }
@@ -260,15 +299,33 @@ impl ShellCore {
 
     pub fn set_current_directory(&mut self, path: &path::PathBuf) -> Result<(), io::Error> {
         env::set_current_dir(path)?;
+        self.sync_dirstack(path);
         self.current_dir = Some(path.clone());
         Ok(())
     }
 
+    /* $PWD inherited from the parent is kept if it points to the same directory */
+    fn logical_pwd(physical: &path::Path) -> Option<path::PathBuf> {
+        let pwd = file::name_to_path(&c_string::getenv("PWD")?);
+        let (p, l) = (fs::metadata(physical).ok()?, fs::metadata(&pwd).ok()?);
+        let parent = pwd.components().any(|c| c == path::Component::ParentDir);
+        match pwd.is_absolute() && !parent && p.dev() == l.dev() && p.ino() == l.ino() {
+            true => Some(pwd),
+            false => None,
+        }
+    }
+
+    /* DIRSTACK[0] always shows the current directory */
+    fn sync_dirstack(&mut self, path: &path::Path) {
+        let dir = file::buf_to_name(path);
+        let _ = self.db.set_array_elem("DIRSTACK", &dir, 0, Some(0), false);
+    }
+
     pub fn get_ps4(&mut self) -> String {
         let ps4 = self
-            .db
-            .get_param("PS4")
-            .unwrap_or_default()
+            .make_prompt("PS4")
+            .replace("\\[", "")
+            .replace("\\]", "")
             .trim_end()
             .to_string();
         let mut multi_ps4 = ps4.to_string();
@@ -6,32 +6,41 @@ mod alias;
 mod caller;
 mod cd;
 mod command;
+mod dir_stack;
 pub mod compgen;
 pub mod complete;
 mod compopt;
 mod echo;
-mod exec;
+pub mod exec;
+mod fc;
 mod getopts;
 mod hash;
+mod help;
 mod history;
 mod job_commands;
+mod mapfile;
 mod loop_control;
 pub mod option;
 pub mod variable;
 mod printf;
 mod pwd;
-mod read;
+pub mod read;
 pub mod source;
+mod times;
 mod trap;
 mod type_;
 #[cfg(not(target_os = "macos"))]
 mod ulimit;
+mod umask;
 mod unset;
 
 use crate::elements::expr::arithmetic::ArithmeticExpr;
 use crate::error::exec::ExecError;
 use crate::error::parse::ParseError;
+use crate::i18n::{fl, fl_args};
+use crate::utils::c_string;
 use crate::{exit, Feeder, Script, ShellCore};
+use std::ffi::OsString;
 use std::io::Write;
 use std::process::Command;
 
@@ -41,7 +50,8 @@ pub fn error_(exit_status: i32, name: &str, msg: &str, core: &mut ShellCore) ->
         eprintln!("{}: {}: {}", &shellname, name, msg);
     } else {
         let lineno = core.db.get_param("LINENO").unwrap_or("".to_string());
-        eprintln!("{}: line {}: {}: {}", &shellname, &lineno, name, msg);
+        let line = fl_args("at-line", &[("lineno", &lineno)]);
+        eprintln!("{}: {}: {}: {}", &shellname, &line, name, msg);
     }
     exit_status
 }
@@ -51,7 +61,8 @@ pub fn error(exit_status: i32, name: &str, err: &ExecError, core: &mut ShellCore
 }
 
 pub fn run_external(core: &mut ShellCore, args: &[String], err_msg_cond: fn(i32) -> bool) -> i32 {
-    match Command::new(&args[0]).args(args[1..].to_vec()).output() {
+    let args: Vec<OsString> = args.iter().map(|a| c_string::to_os_string(a)).collect();
+    match Command::new(&args[0]).args(&args[1..]).output() {
         Ok(com) => {
             let exit_status = com.status.code().unwrap_or(127);
             if ! com.stdout.is_empty() {
@@ -65,7 +76,7 @@ pub fn run_external(core: &mut ShellCore, args: &[String], err_msg_cond: fn(i32)
             eprint!("{}: ", &shellname);
             if ! core.db.flags.contains('i') {
                 let lineno = core.db.get_param("LINENO").unwrap_or("".to_string());
-                eprint!("line {}: ", &lineno);
+                eprint!("{}: ", fl_args("at-line", &[("lineno", &lineno)]));
             }
             let _ = std::io::stderr().write_all(&com.stderr);
             exit_status
@@ -97,6 +108,7 @@ impl ShellCore {
         self.builtins
             .insert("continue".to_string(), loop_control::continue_);
         self.builtins.insert("debug".to_string(), debug);
+        self.builtins.insert("dirs".to_string(), dir_stack::dirs);
         self.builtins
             .insert("disown".to_string(), job_commands::disown);
         self.builtins.insert("echo".to_string(), echo::echo);
@@ -104,21 +116,32 @@ impl ShellCore {
         self.builtins.insert("exec".to_string(), exec::exec);
         self.builtins.insert("exit".to_string(), exit);
         self.builtins.insert("false".to_string(), false_);
+        self.builtins.insert("fc".to_string(), fc::fc);
         self.builtins.insert("fg".to_string(), job_commands::fg);
         self.builtins
             .insert("getopts".to_string(), getopts::getopts);
         self.builtins.insert("hash".to_string(), hash::hash);
+        self.builtins.insert("help".to_string(), help::help);
         self.builtins
             .insert("history".to_string(), history::history);
         self.builtins.insert("jobs".to_string(), job_commands::jobs);
         self.builtins.insert("kill".to_string(), job_commands::kill);
         self.builtins.insert("let".to_string(), let_);
+        self.builtins
+            .insert("mapfile".to_string(), mapfile::mapfile);
+        self.builtins.insert("popd".to_string(), dir_stack::popd);
         self.builtins.insert("printf".to_string(), printf::printf);
+        self.builtins.insert("pushd".to_string(), dir_stack::pushd);
         self.builtins.insert("pwd".to_string(), pwd::pwd);
         self.builtins.insert("read".to_string(), read::read);
+        self.builtins
+            .insert("readarray".to_string(), mapfile::mapfile);
         self.builtins
             .insert("return".to_string(), loop_control::return_);
         self.builtins.insert("set".to_string(), option::set);
+        self.builtins
+            .insert("suspend".to_string(), job_commands::suspend);
+        self.builtins.insert("times".to_string(), times::times);
         self.builtins.insert("trap".to_string(), trap::trap);
         self.builtins.insert("type".to_string(), type_::type_);
         self.builtins.insert("shift".to_string(), option::shift);
@@ -132,6 +155,7 @@ impl ShellCore {
                     self.builtins.insert("ulimit".to_string(), ulimit_mac::ulimit);
                 }*/
 
+        self.builtins.insert("umask".to_string(), umask::umask);
         self.builtins.insert("unalias".to_string(), alias::unalias);
         self.builtins.insert("unset".to_string(), unset::unset);
         self.builtins.insert("source".to_string(), source::source);
@@ -170,7 +194,7 @@ pub fn eval(core: &mut ShellCore, args: &[String]) -> i32 {
     feeder.lineno += lineno - 1;
 
     match Script::parse(&mut feeder, core, false) {
-        Ok(Some(mut s)) => {
+        Ok(Some(s)) => {
             core.eval_level += 1;
             let _ = s.exec(core);
             core.eval_level -= 1;
@@ -178,11 +202,10 @@ pub fn eval(core: &mut ShellCore, args: &[String]) -> i32 {
         Err(ParseError::UnexpectedSymbol(t)) => {
             let lineno = core.db.get_param("LINENO").unwrap_or("0".to_string());
             let com = &core.db.position_parameters[0][0];
-            eprintln!(
-                "{}: eval: line {}: syntax error near unexpected token `{}'",
-                com, &lineno, &t
-            );
-            eprintln!("{}: eval: line {}: `{}'", com, &lineno, &script);
+            let msg = fl_args("exec-syntax-error", &[("token", &t)]);
+            let line = fl_args("at-line", &[("lineno", &lineno)]);
+            eprintln!("{}: eval: {}: {}", com, &line, &msg);
+            eprintln!("{}: eval: {}: `{}'", com, &line, &script);
             return 2;
         }
         Err(e) => e.print(core),
@@ -232,7 +255,7 @@ pub fn let_(core: &mut ShellCore, args: &[String]) -> i32 {
 
     for a in &args[1..] {
         match ArithmeticExpr::parse(&mut Feeder::new(&a.replace("$", "\\$")), core, false, "") {
-            Ok(Some(mut a)) => match a.eval(core) {
+            Ok(Some(a)) => match a.eval(core) {
                 Ok(s) => last_result = if s == "0" { 1 } else { 0 },
                 Err(e) => {
                     core.valid_assoc_expand_once = false;
@@ -241,7 +264,7 @@ pub fn let_(core: &mut ShellCore, args: &[String]) -> i32 {
             },
             Ok(None) => {
                 core.valid_assoc_expand_once = false;
-                return error_(1, &args[0], "expression expected", core);
+                return error_(1, &args[0], &fl("expression-expected"), core);
             }
             Err(e) => {
                 core.valid_assoc_expand_once = false;
@@ -280,7 +303,8 @@ pub fn test(core: &mut ShellCore, args: &[String]) -> i32 {
                     eprintln!("{}: {}", &shellname, msg);
                 } else {
                     let lineno = core.db.get_param("LINENO").unwrap_or("".to_string());
-                    eprintln!("{}: line {}: {}", &shellname, &lineno, msg);
+                    let line = fl_args("at-line", &[("lineno", &lineno)]);
+                    eprintln!("{}: {}: {}", &shellname, &line, msg);
                 }
             }
             exit_status
@@ -34,7 +34,7 @@ pub fn alias(core: &mut ShellCore, args: &[String]) -> i32 {
 
 pub fn unalias(core: &mut ShellCore, args: &[String]) -> i32 {
     if args.len() <= 1 {
-        println!("unalias: usage: unalias [-a] name [name ...]");
+        eprintln!("{}", super::help::usage("unalias"));
     }
 
     if args.iter().any(|s| s == "-a") {
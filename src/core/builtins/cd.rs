@@ -2,70 +2,199 @@
 //SPDX-FileCopyrightText: 2023 @caro@mi.shellgei.org
 //SPDX-License-Identifier: BSD-3-Clause
 
-use crate::utils::{arg, file};
-use crate::{error, ShellCore};
+use crate::i18n::{fl, fl_args};
+use crate::utils::{file, file_check};
+use crate::{error, utils, ShellCore};
+use std::env;
+
+fn parse_options(core: &mut ShellCore, args: &mut Vec<String>) -> Result<(bool, bool), i32> {
+    let mut physical = core.options.query("physical");
+    let mut check = false;
+
+    while args.len() > 1 && args[1].starts_with('-') && args[1] != "-" {
+        let opt = args.remove(1);
+        if opt == "--" {
+            break;
+        }
+
+        for c in opt[1..].chars() {
+            match c {
+                'L' => physical = false,
+                'P' => physical = true,
+                'e' => check = true,
+                '@' => {}
+                _ => {
+                    let msg = fl_args("invalid-option", &[("opt", &format!("-{}", c))]);
+                    super::error_(2, &args[0], &msg, core);
+                    eprintln!("{}", super::help::usage("cd"));
+                    return Err(2);
+                }
+            }
+        }
+    }
+
+    Ok((physical, check && physical))
+}
 
 pub fn cd(core: &mut ShellCore, args: &[String]) -> i32 {
     if core.db.flags.contains('r') {
-        return super::error_(1, &args[0], "restricted", core);
+        return super::error_(1, &args[0], &fl("restricted"), core);
     }
 
     let mut args = args.to_owned();
-    arg::consume_arg("--", &mut args);
+    let (physical, check) = match parse_options(core, &mut args) {
+        Ok(opts) => opts,
+        Err(exit_status) => return exit_status,
+    };
 
     if args.len() > 2 {
-        eprintln!("sush: cd: too many arguments");
+        eprintln!("sush: cd: {}", fl("too-many-arguments"));
         return 1;
     }
 
     // only "cd"
     if args.len() == 1 {
-        set_oldpwd(core);
+        if !core.db.exist("HOME") {
+            return super::error_(1, &args[0], &fl_args("not-set", &[("name", "HOME")]), core);
+        }
         let home = core.db.get_param("HOME").unwrap_or_default();
-        return change_directory(core, &home);
+        if home.is_empty() {
+            return 0;
+        }
+        set_oldpwd(core);
+        return change_directory_with(core, &args[0], &home, physical, check);
     }
 
     // cd -
     if args[1] == "-" {
-        return cd_oldpwd(core);
+        return cd_oldpwd(core, physical, check);
     }
 
     // cd /some/dir
     set_oldpwd(core);
-    change_directory(core, &args[1])
+    let found = match search_cdpath(core, &args[1]) {
+        Some(dir) => Some(dir),
+        None => alternative_dir(core, &args[1]),
+    };
+    if let Some(dir) = found {
+        let exit_status = change_directory_with(core, &args[0], &dir, physical, check);
+        if exit_status == 0 {
+            println!("{}", core.db.get_param("PWD").unwrap_or_default());
+        }
+        return exit_status;
+    }
+
+    change_directory_with(core, &args[0], &args[1], physical, check)
 }
 
-fn cd_oldpwd(core: &mut ShellCore) -> i32 {
+fn cd_oldpwd(core: &mut ShellCore, physical: bool, check: bool) -> i32 {
     match core.db.get_param("OLDPWD") {
         Ok(old) => {
             println!("{}", &old);
             set_oldpwd(core);
-            change_directory(core, &old)
+            change_directory_with(core, "cd", &old, physical, check)
         }
         Err(_) => {
-            error::print("cd: OLDPWD not set", core);
+            let msg = fl_args("not-set", &[("name", "OLDPWD")]);
+            error::print(&format!("cd: {}", msg), core);
             1
         }
     }
 }
 
-fn set_oldpwd(core: &mut ShellCore) {
+/* an empty entry of CDPATH means the current directory, which is not printed */
+fn search_cdpath(core: &mut ShellCore, target: &str) -> Option<String> {
+    if target.starts_with('/')
+        || target.starts_with('~')
+        || target == "."
+        || target == ".."
+        || target.starts_with("./")
+        || target.starts_with("../")
+    {
+        return None;
+    }
+
+    let cdpath = core.db.get_param("CDPATH").unwrap_or_default();
+    if cdpath.is_empty() {
+        return None;
+    }
+
+    for entry in cdpath.split(':') {
+        if entry.is_empty() {
+            if file_check::is_dir(&file::buf_to_name(&file::make_absolute_path(core, target))) {
+                return None;
+            }
+            continue;
+        }
+
+        let dir = format!("{}/{}", entry.trim_end_matches('/'), target);
+        if file_check::is_dir(&file::buf_to_name(&file::make_absolute_path(core, &dir))) {
+            return Some(dir);
+        }
+    }
+    None
+}
+
+/* cdable_vars and cdspell */
+fn alternative_dir(core: &mut ShellCore, target: &str) -> Option<String> {
+    if file_check::is_dir(&file::buf_to_name(&file::make_absolute_path(core, target))) {
+        return None;
+    }
+
+    if core.shopts.query("cdable_vars") && utils::is_var(target) && core.db.exist(target) {
+        let value = core.db.get_param(target).unwrap_or_default();
+        if file_check::is_dir(&file::buf_to_name(&file::make_absolute_path(core, &value))) {
+            return Some(value);
+        }
+    }
+
+    if core.shopts.query("cdspell") && core.db.flags.contains('i') {
+        return file::spell_correct(target).filter(|dir| dir != target && file_check::is_dir(dir));
+    }
+    None
+}
+
+pub fn set_oldpwd(core: &mut ShellCore) {
     if let Some(old) = core.get_current_directory() {
         let _ = core
             .db
-            .set_param("OLDPWD", &old.display().to_string(), Some(0));
+            .set_param("OLDPWD", &file::buf_to_name(&old), Some(0));
     };
 }
 
-fn change_directory(core: &mut ShellCore, target: &str) -> i32 {
-    let path = file::make_canonical_path(core, target);
+pub fn change_directory(core: &mut ShellCore, com: &str, target: &str) -> i32 {
+    let physical = core.options.query("physical");
+    change_directory_with(core, com, target, physical, false)
+}
+
+fn change_directory_with(
+    core: &mut ShellCore,
+    com: &str,
+    target: &str,
+    physical: bool,
+    check: bool,
+) -> i32 {
+    let mut path = file::make_canonical_path(core, target);
+    if physical {
+        match file::make_absolute_path(core, target).canonicalize() {
+            Ok(p) => path = p,
+            Err(_) => {
+                let msg = fl_args("no-such-file", &[("name", &format!("{:?}", &path))]);
+                eprintln!("sush: {}: {}", com, msg);
+                return 1;
+            }
+        }
+    }
+
     if core.set_current_directory(&path).is_ok() {
-        let _ = core
-            .db
-            .set_param("PWD", &path.display().to_string(), Some(0));
+        let _ = core.db.set_param("PWD", &file::buf_to_name(&path), Some(0));
+        if check && env::current_dir().is_err() {
+            return 1;
+        }
         0
     } else {
-        eprintln!("sush: cd: {:?}: No such file or directory", &path);
+        let msg = fl_args("no-such-file", &[("name", &format!("{:?}", &path))]);
+        eprintln!("sush: {}: {}", com, msg);
         1
     }
 }
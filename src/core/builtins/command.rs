@@ -3,6 +3,7 @@
 
 use crate::elements::command::simple::SimpleCommand;
 use crate::elements::io::pipe::Pipe;
+use crate::i18n::{fl, fl_args};
 use crate::utils::{arg, file};
 use crate::{error, file_check, proc_ctrl, utils, ShellCore};
 
@@ -12,7 +13,7 @@ pub fn builtin(core: &mut ShellCore, args: &[String]) -> i32 {
     }
 
     if !core.builtins.contains_key(&args[1]) {
-        let msg = format!("{}: not a shell builtin", &args[1]);
+        let msg = fl_args("not-a-shell-builtin", &[("name", &args[1])]);
         return super::error_(1, &args[0], &msg, core);
     }
 
@@ -52,7 +53,7 @@ fn command_v(words: &[String], core: &mut ShellCore, large_v: bool) -> i32 {
             match large_v {
                 true => {
                     println!("{} is a function", &com);
-                    core.db.functions.get_mut(com).unwrap().pretty_print(0);
+                    core.db.functions[com].pretty_print(0);
                 }
                 false => println!("{}", &com),
             }
@@ -69,7 +70,7 @@ fn command_v(words: &[String], core: &mut ShellCore, large_v: bool) -> i32 {
                 false => println!("{}", &com),
             }
         } else if large_v {
-            let msg = format!("command: {com}: not found");
+            let msg = format!("command: {}", fl_args("not-found", &[("name", com)]));
             error::print(&msg, core);
         }
     }
@@ -81,13 +82,18 @@ pub fn command(core: &mut ShellCore, args: &[String]) -> i32 {
     if args.len() > 1 {
         if core.subst_builtins.contains_key(&args[1]) {
             //TODO
-            return super::error_(1, &args[0], "substitution command are not supported", core);
+            return super::error_(1, &args[0], &fl("subst-command-unsupported"), core);
         }
     }
 
     let mut args = arg::dissolve_options(args);
     if core.db.flags.contains('r') && arg::consume_arg("-p", &mut args) {
-        return super::error_(1, &args[0], "-p: restricted", core);
+        return super::error_(
+            1,
+            &args[0],
+            &fl_args("exec-restricted", &[("name", "-p")]),
+            core,
+        );
     }
 
     if args.len() <= 1 {
@@ -117,10 +123,9 @@ pub fn command(core: &mut ShellCore, args: &[String]) -> i32 {
         return core.builtins[&words[0]](core, &words[..]);
     }
 
-    let mut command = SimpleCommand::default();
+    let command = SimpleCommand::default();
     let mut pipe = Pipe::new("".to_string());
-    command.args = words;
-    if let Ok(pid) = command.exec_command(core, &mut pipe) {
+    if let Ok(pid) = command.exec_command(words, &[], core, &mut pipe) {
         proc_ctrl::wait_pipeline(core, vec![pid], false, false);
     }
 
@@ -3,12 +3,12 @@
 
 use crate::elements::word::{path_expansion, tilde_expansion};
 use crate::elements::word::{Word, WordMode};
+use crate::i18n::fl_args;
 use crate::utils;
-use crate::utils::{arg, directory, glob};
+use crate::utils::{arg, c_string, directory, file, glob};
 use crate::{file_check, Feeder, ShellCore};
 use faccess;
 use faccess::PathExt;
-use rev_lines::RevLines;
 use std::env;
 use std::collections::HashSet;
 use std::fs::File;
@@ -35,7 +35,7 @@ pub fn compgen_f(core: &mut ShellCore, args: &[String], dir_only: bool) -> Vec<S
     };
 
     split.push("".to_string());
-    let org_dir = split.join("/");
+    let mut org_dir = split.join("/");
     let mut dir = org_dir.clone();
     if dir.starts_with("~") {
         let mut feeder = Feeder::new(&dir);
@@ -45,6 +45,15 @@ pub fn compgen_f(core: &mut ShellCore, args: &[String], dir_only: bool) -> Vec<S
         }
     }
 
+    if core.shopts.query("dirspell")
+        && !dir.is_empty()
+        && !file_check::is_dir(&dir)
+        && let Some(d) = file::spell_correct(dir.trim_end_matches('/'))
+    {
+        dir = d + "/";
+        org_dir = dir.clone();
+    }
+
     if key.is_empty() {
         let mut files = directory::files(&dir);
         if dir_only {
@@ -126,7 +135,7 @@ fn command_list(target: &String, core: &mut ShellCore) -> Vec<String> {
 pub fn compgen(core: &mut ShellCore, args: &[String]) -> i32 {
     let args = args.to_owned();
     if args.len() <= 1 {
-        eprintln!("sush: {}: still unsupported", &args[0]);
+        eprintln!("sush: {}", fl_args("still-unsupported", &[("name", &args[0])]));
         return 1;
     }
     let mut args = arg::dissolve_options(&args);
@@ -154,20 +163,21 @@ pub fn compgen(core: &mut ShellCore, args: &[String]) -> i32 {
         "-A stopped" => compgen_stopped(core, &args),
         "-W" => {
             if args.len() < 2 {
-                eprintln!("sush: compgen: -W: option requires an argument");
+                eprintln!("sush: compgen: {}", fl_args("option-requires-argument", &[("opt", "-W")]));
                 return 2;
             }
             compgen_large_w(core, &args)
         }
         "-G" => {
             if args.len() < 2 {
-                eprintln!("sush: compgen: -G: option requires an argument");
+                eprintln!("sush: compgen: {}", fl_args("option-requires-argument", &[("opt", "-G")]));
                 return 2;
             }
             compgen_large_g(core, &args)
         }
         _ => {
-            eprintln!("sush: compgen: {}: invalid option", &args[1]);
+            eprintln!("sush: compgen: {}", fl_args("invalid-option", &[("opt", &args[1])]));
+            eprintln!("{}", super::help::usage("compgen"));
             return 2;
         }
     };
@@ -265,7 +275,7 @@ fn compgen_d(core: &mut ShellCore, args: &[String]) -> Vec<String> {
 }
 
 pub fn compgen_e(args: &[String]) -> Vec<String> {
-    let mut envs = env::vars().map(|e| e.0).collect::<Vec<String>>();
+    let mut envs = env::vars_os().map(|e| c_string::from_os_str(&e.0)).collect::<Vec<String>>();
 
     let head = get_head(args, 2);
     if !head.is_empty() {
@@ -276,25 +286,7 @@ pub fn compgen_e(args: &[String]) -> Vec<String> {
 }
 
 pub fn compgen_h(core: &mut ShellCore, _: &[String]) -> Vec<String> {
-    let len = core.history.len();
-    if len >= 10 {
-        return core.history[0..10].to_vec();
-    }
-
-    let mut ans = core.history.to_vec();
-
-    if let Ok(hist_file) = File::open(core.db.get_param("HISTFILE").unwrap_or_default()) {
-        for h in RevLines::new(BufReader::new(hist_file)) {
-            if let Ok(s) = h {
-                ans.push(s)
-            }
-
-            if ans.len() >= 10 {
-                return ans;
-            }
-        }
-    }
-
+    let mut ans: Vec<String> = core.history.iter().take(10).cloned().collect();
     while ans.len() < 10 {
         ans.push("echo Hello World".to_string());
     }
@@ -349,7 +341,7 @@ fn compgen_large_w(core: &mut ShellCore, args: &[String]) -> Vec<String> {
     let mut feeder = Feeder::new(&words);
     while !feeder.is_empty() {
         match Word::parse(&mut feeder, core, None) {
-            Ok(Some(mut w)) => {
+            Ok(Some(w)) => {
                 if let Ok(mut v) = w.eval(core) {
                     ans.append(&mut v);
                 }
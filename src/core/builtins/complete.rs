@@ -2,6 +2,7 @@
 //SPDX-License-Identifier: BSD-3-Clause
 
 use crate::core::{CompletionEntry, HashMap};
+use crate::i18n::fl_args;
 use crate::utils::arg;
 use crate::{builtins, ShellCore};
 
@@ -85,7 +86,7 @@ fn complete_f(core: &mut ShellCore, args: &[String], o_options: &[String]) -> i3
     }
 
     if args.len() <= arg_index {
-        return builtins::error_(2, &args[0], "-F: option requires an argument", core);
+        return builtins::error_(2, &args[0], &fl_args("option-requires-argument", &[("opt", "-F")]), core);
     }
 
     if d_option {
@@ -195,7 +196,7 @@ pub fn complete(core: &mut ShellCore, args: &[String]) -> i32 {
     if arg::consume_arg("-F", &mut args) {
         complete_f(core, &args, &o_options)
     } else {
-        let msg = format!("{}: still unsupported", &args[1]);
+        let msg = fl_args("still-unsupported", &[("name", &args[1])]);
         builtins::error_(1, &args[0], &msg, core)
     }
 }
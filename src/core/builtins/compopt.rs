@@ -2,6 +2,7 @@
 //SPDX-License-Identifier: BSD-3-Clause
 
 use crate::core::CompletionEntry;
+use crate::i18n::fl_args;
 use crate::utils::arg;
 use crate::ShellCore;
 
@@ -47,7 +48,10 @@ fn compopt_print(core: &mut ShellCore, args: &[String]) -> i32 {
         }
         println!("{}", &com);
     } else {
-        eprintln!("sush: compopt: {}: no completion specification", &args[1]);
+        eprintln!(
+            "sush: compopt: {}",
+            fl_args("no-completion-spec", &[("name", &args[1])])
+        );
         return 1;
     }
 
@@ -0,0 +1,233 @@
+//SPDX-FileCopyrightText: 2025 Ryuichi Ueda <ryuichiueda@gmail.com>
+//SPDX-License-Identifier: BSD-3-Clause
+
+use super::{cd, error_};
+use crate::ShellCore;
+use crate::i18n::{fl, fl_args};
+use crate::utils::{arg, file};
+
+/* DIRSTACK[0] is always the current directory */
+fn get_stack(core: &mut ShellCore) -> Vec<String> {
+    let mut stack = core.db.get_vec("DIRSTACK", false).unwrap_or_default();
+    let current = match core.get_current_directory() {
+        Some(p) => file::buf_to_name(&p),
+        None => core.db.get_param("PWD").unwrap_or_default(),
+    };
+
+    match stack.is_empty() {
+        true => stack.push(current),
+        false => stack[0] = current,
+    }
+    stack
+}
+
+fn set_stack(core: &mut ShellCore, stack: Vec<String>) {
+    let _ = core.db.init_array("DIRSTACK", Some(stack), Some(0), false);
+    if let Some(dir) = core.get_current_directory() {
+        let dir = file::buf_to_name(&dir);
+        let _ = core.db.set_array_elem("DIRSTACK", &dir, 0, Some(0), false);
+    }
+}
+
+fn abbreviate(core: &mut ShellCore, dir: &str) -> String {
+    let home = core.db.get_param("HOME").unwrap_or_default();
+    if home.is_empty() || home == "/" {
+        return dir.to_string();
+    }
+
+    match dir.strip_prefix(&home) {
+        Some(rest) if rest.is_empty() || rest.starts_with('/') => format!("~{}", rest),
+        _ => dir.to_string(),
+    }
+}
+
+fn is_index(arg: &str) -> bool {
+    arg.len() > 1 && (arg.starts_with('+') || arg.starts_with('-'))
+}
+
+/* +N counts from the left and -N from the right of the list printed by dirs */
+fn index(stack: &[String], arg: &str) -> Option<usize> {
+    let n = arg[1..]
+        .parse::<usize>()
+        .ok()
+        .filter(|n| *n < stack.len())?;
+    match arg.starts_with('+') {
+        true => Some(n),
+        false => Some(stack.len() - 1 - n),
+    }
+}
+
+fn usage(core: &mut ShellCore, com: &str, msg: &str) -> i32 {
+    error_(2, com, msg, core);
+    eprintln!("{}", super::help::usage(com));
+    2
+}
+
+fn check_index(core: &mut ShellCore, com: &str, stack: &[String], arg: &str) -> Result<usize, i32> {
+    if arg[1..].is_empty() || !arg[1..].chars().all(|c| c.is_ascii_digit()) {
+        return Err(usage(core, com, &fl_args("invalid-number", &[("num", arg)])));
+    }
+    match index(stack, arg) {
+        Some(n) => Ok(n),
+        None if stack.len() == 1 && com != "pushd" => {
+            Err(error_(1, com, &fl("directory-stack-empty"), core))
+        }
+        None => {
+            /* only dirs drops the sign like Bash */
+            let arg = if com == "dirs" { &arg[1..] } else { arg };
+            let msg = fl_args("directory-stack-out-of-range", &[("index", arg)]);
+            Err(error_(1, com, &msg, core))
+        }
+    }
+}
+
+fn print(
+    core: &mut ShellCore,
+    stack: &[String],
+    first: usize,
+    long: bool,
+    per_line: bool,
+    verbose: bool,
+) {
+    let dirs: Vec<String> = stack
+        .iter()
+        .map(|d| match long {
+            true => d.clone(),
+            false => abbreviate(core, d),
+        })
+        .collect();
+
+    if verbose {
+        dirs.iter()
+            .enumerate()
+            .for_each(|(i, d)| println!("{:2}  {}", i + first, d));
+    } else if per_line {
+        dirs.iter().for_each(|d| println!("{}", d));
+    } else {
+        println!("{}", dirs.join(" "));
+    }
+}
+
+fn change_directory(core: &mut ShellCore, com: &str, dir: &str) -> bool {
+    cd::set_oldpwd(core);
+    cd::change_directory(core, com, dir) == 0
+}
+
+pub fn dirs(core: &mut ShellCore, args: &[String]) -> i32 {
+    let mut args = args.to_owned();
+    let mut clear = false;
+    let mut long = false;
+    let mut per_line = false;
+    let mut verbose = false;
+    let mut pos = None;
+
+    let stack = get_stack(core);
+    for a in args.split_off(1) {
+        match a.as_str() {
+            "-c" => clear = true,
+            "-l" => long = true,
+            "-p" => per_line = true,
+            "-v" => verbose = true,
+            _ if is_index(&a) => match check_index(core, &args[0], &stack, &a) {
+                Ok(n) => pos = Some(n),
+                Err(exit_status) => return exit_status,
+            },
+            _ => return usage(core, &args[0], &fl_args("invalid-number", &[("num", &a)])),
+        }
+    }
+
+    if clear {
+        set_stack(core, vec![]);
+        return 0;
+    }
+
+    match pos {
+        Some(n) => print(core, &stack[n..n + 1], n, long, per_line, verbose),
+        None => print(core, &stack, 0, long, per_line, verbose),
+    }
+    0
+}
+
+pub fn pushd(core: &mut ShellCore, args: &[String]) -> i32 {
+    let mut args = args.to_owned();
+    let no_cd = arg::consume_arg("-n", &mut args);
+    arg::consume_arg("--", &mut args);
+
+    let mut stack = get_stack(core);
+    if args.len() == 1 {
+        if no_cd {
+            return 0;
+        }
+        if stack.len() < 2 {
+            return error_(1, &args[0], &fl("no-other-directory"), core);
+        }
+        stack.swap(0, 1);
+    } else if is_index(&args[1]) {
+        match check_index(core, &args[0], &stack, &args[1]) {
+            Ok(n) => stack.rotate_left(n),
+            Err(exit_status) => return exit_status,
+        }
+        if no_cd {
+            set_stack(core, stack);
+            return 0;
+        }
+    } else if no_cd {
+        stack.insert(1, args[1].clone());
+    } else {
+        let old = stack.remove(0);
+        stack.insert(0, args[1].clone());
+        stack.insert(1, old);
+    }
+
+    if !no_cd && !change_directory(core, &args[0], &stack[0].clone()) {
+        return 1;
+    }
+
+    set_stack(core, stack);
+    let stack = get_stack(core);
+    print(core, &stack, 0, false, false, false);
+    0
+}
+
+pub fn popd(core: &mut ShellCore, args: &[String]) -> i32 {
+    let mut args = args.to_owned();
+    let no_cd = arg::consume_arg("-n", &mut args);
+    arg::consume_arg("--", &mut args);
+
+    let mut stack = get_stack(core);
+    let pos = match args.get(1) {
+        None => 0,
+        Some(a) if is_index(a) => match check_index(core, &args[0], &stack, a) {
+            Ok(n) => n,
+            Err(exit_status) => return exit_status,
+        },
+        Some(a) => {
+            let msg = fl_args("invalid-argument", &[("arg", a)]);
+            return usage(core, &args[0], &msg);
+        }
+    };
+
+    if stack.len() < 2 {
+        return error_(1, &args[0], &fl("directory-stack-empty"), core);
+    }
+
+    match (pos, no_cd) {
+        (0, true) => {
+            stack.remove(1);
+        }
+        (0, false) => {
+            if !change_directory(core, &args[0], &stack[1].clone()) {
+                return 1;
+            }
+            stack.remove(0);
+        }
+        _ => {
+            stack.remove(pos);
+        }
+    }
+
+    set_stack(core, stack);
+    let stack = get_stack(core);
+    print(core, &stack, 0, false, false, false);
+    0
+}
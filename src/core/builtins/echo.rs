@@ -5,15 +5,14 @@ use crate::elements::ansi_c_str::AnsiCString;
 use crate::error::exec::ExecError;
 use crate::utils::c_string;
 use crate::{Feeder, ShellCore};
-use std::ffi::CString;
 use std::io;
 use std::io::{stdout, Write};
 
-fn arg_to_c_str(arg: &str, core: &mut ShellCore) -> Result<CString, ExecError> {
+fn arg_to_bytes(arg: &str, core: &mut ShellCore) -> Result<Vec<u8>, ExecError> {
     let mut f = Feeder::new(arg);
     let ans = match AnsiCString::parse(&mut f, core, true) {
-        Ok(Some(mut ansi_c_str)) => c_string::to_carg(&ansi_c_str.eval()),
-        Ok(None) => c_string::to_carg(arg),
+        Ok(Some(mut ansi_c_str)) => c_string::to_bytes(&ansi_c_str.eval()),
+        Ok(None) => c_string::to_bytes(arg),
         Err(e) => return Err(ExecError::ParseError(e)),
     };
 
@@ -55,9 +54,9 @@ pub fn echo(core: &mut ShellCore, args: &[String]) -> i32 {
         first = false;
 
         let bytes = match e_opt {
-            false => c_string::to_carg(a).into_bytes(),
-            true => match arg_to_c_str(a, core) {
-                Ok(v) => v.into_bytes(),
+            false => c_string::to_bytes(a),
+            true => match arg_to_bytes(a, core) {
+                Ok(v) => v,
                 Err(e) => {
                     e.print(core);
                     return 1;
@@ -1,50 +1,236 @@
 //SPDX-FileCopyrightText: 2025 Ryuichi Ueda <ryuichiueda@gmail.com>
 //SPDX-License-Identifier: BSD-3-Clause
 
+use crate::i18n::{fl, fl_args};
+use crate::utils::{c_string, file};
 use crate::{proc_ctrl, ShellCore};
 use nix::errno::Errno;
 use nix::unistd;
 use std::ffi::CString;
 
+#[derive(Debug, Default)]
+struct Options {
+    name: Option<String>,
+    clear_env: bool,
+    login: bool,
+}
+
+impl Options {
+    fn make_argv(&self, args: &[String]) -> Vec<String> {
+        let mut argv = args.to_vec();
+        if let Some(name) = &self.name {
+            argv[0] = name.clone();
+        }
+        if self.login {
+            argv[0].insert(0, '-');
+        }
+        argv
+    }
+}
+
+/* returns the options and the position of the command */
+fn parse_options(args: &[String]) -> Result<(Options, usize), String> {
+    let mut opts = Options::default();
+    let mut pos = 1;
+    while pos < args.len() && args[pos].starts_with('-') && args[pos].len() > 1 {
+        if args[pos] == "--" {
+            pos += 1;
+            break;
+        }
+
+        let mut chars = args[pos][1..].chars();
+        while let Some(c) = chars.next() {
+            match c {
+                'c' => opts.clear_env = true,
+                'l' => opts.login = true,
+                'a' => {
+                    let rest: String = chars.by_ref().collect();
+                    opts.name = match rest.is_empty() {
+                        true => {
+                            pos += 1;
+                            args.get(pos).cloned()
+                        }
+                        false => Some(rest),
+                    };
+                    if opts.name.is_none() {
+                        return Err(fl_args("option-requires-argument", &[("opt", "-a")]));
+                    }
+                }
+                _ => return Err(fl_args("invalid-option", &[("opt", &format!("-{}", c))])),
+            }
+        }
+        pos += 1;
+    }
+    Ok((opts, pos))
+}
+
+/* exec 3>file: the redirections are kept in the shell */
+pub fn has_no_command(args: &[String]) -> bool {
+    matches!(parse_options(args), Ok((_, pos)) if pos >= args.len())
+}
+
 pub fn exec(core: &mut ShellCore, args: &[String]) -> i32 {
-    let args = args.to_owned();
     if core.db.flags.contains('r') {
-        return super::error_(1, &args[0], "restricted", core);
+        return super::error_(1, &args[0], &fl("restricted"), core);
     }
 
-    if args.len() == 1 {
+    let (opts, pos) = match parse_options(args) {
+        Ok(ans) => ans,
+        Err(msg) => {
+            super::error_(2, &args[0], &msg, core);
+            eprintln!("{}", super::help::usage("exec"));
+            return 2;
+        }
+    };
+    if pos >= args.len() {
         return 0;
     }
 
+    let argv = opts.make_argv(&args[pos..]);
+    let err = exec_command(&args[pos], &argv, opts.clear_env);
     if core.db.flags.contains('i') || core.shopts.query("execfail") {
-        exec_command(&args[1..], core, "")
-    } else {
-        proc_ctrl::exec_command(&args[1..], core, "")
+        return report_error(err, &args[pos], core);
     }
+    proc_ctrl::exit_on_exec_error(err, &args[pos], core)
 }
 
-fn exec_command(args: &[String], core: &mut ShellCore, fullpath: &str) -> i32 {
-    let cargs: Vec<CString> = args
-        .iter()
-        .map(|a| CString::new(a.to_string()).unwrap())
-        .collect();
-    let cfullpath = CString::new(fullpath).unwrap();
-
-    if !fullpath.is_empty() {
-        let _ = unistd::execv(&cfullpath, &cargs);
+/* returns only when it fails */
+fn exec_command(command: &str, argv: &[String], clear_env: bool) -> Errno {
+    let cargs = c_string::to_cargs(argv);
+    if !clear_env {
+        let Err(e) = unistd::execvp(&c_string::to_carg(command), &cargs);
+        return e;
     }
-    let result = unistd::execvp(&cargs[0], &cargs);
 
-    match result {
-        Err(Errno::E2BIG) => super::error_(126, &args[0], "Arg list too long", core),
-        Err(Errno::EACCES) => {
-            super::error_(126, &args[0], "cannot execute: Permission denied", core)
-        }
-        Err(Errno::ENOENT) => super::error_(127, &args[0], "No such file or directory", core),
-        Err(e) => {
+    /* the command is searched with PATH before the environment is dropped */
+    let path = match command.contains('/') {
+        true => command.to_string(),
+        false => match file::search_command(command) {
+            Some(p) => p,
+            None => return Errno::ENOENT,
+        },
+    };
+    let Err(e) = unistd::execve(&c_string::to_carg(&path), &cargs, &[] as &[CString]);
+    e
+}
+
+fn report_error(err: Errno, command: &str, core: &mut ShellCore) -> i32 {
+    match err {
+        Errno::E2BIG => super::error_(126, command, &fl("arg-list-too-long"), core),
+        Errno::EACCES => super::error_(126, command, &fl("cannot-execute"), core),
+        Errno::ENOENT => super::error_(127, command, &fl("no-such-file-or-directory"), core),
+        e => {
             let msg = format!("{:?}", &e);
-            super::error_(127, &args[0], &msg, core)
+            super::error_(127, command, &msg, core)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Feeder, Script};
+    use nix::sys::wait::{self, WaitStatus};
+    use nix::unistd::ForkResult;
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn to_args(s: &str) -> Vec<String> {
+        s.split(' ').map(|a| a.to_string()).collect()
+    }
+
+    fn temp_file(name: &str) -> PathBuf {
+        let file = format!("sush-exec-test-{}.{}", name, std::process::id());
+        std::env::temp_dir().join(file)
+    }
+
+    /* runs the script as sush -c does in a child process since exec replaces it */
+    fn run_in_child(script: &str) -> i32 {
+        match unsafe { unistd::fork() }.unwrap() {
+            ForkResult::Child => {
+                for fd in 3..256 {
+                    unsafe { libc::close(fd) };
+                }
+                let mut core = ShellCore::new();
+                let _ = core.configure_c_mode();
+                let mut feeder = Feeder::new(script);
+                if let Ok(Some(s)) = Script::parse(&mut feeder, &mut core, false) {
+                    let _ = s.exec(&mut core);
+                }
+                unsafe { libc::_exit(core.db.exit_status) }
+            }
+            ForkResult::Parent { child } => match wait::waitpid(child, None) {
+                Ok(WaitStatus::Exited(_, status)) => status,
+                _ => -1,
+            },
         }
-        _ => 127,
+    }
+
+    fn run_and_read(name: &str, script: &str) -> (i32, String) {
+        let file = temp_file(name);
+        let status = run_in_child(&script.replace("FILE", file.to_str().unwrap()));
+        let output = fs::read_to_string(&file).unwrap_or_default();
+        let _ = fs::remove_file(&file);
+        (status, output)
+    }
+
+    #[test]
+    fn options_end_at_command() {
+        let args = to_args("exec -c -a name ls -l");
+        let (opts, pos) = parse_options(&args).unwrap();
+        assert!(opts.clear_env && !opts.login);
+        assert_eq!(opts.name.as_deref(), Some("name"));
+        assert_eq!(args[pos..], to_args("ls -l"));
+
+        let args = to_args("exec -la name -- -ls");
+        let (opts, pos) = parse_options(&args).unwrap();
+        assert_eq!(opts.make_argv(&args[pos..]), to_args("-name"));
+    }
+
+    #[test]
+    fn name_attached_to_a() {
+        let args = to_args("exec -lafoo sh");
+        let (opts, pos) = parse_options(&args).unwrap();
+        assert_eq!(opts.make_argv(&args[pos..]), to_args("-foo"));
+    }
+
+    #[test]
+    fn fd_only_exec() {
+        assert!(has_no_command(&to_args("exec")));
+        assert!(has_no_command(&to_args("exec -c")));
+        assert!(!has_no_command(&to_args("exec -c ls")));
+        assert!(!has_no_command(&to_args("exec -x")));
+        assert!(!has_no_command(&to_args("exec -a")));
+    }
+
+    #[test]
+    fn redirects_kept_without_command() {
+        let ans = run_and_read("fd3", "exec 3>FILE; echo ok >&3; exec 3>&-");
+        assert_eq!(ans, (0, "ok\n".to_string()));
+    }
+
+    #[test]
+    fn named_fd_left_open() {
+        /* the signal pipe is placed just under the fd limit, so 10 is free */
+        let ans = run_and_read("named", "exec {fd}>FILE; echo $fd >&$fd");
+        assert_eq!(ans, (0, "10\n".to_string()));
+    }
+
+    #[test]
+    fn clear_environment() {
+        let ans = run_and_read("env", "export SUSH_EXEC_TEST=1; exec -c env >FILE");
+        assert_eq!(ans, (0, String::new()));
+    }
+
+    #[test]
+    fn argv0_given() {
+        let ans = run_and_read("name", "exec -a myname sh -c 'echo $0' >FILE");
+        assert_eq!(ans, (0, "myname\n".to_string()));
+
+        let ans = run_and_read("login", "exec -l sh -c 'echo $0' >FILE");
+        assert_eq!(ans, (0, "-sh\n".to_string()));
+
+        let ans = run_and_read("both", "exec -la myname sh -c 'echo $0' >FILE");
+        assert_eq!(ans, (0, "-myname\n".to_string()));
     }
 }
@@ -0,0 +1,277 @@
+//SPDX-FileCopyrightText: 2024 Ryuichi Ueda <ryuichiueda@gmail.com>
+//SPDX-License-Identifier: BSD-3-Clause
+
+use crate::i18n::{fl, fl_args};
+use crate::utils::{c_string, file};
+use crate::ShellCore;
+use nix::unistd;
+use std::fs;
+use std::fs::File;
+use std::io::Write;
+
+#[derive(Debug, Default)]
+struct Options {
+    editor: Option<String>,
+    list: bool,
+    no_number: bool,
+    reverse: bool,
+    substitute: bool,
+}
+
+fn usage(core: &mut ShellCore, msg: &str) -> i32 {
+    super::error_(2, "fc", msg, core);
+    eprintln!("{}", super::help::usage("fc"));
+    2
+}
+
+fn is_number(arg: &str) -> bool {
+    arg.trim_start_matches('-')
+        .chars()
+        .all(|c| c.is_ascii_digit())
+}
+
+fn parse_options(core: &mut ShellCore, args: &[String]) -> Result<(Options, Vec<String>), i32> {
+    let mut opts = Options::default();
+    let mut i = 1;
+
+    while i < args.len() && args[i].starts_with('-') && args[i].len() > 1 {
+        if is_number(&args[i]) {
+            break;
+        }
+        i += 1;
+        if args[i - 1] == "--" {
+            break;
+        }
+
+        for c in args[i - 1][1..].chars() {
+            match c {
+                'l' => opts.list = true,
+                'n' => opts.no_number = true,
+                'r' => opts.reverse = true,
+                's' => opts.substitute = true,
+                'e' if i < args.len() => {
+                    opts.editor = Some(args[i].clone());
+                    i += 1;
+                }
+                'e' => {
+                    return Err(usage(
+                        core,
+                        &fl_args("option-requires-argument", &[("opt", "-e")]),
+                    ))
+                }
+                _ => {
+                    return Err(usage(
+                        core,
+                        &fl_args("invalid-option", &[("opt", &format!("-{}", c))]),
+                    ))
+                }
+            }
+        }
+    }
+
+    if opts.editor.as_deref() == Some("-") {
+        opts.substitute = true;
+    }
+    Ok((opts, args[i..].to_vec()))
+}
+
+/* the fc command itself is history[0] if it has been recorded */
+fn skip_num(core: &mut ShellCore) -> usize {
+    match core.options.query("history") && !core.history.is_empty() {
+        true => 1,
+        false => 0,
+    }
+}
+
+/* history number of the command before fc */
+fn previous_number(core: &mut ShellCore) -> usize {
+    core.history.len() - skip_num(core)
+}
+
+fn history_number(core: &mut ShellCore, spec: &str) -> Result<usize, i32> {
+    let prev = previous_number(core) as i64;
+    if prev == 0 {
+        return Err(super::error_(
+            1,
+            "fc",
+            &fl("history-spec-out-of-range"),
+            core,
+        ));
+    }
+
+    if let Ok(n) = spec.parse::<i64>() {
+        let n = match n {
+            0 => prev,
+            n if n < 0 => std::cmp::max(prev + 1 + n, 1),
+            n => std::cmp::min(n, prev),
+        };
+        return Ok(n as usize);
+    }
+
+    let len = core.history.len();
+    let skip = skip_num(core);
+    match core.history[skip..]
+        .iter()
+        .position(|h| h.starts_with(spec))
+    {
+        Some(pos) => Ok(len - skip - pos),
+        None => Err(super::error_(1, "fc", &fl("no-command-found"), core)),
+    }
+}
+
+fn entry(core: &mut ShellCore, num: usize) -> String {
+    let pos = core.history.len() - num;
+    core.history[pos].replace("↵ \0", "\n")
+}
+
+/* numbers of the selected entries in the order of output */
+fn range(
+    core: &mut ShellCore,
+    first: Option<&String>,
+    last: Option<&String>,
+    default_first: &str,
+    reverse: bool,
+) -> Result<Vec<usize>, i32> {
+    let first = history_number(core, first.map_or(default_first, |f| f))?;
+    let last = match last {
+        Some(l) => history_number(core, l)?,
+        None if default_first == "-16" => previous_number(core),
+        None => first,
+    };
+
+    let mut nums: Vec<usize> = match first <= last {
+        true => (first..=last).collect(),
+        false => (last..=first).rev().collect(),
+    };
+    if reverse {
+        nums.reverse();
+    }
+    Ok(nums)
+}
+
+fn list(core: &mut ShellCore, opts: &Options, args: &[String]) -> i32 {
+    if previous_number(core) == 0 {
+        return 0;
+    }
+    let nums = match range(core, args.first(), args.get(1), "-16", opts.reverse) {
+        Ok(nums) => nums,
+        Err(exit_status) => return exit_status,
+    };
+
+    for n in nums {
+        let line = entry(core, n);
+        match opts.no_number {
+            true => println!("\t {}", line),
+            false => println!("{}\t {}", n, line),
+        }
+    }
+    0
+}
+
+/* the executed commands replace the fc command in the history */
+fn run(core: &mut ShellCore, script: &str) -> i32 {
+    if core.options.query("history") {
+        for line in script.lines().filter(|l| !l.trim().is_empty()) {
+            core.add_history(line);
+        }
+    }
+
+    eprintln!("{}", script.trim_end_matches('\n'));
+    super::eval(core, &["eval".to_string(), script.to_string()])
+}
+
+fn substitute(core: &mut ShellCore, args: &[String]) -> i32 {
+    let (pat_rep, spec) = match args.first() {
+        Some(a) if a.contains('=') => (a.split_once('='), args.get(1)),
+        _ => (None, args.first()),
+    };
+
+    let num = match history_number(core, spec.map_or("-1", |s| s)) {
+        Ok(n) => n,
+        Err(exit_status) => return exit_status,
+    };
+
+    let mut command = entry(core, num);
+    if let Some((pat, rep)) = pat_rep.filter(|(pat, _)| !pat.is_empty()) {
+        command = command.replace(pat, rep);
+    }
+    super::history::remove_current_command(core);
+    run(core, &command)
+}
+
+fn editor(core: &mut ShellCore, opts: &Options) -> String {
+    if let Some(e) = &opts.editor {
+        return e.clone();
+    }
+    for name in ["FCEDIT", "EDITOR"] {
+        let e = core.db.get_param(name).unwrap_or_default();
+        if !e.is_empty() {
+            return e;
+        }
+    }
+    "vi".to_string()
+}
+
+fn edit(core: &mut ShellCore, opts: &Options, args: &[String]) -> i32 {
+    let nums = match range(core, args.first(), args.get(1), "-1", opts.reverse) {
+        Ok(nums) => nums,
+        Err(exit_status) => return exit_status,
+    };
+
+    let mut text = String::new();
+    for n in nums {
+        text += &entry(core, n);
+        text += "\n";
+    }
+
+    let tmpdir = core.db.get_param("TMPDIR").unwrap_or_default();
+    let tmpdir = match tmpdir.is_empty() {
+        true => "/tmp".to_string(),
+        false => tmpdir,
+    };
+    /* mkstemp creates a new file with mode 0600 and never follows a link */
+    let template = format!("{}/sush-fc.XXXXXX", tmpdir.trim_end_matches('/'));
+    let (fd, path) = match unistd::mkstemp(&file::name_to_path(&template)) {
+        Ok(ans) => ans,
+        Err(_) => {
+            let msg = fl_args("cannot-open-temp-file", &[("file", &template)]);
+            return super::error_(1, "fc", &msg, core);
+        }
+    };
+    let filename = c_string::from_os_str(path.as_os_str());
+    if File::from(fd)
+        .write_all(&c_string::to_bytes(&text))
+        .is_err()
+    {
+        let _ = fs::remove_file(&path);
+        let msg = fl_args("cannot-open-temp-file", &[("file", &filename)]);
+        return super::error_(1, "fc", &msg, core);
+    }
+
+    let command = format!("{} {}", editor(core, opts), super::trap::quote(&filename));
+    let exit_status = super::eval(core, &["eval".to_string(), command]);
+    let script = fs::read(&path).map(|s| c_string::from_bytes(&s));
+    let _ = fs::remove_file(&path);
+    super::history::remove_current_command(core);
+
+    match (exit_status, script) {
+        (0, Ok(script)) if !script.trim().is_empty() => run(core, &script),
+        (0, _) => 0,
+        (n, _) => n,
+    }
+}
+
+pub fn fc(core: &mut ShellCore, args: &[String]) -> i32 {
+    let (opts, args) = match parse_options(core, args) {
+        Ok(parsed) => parsed,
+        Err(exit_status) => return exit_status,
+    };
+
+    if opts.list {
+        return list(core, &opts, &args);
+    }
+    if opts.substitute {
+        return substitute(core, &args);
+    }
+    edit(core, &opts, &args)
+}
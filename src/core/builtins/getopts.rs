@@ -161,7 +161,7 @@ pub fn getopts(core: &mut ShellCore, args: &[String]) -> i32 {
     let _ = core.db.set_param("OPTARG", "", Some(scope));
 
     if args.len() < 3 {
-        error::print("getopts: usage: getopts optstring name [arg ...]", core);
+        error::print(&super::help::usage("getopts"), core);
         return 2;
     }
 
@@ -1,6 +1,7 @@
 //SPDX-FileCopyrightText: 2025 Ryuichi Ueda <ryuichiueda@gmail.com>
 //SPDX-License-Identifier: BSD-3-Clause
 
+use crate::i18n::{fl, fl_args};
 use crate::utils::arg;
 use crate::ShellCore;
 
@@ -31,10 +32,11 @@ pub fn hash(core: &mut ShellCore, args: &[String]) -> i32 {
 
     if arg::consume_arg("-p", &mut args) {
         if args.len() == 1 {
-            return super::error_(1, "hash", "-p: option requires an argument", core);
+            let msg = fl_args("option-requires-argument", &[("opt", "-p")]);
+            return super::error_(1, "hash", &msg, core);
         }
         if args.len() == 2 {
-            return super::error_(1, "hash", "still not implemented", core);
+            return super::error_(1, "hash", &fl("not-implemented"), core);
         }
 
         if let Err(e) = core
@@ -0,0 +1,195 @@
+//SPDX-FileCopyrightText: 2024 Ryuichi Ueda <ryuichiueda@gmail.com>
+//SPDX-License-Identifier: BSD-3-Clause
+
+use crate::i18n::{fl, fl_args};
+use crate::utils::glob;
+use crate::ShellCore;
+
+/* name, message ID of the descriptions, and synopsis */
+const TOPICS: &[(&str, &str, &str)] = &[
+    (":", "colon", ":"),
+    (".", "source", ". filename [arguments]"),
+    ("[", "test", "[ arg... ]"),
+    ("alias", "alias", "alias [-p] [name[=value] ... ]"),
+    ("bg", "bg", "bg [job_spec ...]"),
+    ("bind", "bind", "bind [-lpsvPSVX] [-m keymap] [-f filename] [-q name] [-u name] [-r keyseq] [-x keyseq:shell-command] [keyseq:readline-function or readline-command]"),
+    ("break", "break", "break [n]"),
+    ("builtin", "builtin", "builtin [shell-builtin [arg ...]]"),
+    ("caller", "caller", "caller [expr]"),
+    ("cd", "cd", "cd [-L|[-P [-e]] [-@]] [dir]"),
+    ("command", "command", "command [-pVv] command [arg ...]"),
+    ("compgen", "compgen", "compgen [-abcdefgjksuv] [-o option] [-A action] [-G globpat] [-W wordlist] [-F function] [-C command] [-X filterpat] [-P prefix] [-S suffix] [word]"),
+    ("complete", "complete", "complete [-abcdefgjksuv] [-pr] [-DEI] [-o option] [-A action] [-G globpat] [-W wordlist] [-F function] [-C command] [-X filterpat] [-P prefix] [-S suffix] [name ...]"),
+    ("compopt", "compopt", "compopt [-o|+o option] [-DEI] [name ...]"),
+    ("continue", "continue", "continue [n]"),
+    ("debug", "debug", "debug"),
+    ("declare", "declare", "declare [-aAfFgiIlnrtux] [name[=value] ...] or declare -p [-aAfFilnrtux] [name ...]"),
+    ("dirs", "dirs", "dirs [-clpv] [+N] [-N]"),
+    ("disown", "disown", "disown [-h] [-ar] [jobspec ... | pid ...]"),
+    ("echo", "echo", "echo [-neE] [arg ...]"),
+    ("eval", "eval", "eval [arg ...]"),
+    ("exec", "exec", "exec [-cl] [-a name] [command [argument ...]] [redirection ...]"),
+    ("exit", "exit", "exit [n]"),
+    ("export", "export", "export [-fn] [name[=value] ...] or export -p"),
+    ("false", "false", "false"),
+    ("fc", "fc", "fc [-e ename] [-lnr] [first] [last] or fc -s [pat=rep] [command]"),
+    ("fg", "fg", "fg [job_spec]"),
+    ("getopts", "getopts", "getopts optstring name [arg ...]"),
+    ("hash", "hash", "hash [-lr] [-p pathname] [-dt] [name ...]"),
+    ("help", "help", "help [-dms] [pattern ...]"),
+    ("history", "history", "history [-c] [-d offset] [n] or history -anrw [filename] or history -ps arg [arg...]"),
+    ("jobs", "jobs", "jobs [-lnprs] [jobspec ...] or jobs -x command [args]"),
+    ("kill", "kill", "kill [-s sigspec | -n signum | -sigspec] pid | jobspec ... or kill -l [sigspec]"),
+    ("let", "let", "let arg [arg ...]"),
+    ("local", "local", "local [option] name[=value] ..."),
+    ("mapfile", "mapfile", "mapfile [-d delim] [-n count] [-O origin] [-s count] [-t] [-u fd] [-C callback] [-c quantum] [array]"),
+    ("popd", "popd", "popd [-n] [+N | -N]"),
+    ("printf", "printf", "printf [-v var] format [arguments]"),
+    ("pushd", "pushd", "pushd [-n] [+N | -N | dir]"),
+    ("pwd", "pwd", "pwd [-LP]"),
+    ("read", "read", "read [-ers] [-a array] [-d delim] [-i text] [-n nchars] [-N nchars] [-p prompt] [-t timeout] [-u fd] [name ...]"),
+    ("readarray", "mapfile", "readarray [-d delim] [-n count] [-O origin] [-s count] [-t] [-u fd] [-C callback] [-c quantum] [array]"),
+    ("readonly", "readonly", "readonly [-aAf] [name[=value] ...] or readonly -p"),
+    ("return", "return", "return [n]"),
+    ("set", "set", "set [-abefhkmnptuvxBCEHPT] [-o option-name] [--] [-] [arg ...]"),
+    ("shift", "shift", "shift [n]"),
+    ("shopt", "shopt", "shopt [-su] [optname ...]"),
+    ("source", "source", "source filename [arguments]"),
+    ("suspend", "suspend", "suspend [-f]"),
+    ("test", "test", "test [expr]"),
+    ("times", "times", "times"),
+    ("trap", "trap", "trap [-lp] [[arg] signal_spec ...]"),
+    ("true", "true", "true"),
+    ("type", "type", "type [-afptP] name [name ...]"),
+    ("typeset", "declare", "typeset [-aAfFgiIlnrtux] name[=value] ... or typeset -p [-aAfFilnrtux] [name ...]"),
+    ("ulimit", "ulimit", "ulimit [-SHabcdefiklmnpqrstuvxPRT] [limit]"),
+    ("umask", "umask", "umask [-p] [-S] [mode]"),
+    ("unalias", "unalias", "unalias [-a] name [name ...]"),
+    ("unset", "unset", "unset [-f] [-v] [-n] [name ...]"),
+    ("wait", "wait", "wait [-fn] [-p var] [id ...]"),
+];
+
+pub fn synopsis(name: &str) -> &'static str {
+    TOPICS.iter().find(|t| t.0 == name).map_or("", |t| t.2)
+}
+
+/* the line printed after an invalid option or a missing argument */
+pub fn usage(name: &str) -> String {
+    format!("{}: usage: {}", name, synopsis(name))
+}
+
+fn short_doc(id: &str) -> String {
+    fl(&format!("help-{}", id))
+}
+
+fn long_doc(id: &str) -> Vec<String> {
+    let mut ans = vec![short_doc(id), String::new()];
+    ans.extend(
+        fl(&format!("help-{}-long", id))
+            .lines()
+            .map(|s| s.to_string()),
+    );
+    ans
+}
+
+fn print_list(core: &mut ShellCore) {
+    println!(
+        "Sushi shell (a.k.a. Sush), {} {} - {}",
+        fl("version"),
+        env!("CARGO_PKG_VERSION"),
+        env!("CARGO_BUILD_PROFILE")
+    );
+    println!("{}\n", fl("help-header"));
+
+    let cells: Vec<String> = TOPICS
+        .iter()
+        .filter(|t| core.builtins.contains_key(t.0) || core.subst_builtins.contains_key(t.0))
+        .map(|t| match t.2.chars().count() > 38 {
+            true => t.2.chars().take(38).collect::<String>() + ">",
+            false => t.2.to_string(),
+        })
+        .collect();
+
+    let half = cells.len().div_ceil(2);
+    for i in 0..half {
+        match cells.get(i + half) {
+            Some(right) => println!(" {:<39} {}", cells[i], right),
+            None => println!(" {}", cells[i]),
+        }
+    }
+}
+
+fn print_topic(name: &str, id: &str, synopsis: &str, opt: char) {
+    match opt {
+        'd' => println!("{} - {}", name, short_doc(id)),
+        's' => println!("{}: {}", name, synopsis),
+        'm' => {
+            println!("NAME\n    {} - {}\n", name, short_doc(id));
+            println!("SYNOPSIS\n    {}\n", synopsis);
+            println!("DESCRIPTION");
+            for line in long_doc(id) {
+                println!("    {}", line);
+            }
+            println!("\nSEE ALSO\n    sush(1)\n");
+        }
+        _ => {
+            println!("{}: {}", name, synopsis);
+            for line in long_doc(id) {
+                println!("    {}", line);
+            }
+        }
+    }
+}
+
+fn matches(name: &str, pattern: &str, extglob: bool) -> bool {
+    name.starts_with(pattern) || glob::parse_and_compare(name, pattern, extglob)
+}
+
+pub fn help(core: &mut ShellCore, args: &[String]) -> i32 {
+    let mut opt = ' ';
+    let mut pos = 1;
+    while pos < args.len() && args[pos].starts_with('-') && args[pos].len() > 1 {
+        if args[pos] == "--" {
+            pos += 1;
+            break;
+        }
+        for c in args[pos][1..].chars() {
+            match c {
+                'd' | 'm' | 's' => opt = c,
+                _ => {
+                    let msg = fl_args("invalid-option", &[("opt", &format!("-{}", c))]);
+                    super::error_(2, &args[0], &msg, core);
+                    eprintln!("{}", usage("help"));
+                    return 2;
+                }
+            }
+        }
+        pos += 1;
+    }
+
+    if pos >= args.len() {
+        print_list(core);
+        return 0;
+    }
+
+    let extglob = core.shopts.query("extglob");
+    let mut exit_status = 0;
+    for pattern in &args[pos..] {
+        if pattern.contains(['*', '?', '[']) {
+            println!("{}\n", fl_args("help-matching", &[("pattern", pattern)]));
+        }
+
+        let found: Vec<&(&str, &str, &str)> = TOPICS
+            .iter()
+            .filter(|t| matches(t.0, pattern, extglob))
+            .collect();
+        if found.is_empty() {
+            let msg = fl_args("help-no-topics", &[("pattern", pattern)]);
+            exit_status = super::error_(1, &args[0], &msg, core);
+        }
+        for t in found {
+            print_topic(t.0, t.1, t.2, opt);
+        }
+    }
+    exit_status
+}
@@ -1,51 +1,219 @@
 //SPDX-FileCopyrightText: 2024 Ryuichi Ueda <ryuichiueda@gmail.com>
 //SPDX-License-Identifier: BSD-3-Clause
 
-use crate::utils::arg;
+use crate::core::history::expansion;
+use crate::i18n::{fl, fl_args};
+use crate::utils::clock;
 use crate::ShellCore;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
 
-pub fn history_c(core: &mut ShellCore) -> i32 {
-    core.rewritten_history.clear();
-    core.history.clear();
+fn usage(core: &mut ShellCore, msg: &str) -> i32 {
+    super::error_(2, "history", msg, core);
+    eprintln!("{}", super::help::usage("history"));
+    2
+}
+
+/* removes the history command itself as -s and -p of Bash */
+pub fn remove_current_command(core: &mut ShellCore) {
+    if core.options.query("history") && !core.history.is_empty() {
+        core.delete_history(0);
+    }
+}
+
+/* converts an offset of -d into a position from the newest entry */
+fn position(core: &mut ShellCore, offset: &str) -> Result<usize, i32> {
+    let len = core.history.len() as i64;
+    let n = match offset.parse::<i64>() {
+        Ok(n) => n,
+        Err(_) => {
+            let msg = fl_args("history-position-out-of-range", &[("pos", offset)]);
+            return Err(super::error_(1, "history", &msg, core));
+        }
+    };
+
+    let from_oldest = match n < 0 {
+        true => len + n,
+        false => n - 1,
+    };
+    if from_oldest < 0 || from_oldest >= len {
+        let msg = fl_args("history-position-out-of-range", &[("pos", offset)]);
+        return Err(super::error_(1, "history", &msg, core));
+    }
+    Ok((len - 1 - from_oldest) as usize)
+}
+
+fn delete(core: &mut ShellCore, offset: &str) -> i32 {
+    let range = match offset.char_indices().skip(1).find(|(_, c)| *c == '-') {
+        Some((p, _)) => (&offset[..p], &offset[p + 1..]),
+        None => (offset, offset),
+    };
+
+    let newest = match position(core, range.1) {
+        Ok(n) => n,
+        Err(exit_status) => return exit_status,
+    };
+    let oldest = match range.0 == range.1 {
+        true => newest,
+        false => match position(core, range.0) {
+            Ok(n) => n,
+            Err(exit_status) => return exit_status,
+        },
+    };
+    if newest > oldest {
+        let msg = fl_args("history-position-out-of-range", &[("pos", offset)]);
+        return super::error_(1, "history", &msg, core);
+    }
+
+    for pos in (newest..=oldest).rev() {
+        core.delete_history(pos);
+    }
     0
 }
 
+fn file_operation(core: &mut ShellCore, op: char, filename: Option<&String>) -> i32 {
+    let filename = core.history_file(filename);
+    let ok = match op {
+        'a' => {
+            let ok = core.write_history(&filename, core.history_new_lines, true);
+            if ok {
+                core.history_file_lines += core.history_new_lines;
+                core.history_new_lines = 0;
+            }
+            ok
+        }
+        'n' => {
+            let skip = core.history_file_lines;
+            core.read_history_file(&filename, skip)
+        }
+        'r' => core.read_history_file(&filename, 0),
+        _ => {
+            let ok = core.write_history(&filename, core.history.len(), false);
+            if ok {
+                core.history_file_lines = core.history.len();
+                core.history_new_lines = 0;
+            }
+            ok
+        }
+    };
+
+    match ok {
+        true => 0,
+        false => {
+            let msg = fl_args("cannot-access-history-file", &[("file", &filename)]);
+            super::error_(1, "history", &msg, core)
+        }
+    }
+}
+
+fn print(core: &mut ShellCore, num: Option<usize>) {
+    let len = core.history.len();
+    let num = num.unwrap_or(len).min(len);
+    let format = core.db.get_param("HISTTIMEFORMAT").ok();
+    let with_time = core.db.exist("HISTTIMEFORMAT");
+
+    for pos in (0..num).rev() {
+        let time = match (with_time, core.history_times[pos]) {
+            (false, _) => String::new(),
+            (true, Some(t)) => clock::strftime(format.as_deref().unwrap_or_default(), t),
+            (true, None) => "??".to_string(),
+        };
+        let line = core.history[pos].replace("↵ \0", "\n");
+        println!("{:5}  {}{}", len - pos, time, line);
+    }
+}
+
 pub fn history(core: &mut ShellCore, args: &[String]) -> i32 {
-    let args = args.to_owned();
-    let mut args = arg::dissolve_options(&args);
-    if arg::consume_arg("-c", &mut args) {
-        return history_c(core);
+    let mut flags = String::new();
+    let mut offsets = vec![];
+    let mut i = 1;
+
+    while i < args.len() && args[i].starts_with('-') && args[i].len() > 1 {
+        i += 1;
+        if args[i - 1] == "--" {
+            break;
+        }
+
+        for c in args[i - 1][1..].chars() {
+            match c {
+                'c' | 'a' | 'n' | 'r' | 'w' | 'p' | 's' => flags.push(c),
+                'd' if i < args.len() => {
+                    offsets.push(args[i].clone());
+                    i += 1;
+                }
+                'd' => return usage(core, &fl_args("option-requires-argument", &[("opt", "-d")])),
+                _ => {
+                    return usage(
+                        core,
+                        &fl_args("invalid-option", &[("opt", &format!("-{}", c))]),
+                    )
+                }
+            }
+        }
+
+        if flags.contains('p') || flags.contains('s') {
+            break;
+        }
     }
+    let rest = &args[i..];
 
-    if args.len() > 1 {
-        let msg = format!("{}: invalid option", &args[1]);
+    if flags.matches(['a', 'n', 'r', 'w']).count() > 1 {
+        let msg = fl_args("cannot-use-more-than-one", &[("opts", "-anrw")]);
         return super::error_(1, "history", &msg, core);
     }
 
-    let mut number = 1;
+    if flags.contains('c') {
+        core.clear_history();
+    }
+
+    for offset in &offsets {
+        let exit_status = delete(core, offset);
+        if exit_status != 0 {
+            return exit_status;
+        }
+    }
+
+    if let Some(op) = flags.chars().find(|c| "anrw".contains(*c)) {
+        return file_operation(core, op, rest.first());
+    }
 
-    let filename = core.db.get_param("HISTFILE").unwrap_or_default();
-    if filename.is_empty() {
+    if flags.contains('s') {
+        remove_current_command(core);
+        core.push_history(&rest.join(" "), clock::get_epochseconds().parse().ok());
+        core.history_new_lines += 1;
         return 0;
     }
 
-    let file = match File::open(&filename) {
-        Ok(f) => f,
-        _ => return 0,
-    };
+    if flags.contains('p') {
+        remove_current_command(core);
+        let mut exit_status = 0;
+        for a in rest {
+            match expansion::expand(core, a) {
+                Ok((line, _)) => println!("{}", line),
+                Err(_) => {
+                    let msg = fl_args("history-expansion-failed", &[("arg", a)]);
+                    exit_status = super::error_(1, "history", &msg, core);
+                }
+            }
+        }
+        return exit_status;
+    }
 
-    let f = BufReader::new(file);
-    for line in f.lines() {
-        println!("{:5} {}", number, &line.unwrap());
-        number += 1;
+    if !flags.is_empty() || !offsets.is_empty() {
+        return 0;
     }
 
-    for h in core.history.iter().rev() {
-        println!("{:5} {}", number, &h);
-        number += 1;
+    if rest.len() > 1 {
+        return super::error_(1, "history", &fl("too-many-arguments"), core);
     }
 
+    let num = match rest.first().map(|n| n.parse::<usize>()) {
+        None => None,
+        Some(Ok(n)) => Some(n),
+        Some(Err(_)) => {
+            let msg = fl_args("numeric-argument-required", &[("arg", &rest[0])]);
+            return super::error_(1, "history", &msg, core);
+        }
+    };
+
+    print(core, num);
     0
 }
@@ -3,6 +3,7 @@
 
 use libc;
 use crate::core::JobEntry;
+use crate::i18n::{fl, fl_args};
 use crate::utils::arg;
 use crate::ShellCore;
 use crate::{signal, utils};
@@ -33,11 +34,13 @@ pub fn bg(core: &mut ShellCore, args: &[String]) -> i32 {
 
     let mut args = arg::dissolve_options(&args);
     if !core.db.flags.contains('m') {
-        return super::error_(1, &args[0], "no job control", core);
+        return super::error_(1, &args[0], &fl("no-job-control"), core);
     }
 
     if arg::consume_arg("-s", &mut args) {
-        return super::error_(1, &args[0], "-s: invalid option", core);
+        let _ = super::error_(1, &args[0], &fl_args("invalid-option", &[("opt", "-s")]), core);
+        eprintln!("{}", super::help::usage(&args[0]));
+        return 1;
     }
 
     let pos = match args.len() {
@@ -54,12 +57,12 @@ pub fn bg(core: &mut ShellCore, args: &[String]) -> i32 {
             let id = core.job_table[p].id;
 
             if core.job_table[p].no_control {
-                let msg = format!("job {} started without job control", &id);
+                let msg = fl_args("job-without-job-control", &[("id", &id.to_string())]);
                 return super::error_(1, &args[0], &msg, core);
             }
 
             if core.job_table[p].display_status == "Running" {
-                let msg = format!("job {} already in background", &id);
+                let msg = fl_args("job-already-in-background", &[("id", &id.to_string())]);
                 return super::error_(0, &args[0], &msg, core);
             }
 
@@ -81,11 +84,13 @@ pub fn fg(core: &mut ShellCore, args: &[String]) -> i32 {
     let args = args.to_owned();
     let mut args = arg::dissolve_options(&args);
     if !core.db.flags.contains('m') {
-        return super::error_(1, &args[0], "no job control", core);
+        return super::error_(1, &args[0], &fl("no-job-control"), core);
     }
 
     if arg::consume_arg("-s", &mut args) {
-        return super::error_(1, &args[0], "-s: invalid option", core);
+        let _ = super::error_(1, &args[0], &fl_args("invalid-option", &[("opt", "-s")]), core);
+        eprintln!("{}", super::help::usage(&args[0]));
+        return 1;
     }
 
     let id = if args.len() == 1 {
@@ -109,7 +114,7 @@ pub fn fg(core: &mut ShellCore, args: &[String]) -> i32 {
 
     if core.job_table[pos].no_control {
         let id = core.job_table[pos].id;
-        let msg = format!("job {} started without job control", &id);
+        let msg = fl_args("job-without-job-control", &[("id", &id.to_string())]);
         return super::error_(1, &args[0], &msg, core);
     }
 
@@ -146,14 +151,12 @@ pub fn fg(core: &mut ShellCore, args: &[String]) -> i32 {
 fn jobspec_to_array_pos(core: &mut ShellCore, com: &str, jobspec: &str) -> Option<usize> {
     let poss = jobspec_to_array_poss(core, jobspec);
     if poss.is_empty() {
-        let msg = format!("{}: no such job", &jobspec);
+        let msg = fl_args("no-such-job", &[("spec", jobspec)]);
         super::error_(127, com, &msg, core);
         return None;
     } else if poss.len() > 1 {
-        let msg = format!(
-            "{}: ambiguous job spec",
-            jobspec.strip_prefix('%').unwrap_or(jobspec)
-        );
+        let spec = jobspec.strip_prefix('%').unwrap_or(jobspec);
+        let msg = fl_args("ambiguous-job-spec", &[("spec", spec)]);
         super::error_(127, com, &msg, core);
         return None;
     }
@@ -232,16 +235,14 @@ pub fn jobs(core: &mut ShellCore, args: &[String]) -> i32 {
     let poss = jobspec_to_array_poss(core, &jobspec);
 
     if poss.is_empty() {
-        let msg = format!("{}: no such job", &jobspec);
+        let msg = fl_args("no-such-job", &[("spec", &jobspec)]);
         return super::error_(127, "jobs", &msg, core);
     }
     if poss.len() > 1 && !jobspec.is_empty() {
-        let msg = format!(
-            "{}: ambiguous job spec",
-            jobspec.strip_prefix('%').unwrap_or(&jobspec)
-        );
+        let spec = jobspec.strip_prefix('%').unwrap_or(&jobspec);
+        let msg = fl_args("ambiguous-job-spec", &[("spec", spec)]);
         super::error_(127, "jobs", &msg, core);
-        let msg = format!("{}: no such job", &jobspec);
+        let msg = fl_args("no-such-job", &[("spec", &jobspec)]);
         return super::error_(127, "jobs", &msg, core);
     }
 
@@ -411,7 +412,7 @@ fn wait_a_job(
 ) -> (i32, bool) {
     if core.job_table.len() < pos {
         return (
-            super::error_(127, "wait", "invalpos jobpos", core),
+            super::error_(127, "wait", &fl("invalid-job-position"), core),
             false,
         );
     }
@@ -532,7 +533,7 @@ fn wait_n(
 pub fn wait(core: &mut ShellCore, args: &[String]) -> i32 {
     let args = args.to_owned();
     if core.is_subshell {
-        super::error_(127, &args[0], "called from subshell", core);
+        super::error_(127, &args[0], &fl("called-from-subshell"), core);
     }
 
     if args.len() <= 1 {
@@ -580,7 +581,7 @@ pub fn kill(core: &mut ShellCore, args: &[String]) -> i32 {
             if let Some(pos) = jobspec_to_array_pos(core, &com, arg) {
                 *arg = core.job_table[pos].pids[0].to_string();
             } else {
-                let msg = format!("{}: no such job", &arg);
+                let msg = fl_args("no-such-job", &[("spec", arg)]);
                 return super::error_(127, "jobs", &msg, core);
             }
         }
@@ -619,9 +620,9 @@ pub fn disown(core: &mut ShellCore, args: &[String]) -> i32 {
 
     for a in &args[1..] {
         if a.starts_with("-") {
-            let msg = format!("{}: invalid option", &a);
+            let msg = fl_args("invalid-option", &[("opt", a)]);
             super::error_(127, &args[0], &msg, core);
-            eprintln!("disown: usage: disown [-h] [-ar] [jobspec ... | pid ...]");
+            eprintln!("{}", super::help::usage("disown"));
             return 127;
         }
     }
@@ -638,3 +639,28 @@ pub fn disown(core: &mut ShellCore, args: &[String]) -> i32 {
 
     0
 }
+
+pub fn suspend(core: &mut ShellCore, args: &[String]) -> i32 {
+    let mut args = args.to_owned();
+    let force = arg::consume_arg("-f", &mut args);
+
+    if let Some(a) = args.get(1).filter(|a| a.starts_with('-') && *a != "--") {
+        let msg = fl_args("invalid-option", &[("opt", a)]);
+        super::error_(2, &args[0], &msg, core);
+        eprintln!("{}", super::help::usage("suspend"));
+        return 2;
+    }
+
+    if !force && core.shopts.query("login_shell") {
+        return super::error_(1, &args[0], &fl("cannot-suspend-login-shell"), core);
+    }
+    if !force && core.db.flags.contains('r') {
+        return super::error_(1, &args[0], &fl("restricted"), core);
+    }
+    if !core.db.flags.contains('m') {
+        return super::error_(1, &args[0], &fl("cannot-suspend-no-job-control"), core);
+    }
+
+    let _ = nix::sys::signal::killpg(unistd::getpgrp(), Signal::SIGSTOP);
+    0
+}
@@ -1,12 +1,13 @@
 //SPDX-FileCopyrightText: 2024 Ryuichi Ueda <ryuichiueda@gmail.com>
 //SPDX-License-Identifier: BSD-3-Clause
 
+use crate::i18n::{fl, fl_args};
 use crate::ShellCore;
 
 pub fn return_(core: &mut ShellCore, args: &[String]) -> i32 {
     let args = args.to_owned();
     if core.source_function_level <= 0 {
-        eprintln!("sush: return: can only `return' from a function or sourced script");
+        eprintln!("sush: return: {}", fl("return-outside-function"));
         return 2;
     }
     core.return_flag = true;
@@ -17,14 +18,17 @@ pub fn return_(core: &mut ShellCore, args: &[String]) -> i32 {
         return n % 256;
     }
 
-    eprintln!("sush: return: {}: numeric argument required", args[1]);
+    eprintln!(
+        "sush: return: {}",
+        fl_args("numeric-argument-required", &[("arg", &args[1])])
+    );
     2
 }
 
 pub fn break_(core: &mut ShellCore, args: &[String]) -> i32 {
     let args = args.to_owned();
     if core.loop_level <= 0 {
-        eprintln!("sush: break: only meaningful in a `for', `while', or `until' loop");
+        eprintln!("sush: break: {}", fl("only-meaningful-in-loop"));
         return 0;
     }
 
@@ -38,12 +42,18 @@ pub fn break_(core: &mut ShellCore, args: &[String]) -> i32 {
             if n > 0 {
                 core.break_counter += n - 1;
             } else {
-                eprintln!("sush: break: {}: loop count out of range", args[1]);
+                eprintln!(
+                    "sush: break: {}",
+                    fl_args("loop-count-out-of-range", &[("count", &args[1])])
+                );
                 return 1;
             }
         }
         Err(_) => {
-            eprintln!("sush: break: {}: numeric argument required", args[1]);
+            eprintln!(
+                "sush: break: {}",
+                fl_args("numeric-argument-required", &[("arg", &args[1])])
+            );
             return 128;
         }
     };
@@ -53,7 +63,7 @@ pub fn break_(core: &mut ShellCore, args: &[String]) -> i32 {
 pub fn continue_(core: &mut ShellCore, args: &[String]) -> i32 {
     let args = args.to_owned();
     if core.loop_level <= 0 {
-        eprintln!("sush: continue: only meaningful in a `for', `while', or `until' loop");
+        eprintln!("sush: continue: {}", fl("only-meaningful-in-loop"));
         return 0;
     }
 
@@ -69,12 +79,18 @@ pub fn continue_(core: &mut ShellCore, args: &[String]) -> i32 {
                 //core.continue_counter += n - 1;
                 core.continue_counter = n;
             } else {
-                eprintln!("sush: continue: {}: loop count out of range", args[1]);
+                eprintln!(
+                    "sush: continue: {}",
+                    fl_args("loop-count-out-of-range", &[("count", &args[1])])
+                );
                 return 1;
             }
         }
         Err(_) => {
-            eprintln!("sush: continue: {}: numeric argument required", args[1]);
+            eprintln!(
+                "sush: continue: {}",
+                fl_args("numeric-argument-required", &[("arg", &args[1])])
+            );
             return 128;
         }
     };
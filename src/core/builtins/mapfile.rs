@@ -0,0 +1,217 @@
+//SPDX-FileCopyrightText: 2025 Ryuichi Ueda <ryuichiueda@gmail.com>
+//SPDX-License-Identifier: BSD-3-Clause
+
+use super::error_;
+use crate::i18n::fl_args;
+use crate::{utils, ShellCore};
+use nix::fcntl::{fcntl, FcntlArg};
+use std::os::fd::BorrowedFd;
+
+struct Options {
+    name: String,
+    delim: u8,
+    count: usize,
+    origin: usize,
+    clear: bool,
+    skip: usize,
+    fd: i32,
+    callback: Option<String>,
+    quantum: usize,
+    trim: bool,
+}
+
+fn parse_num(
+    core: &mut ShellCore,
+    com: &str,
+    value: &str,
+    msg: fn(&str) -> String,
+) -> Result<usize, i32> {
+    match value.parse::<usize>() {
+        Ok(n) => Ok(n),
+        Err(_) => Err(error_(1, com, &msg(value), core)),
+    }
+}
+
+fn usage_error(core: &mut ShellCore, com: &str, msg: &str) -> i32 {
+    error_(2, com, msg, core);
+    eprintln!("{}", super::help::usage(com));
+    2
+}
+
+fn parse_options(core: &mut ShellCore, args: &[String]) -> Result<Options, i32> {
+    let com = args[0].clone();
+    let mut opts = Options {
+        name: "MAPFILE".to_string(),
+        delim: b'\n',
+        count: 0,
+        origin: 0,
+        clear: true,
+        skip: 0,
+        fd: 0,
+        callback: None,
+        quantum: 5000,
+        trim: false,
+    };
+
+    let mut i = 1;
+    while i < args.len() && args[i].starts_with('-') && args[i].len() > 1 {
+        i += 1;
+        let word = &args[i - 1];
+        if word == "--" {
+            break;
+        }
+
+        /* the value of an option is the rest of the word or the next arg */
+        for (pos, c) in word.char_indices().skip(1) {
+            if c == 't' {
+                opts.trim = true;
+                continue;
+            }
+            if !"dnOsuCc".contains(c) {
+                let msg = fl_args("invalid-option", &[("opt", &format!("-{c}"))]);
+                return Err(usage_error(core, &com, &msg));
+            }
+
+            let value = match &word[pos + 1..] {
+                "" if i < args.len() => {
+                    i += 1;
+                    args[i - 1].clone()
+                }
+                "" => {
+                    let msg = fl_args("option-requires-argument", &[("opt", &format!("-{c}"))]);
+                    return Err(usage_error(core, &com, &msg));
+                }
+                rest => rest.to_string(),
+            };
+
+            match c {
+                'd' => opts.delim = value.bytes().next().unwrap_or(0),
+                'n' => {
+                    opts.count = parse_num(core, &com, &value, |s| {
+                        fl_args("invalid-line-count", &[("num", s)])
+                    })?
+                }
+                'O' => {
+                    opts.origin = parse_num(core, &com, &value, |s| {
+                        fl_args("invalid-array-origin", &[("num", s)])
+                    })?;
+                    opts.clear = false;
+                }
+                's' => {
+                    opts.skip = parse_num(core, &com, &value, |s| {
+                        fl_args("invalid-line-count", &[("num", s)])
+                    })?
+                }
+                'u' => {
+                    opts.fd = parse_num(core, &com, &value, |s| {
+                        fl_args("invalid-fd-spec", &[("fd", s)])
+                    })? as i32
+                }
+                'C' => opts.callback = Some(value),
+                _ => {
+                    opts.quantum = parse_num(core, &com, &value, |s| {
+                        fl_args("invalid-callback-quantum", &[("num", s)])
+                    })?
+                }
+            }
+            break;
+        }
+    }
+
+    if opts.quantum == 0 {
+        let msg = fl_args("invalid-callback-quantum", &[("num", "0")]);
+        return Err(error_(1, &com, &msg, core));
+    }
+
+    if let Some(name) = args.get(i) {
+        opts.name = name.clone();
+    }
+    if !utils::is_var(&opts.name) {
+        let msg = fl_args("exec-invalid-identifier", &[("name", &opts.name)]);
+        return Err(error_(1, &com, &msg, core));
+    }
+
+    let fd = opts.fd;
+    if fcntl(unsafe { BorrowedFd::borrow_raw(fd) }, FcntlArg::F_GETFD).is_err() {
+        let msg = fl_args("invalid-fd", &[("fd", &fd.to_string())]);
+        return Err(error_(1, &com, &msg, core));
+    }
+
+    Ok(opts)
+}
+
+fn call_back(core: &mut ShellCore, callback: &str, index: usize, line: &str) {
+    let line = format!("'{}'", line.replace('\'', "'\\''"));
+    let script = format!("{} {} {}", callback, index, line);
+    super::eval(core, &["eval".to_string(), script]);
+}
+
+pub fn mapfile(core: &mut ShellCore, args: &[String]) -> i32 {
+    let opts = match parse_options(core, args) {
+        Ok(o) => o,
+        Err(exit_status) => return exit_status,
+    };
+
+    if opts.clear
+        && let Err(e) = core.db.init_array(&opts.name, Some(vec![]), None, false)
+    {
+        return super::error(1, &args[0], &e, core);
+    }
+
+    for _ in 0..opts.skip {
+        if utils::read_line_unbuffered(opts.fd, opts.delim).is_err() {
+            return 0;
+        }
+    }
+
+    let mut index = opts.origin;
+    let mut num = 0;
+    while opts.count == 0 || num < opts.count {
+        let mut line = match utils::read_line_unbuffered(opts.fd, opts.delim) {
+            Ok(line) => line,
+            Err(_) => break,
+        };
+
+        if opts.trim && line.as_bytes().last() == Some(&opts.delim) {
+            line.pop();
+        }
+
+        if let Some(callback) = &opts.callback
+            && (num + 1) % opts.quantum == 0
+        {
+            call_back(core, callback, index, &line);
+        }
+
+        if let Err(e) = core.db.set_array_elem(&opts.name, &line, index as isize, None, false) {
+            return super::error(1, &args[0], &e, core);
+        }
+        index += 1;
+        num += 1;
+    }
+
+    0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_args(s: &str) -> Vec<String> {
+        s.split(' ').map(|a| a.to_string()).collect()
+    }
+
+    #[test]
+    fn attached_values() {
+        let mut core = ShellCore::new();
+        let opts = parse_options(&mut core, &to_args("mapfile -d, -tn2 -s1 -u0 -O3 arr")).unwrap();
+        assert_eq!(opts.delim, b',');
+        assert!(opts.trim && !opts.clear);
+        assert_eq!((opts.count, opts.skip, opts.fd, opts.origin), (2, 1, 0, 3));
+        assert_eq!(opts.name, "arr");
+
+        let opts = parse_options(&mut core, &to_args("mapfile -n 2 -C cb -c1")).unwrap();
+        assert_eq!((opts.count, opts.quantum), (2, 1));
+        assert_eq!(opts.callback.as_deref(), Some("cb"));
+        assert_eq!(opts.name, "MAPFILE");
+    }
+}
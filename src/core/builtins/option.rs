@@ -2,6 +2,7 @@
 //SPDX-License-Identifier: BSD-3-Clause
 
 use crate::error::exec::ExecError;
+use crate::i18n::{fl, fl_args};
 use crate::utils::arg;
 use crate::{error, ShellCore};
 
@@ -14,7 +15,7 @@ pub fn set_positions(core: &mut ShellCore, args: &[String]) -> Result<(), ExecEr
                 scope[0].clone()
             }
         }
-        None => return Err(ExecError::Other("empty param stack".to_string())),
+        None => return Err(ExecError::Other(fl("empty-param-stack"))),
     };
 
     let mut tmp = args.to_vec();
@@ -30,7 +31,7 @@ pub fn set_positions(core: &mut ShellCore, args: &[String]) -> Result<(), ExecEr
 
 pub fn set_positions_c(core: &mut ShellCore, args: &[String]) -> Result<(), ExecError> {
     if core.db.position_parameters.pop().is_none() {
-        return Err(ExecError::Other("empty param stack".to_string()));
+        return Err(ExecError::Other(fl("empty-param-stack")));
     }
 
     core.db.position_parameters.push(args.to_vec());
@@ -61,6 +62,9 @@ pub fn set_short_options(core: &mut ShellCore, args: &mut Vec<String>) {
         ('C', "noclobber"),
         ('a', "allexport"),
         ('B', "braceexpand"),
+        ('E', "errtrace"),
+        ('T', "functrace"),
+        ('P', "physical"),
         ('f', ""),
         ('u', ""),
         ('e', ""),
@@ -93,27 +97,41 @@ pub fn set_short_options(core: &mut ShellCore, args: &mut Vec<String>) {
 fn set_bash_flags(core: &mut ShellCore, args: &[String]) {
     let positive = args[1] == "-o";
 
-    if args[2] == "monitor" {
-        if positive && !core.db.flags.contains('m') {
-            core.db.flags.push('m');
-        } else if !positive {
-            core.db.flags.retain(|f| f != 'm');
+    for (short, long) in [
+        ('m', "monitor"),
+        ('a', "allexport"),
+        ('E', "errtrace"),
+        ('T', "functrace"),
+        ('P', "physical"),
+    ] {
+        if args[2] != long {
+            continue;
         }
-    }else if args[2] == "allexport" {
-        if positive && !core.db.flags.contains('a') {
-            core.db.flags.push('a');
+
+        if positive && !core.db.flags.contains(short) {
+            core.db.flags.push(short);
         } else if !positive {
-            core.db.flags.retain(|f| f != 'a');
+            core.db.flags.retain(|f| f != short);
         }
     }
+
+    /* the editing modes are exclusive */
+    if positive {
+        let _ = match args[2].as_str() {
+            "vi" => core.options.set("emacs", false),
+            "emacs" => core.options.set("vi", false),
+            _ => Ok(()),
+        };
+    }
 }
 
 pub fn set(core: &mut ShellCore, args: &[String]) -> i32 {
     let mut args = arg::dissolve_options(args);
 
     if core.db.flags.contains('r') && arg::consume_arg("+r", &mut args) {
-        let _ = super::error_(1, &args[0], "+r: invalid option", core);
-        eprintln!("set: usage: set [-abefhkmnptuvxBCEHPT] [-o option-name] [--] [-] [arg ...]");
+        let msg = fl_args("invalid-option", &[("opt", "+r")]);
+        let _ = super::error_(1, &args[0], &msg, core);
+        eprintln!("{}", super::help::usage("set"));
         return 1;
     }
 
@@ -202,14 +220,14 @@ pub fn shift(core: &mut ShellCore, args: &[String]) -> i32 {
         let n = match args[1].parse::<i32>() {
             Ok(n) => n,
             Err(_) => {
-                let err = format!("shift: {}: numeric argument required", &args[1]);
+                let err = format!("shift: {}", fl_args("numeric-argument-required", &[("arg", &args[1])]));
                 error::print(&err, core);
                 return 1;
             }
         };
 
         if n < 0 {
-            let err = format!("shift: {}: shift count out of range", &args[1]);
+            let err = format!("shift: {}", fl_args("shift-count-out-of-range", &[("count", &args[1])]));
             error::print(&err, core);
             return 1;
         }
@@ -225,7 +243,7 @@ pub fn shift(core: &mut ShellCore, args: &[String]) -> i32 {
         return 0;
     }
 
-    error::print("shift: too many arguments", core);
+    error::print(&format!("shift: {}", fl("too-many-arguments")), core);
     1
 }
 
@@ -309,7 +327,7 @@ pub fn shopt(core: &mut ShellCore, args: &[String]) -> i32 {
                     }
                 }
             } else {
-                let msg = format!("shopt: {}: not supported yet", &args[2]);
+                let msg = format!("shopt: {}", fl_args("not-supported-yet", &[("name", &args[2])]));
                 error::print(&msg, core);
                 1
             }
@@ -317,7 +335,7 @@ pub fn shopt(core: &mut ShellCore, args: &[String]) -> i32 {
         "-q" => {
             for arg in &args[2..] {
                 if !core.shopts.exist(arg) {
-                    let msg = format!("shopt: {}: invalid shell option name", &arg);
+                    let msg = format!("shopt: {}", fl_args("invalid-shell-option", &[("name", arg)]));
                     error::print(&msg, core);
                     return 1;
                 }
@@ -335,8 +353,8 @@ pub fn shopt(core: &mut ShellCore, args: &[String]) -> i32 {
             }
         },
         arg => {
-            eprintln!("sush: shopt: {arg}: invalid shell option name");
-            eprintln!("shopt: usage: shopt [-su] [optname ...]");
+            eprintln!("sush: shopt: {}", fl_args("invalid-shell-option", &[("name", arg)]));
+            eprintln!("{}", super::help::usage("shopt"));
             1
         }
     }
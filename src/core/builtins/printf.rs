@@ -4,6 +4,8 @@
 use crate::elements::substitution::Substitution;
 use crate::error::arith::ArithError;
 use crate::error::exec::ExecError;
+use crate::i18n::fl_args;
+use crate::utils::c_string;
 use crate::{error, Feeder, ShellCore};
 use std::io::{stdout, Write};
 
@@ -32,7 +34,7 @@ impl PrintfToken {
         if s.starts_with("'") && s.len() > 1 {
             let ch = match s.chars().nth(1) {
                 Some(n) => n,
-                None => return Err(ExecError::Other("Invalid char: ".to_owned() + s)),
+                None => return Err(ExecError::Other(fl_args("invalid-char", &[("char", s)]))),
             };
             return Ok(ch as isize);
         }
@@ -372,16 +374,17 @@ fn format(pattern: &str, args: &mut Vec<String>) -> Result<String, ExecError> {
 
 fn arg_check(core: &mut ShellCore, args: &[String]) -> i32 {
     if args.len() < 2 || args[1] == "--help" || args[1] == "-v" && args.len() == 3 {
-        let msg = "printf: usage: printf [-v var] format [arguments]".to_string();
-        error::print(&msg, core);
+        error::print(&super::help::usage("printf"), core);
         return 2;
     }
 
     if args[1] == "-v" && args.len() == 2 {
-        let msg = "printf: -v: option requires an argument".to_string();
-        error::print(&msg, core);
-        let msg = "printf: usage: printf [-v var] format [arguments]".to_string();
+        let msg = format!(
+            "printf: {}",
+            fl_args("option-requires-argument", &[("opt", "-v")])
+        );
         error::print(&msg, core);
+        error::print(&super::help::usage("printf"), core);
         return 2;
     }
 
@@ -403,7 +406,7 @@ fn printf_v(core: &mut ShellCore, args: &mut Vec<String>) -> i32 {
 
     if args[2].contains("[") {
         let mut f = Feeder::new(&(args[2].clone() + "=" + &s));
-        if let Ok(Some(mut a)) = Substitution::parse(&mut f, core, false, false) {
+        if let Ok(Some(a)) = Substitution::parse(&mut f, core, false, false) {
             if let Err(e) = a.eval(core, None, false) {
                 let msg = String::from(&e);
                 return super::error_(2, "printf", &msg, core);
@@ -440,7 +443,7 @@ pub fn printf(core: &mut ShellCore, args: &[String]) -> i32 {
             return 1;
         }
     };
-    print!("{}", &s);
+    let _ = stdout().write_all(&c_string::to_bytes(&s));
     stdout().flush().unwrap();
     0
 }
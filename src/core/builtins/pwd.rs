@@ -2,12 +2,17 @@
 //SPDX-FileCopyrightText: 2023 @caro@mi.shellgei.org
 //SPDX-License-Identifier: BSD-3-Clause
 
+use crate::i18n::fl_args;
 use crate::ShellCore;
+use std::io;
+use std::io::Write;
+use std::os::unix::ffi::OsStringExt;
 
 pub fn pwd(core: &mut ShellCore, args: &[String]) -> i32 {
     if args.len() == 1 || &args[1][..1] != "-" {
         // $ pwd, $ pwd aaa
-        return show_pwd(core, false);
+        let physical = core.options.query("physical");
+        return show_pwd(core, physical);
     }
 
     match args[1].as_str() {
@@ -15,8 +20,11 @@ pub fn pwd(core: &mut ShellCore, args: &[String]) -> i32 {
         "-P" => show_pwd(core, true), // シンボリックリンク名を解決して表示
         "-L" => show_pwd(core, false), // シンボリックリンク名をそのまま表示（bash default）
         _ => {
-            eprintln!("sush: pwd: {}: invalid option", &args[1]);
-            eprintln!("pwd: usage: pwd [-LP]");
+            eprintln!(
+                "sush: pwd: {}",
+                fl_args("invalid-option", &[("opt", &args[1])])
+            );
+            eprintln!("{}", super::help::usage("pwd"));
             1
         }
     }
@@ -24,12 +32,12 @@ pub fn pwd(core: &mut ShellCore, args: &[String]) -> i32 {
 
 fn show_pwd(core: &mut ShellCore, physical: bool) -> i32 {
     if let Some(mut path) = core.get_current_directory() {
-        if physical && path.is_symlink() {
-            if let Ok(c) = path.canonicalize() {
-                path = c;
-            }
+        if physical && let Ok(c) = path.canonicalize() {
+            path = c;
         }
-        println!("{}", path.display());
+        let mut out = path.into_os_string().into_vec();
+        out.push(b'\n');
+        let _ = io::stdout().write_all(&out);
         return 0;
     }
     1
@@ -3,7 +3,21 @@
 
 use super::error_;
 use crate::elements::substitution::variable::Variable;
-use crate::{arg, error, utils, ShellCore};
+use crate::error::input::InputError;
+use crate::feeder::terminal;
+use crate::i18n::fl_args;
+use crate::utils::c_string;
+use crate::{error, file_check, utils, ShellCore};
+use nix::errno::Errno;
+use nix::fcntl::{fcntl, FcntlArg};
+use nix::sys::signal::Signal;
+use nix::sys::termios;
+use nix::sys::termios::{LocalFlags, SetArg, SpecialCharacterIndices, Termios};
+use std::io;
+use std::io::Write;
+use std::os::fd::{BorrowedFd, RawFd};
+use std::sync::atomic::Ordering::Relaxed;
+use std::time::{Duration, Instant};
 
 fn check_word_limit(word: &mut String, limit: &mut usize) -> bool {
     let mut pos = 0;
@@ -22,14 +36,10 @@ fn check_word_limit(word: &mut String, limit: &mut usize) -> bool {
 pub fn read_(
     core: &mut ShellCore,
     args: &mut Vec<String>,
-    ignore_escape: bool,
-    limit: &mut usize,
-    delim: &String,
+    opts: &mut Options,
+    mut remaining: String,
 ) -> i32 {
-    let mut remaining = utils::read_line_stdin_unbuffered(delim).unwrap_or("".to_string());
-    if remaining.is_empty() {
-        return 1;
-    }
+    let (ignore_escape, limit, delim) = (opts.ignore_escape, &mut opts.limit, &opts.delim);
 
     let ifs = match core.db.exist("IFS") {
         true => core.db.get_param("IFS").unwrap(),
@@ -50,7 +60,7 @@ pub fn read_(
     consume_ifs(&mut remaining, " \t", limit);
 
     while !args.is_empty() && !remaining.is_empty() && *limit != 0 {
-        let mut word = match eat_word(core, &mut remaining, &ifs, ignore_escape, delim) {
+        let mut word = match eat_word(&mut remaining, &ifs, ignore_escape, delim, opts.fd) {
             Some(w) => w,
             None => break,
         };
@@ -80,17 +90,8 @@ pub fn read_(
     0
 }
 
-pub fn read_a(
-    core: &mut ShellCore,
-    name: &str,
-    ignore_escape: bool,
-    limit: &mut usize,
-    delim: &String,
-) -> i32 {
-    let mut remaining = utils::read_line_stdin_unbuffered(delim).unwrap_or("".to_string());
-    if remaining.is_empty() {
-        return 1;
-    }
+pub fn read_a(core: &mut ShellCore, name: &str, opts: &mut Options, mut remaining: String) -> i32 {
+    let (ignore_escape, limit, delim) = (opts.ignore_escape, &mut opts.limit, &opts.delim);
 
     let ifs = match core.db.exist("IFS") {
         true => core.db.get_param("IFS").unwrap(),
@@ -107,7 +108,7 @@ pub fn read_a(
 
     let mut pos = 0;
     while !remaining.is_empty() {
-        let mut word = match eat_word(core, &mut remaining, &ifs, ignore_escape, delim) {
+        let mut word = match eat_word(&mut remaining, &ifs, ignore_escape, delim, opts.fd) {
             Some(w) => w,
             None => break,
         };
@@ -126,43 +127,293 @@ pub fn read_a(
     0
 }
 
+pub struct Options {
+    ignore_escape: bool,
+    limit: usize,
+    exact: bool,
+    delim: String,
+    array: Option<String>,
+    prompt: Option<String>,
+    silent: bool,
+    timeout: Option<f64>,
+    fd: RawFd,
+    edit: bool,
+    text: String,
+}
+
+fn usage_error(core: &mut ShellCore, msg: &str) -> i32 {
+    error_(2, "read", msg, core);
+    eprintln!("{}", super::help::usage("read"));
+    2
+}
+
+fn parse_options(core: &mut ShellCore, args: &mut Vec<String>) -> Result<Options, i32> {
+    let mut opts = Options {
+        ignore_escape: false,
+        limit: usize::MAX,
+        exact: false,
+        delim: "\n".to_string(),
+        array: None,
+        prompt: None,
+        silent: false,
+        timeout: None,
+        fd: 0,
+        edit: false,
+        text: String::new(),
+    };
+
+    let mut i = 1;
+    while i < args.len() && args[i].starts_with('-') && args[i].len() > 1 {
+        i += 1;
+        let word = args[i - 1].clone();
+        if word == "--" {
+            break;
+        }
+
+        /* the value of an option is the rest of the word or the next arg */
+        for (pos, c) in word.char_indices().skip(1) {
+            match c {
+                'r' => opts.ignore_escape = true,
+                's' => opts.silent = true,
+                'e' => opts.edit = true,
+                _ if !"ipadnNtu".contains(c) => {
+                    let msg = fl_args("invalid-option", &[("opt", &format!("-{c}"))]);
+                    return Err(usage_error(core, &msg));
+                }
+                _ => {}
+            }
+            if "rse".contains(c) {
+                continue;
+            }
+
+            let value = match &word[pos + 1..] {
+                "" if i < args.len() => {
+                    i += 1;
+                    args[i - 1].clone()
+                }
+                "" => {
+                    let msg = fl_args("option-requires-argument", &[("opt", &format!("-{c}"))]);
+                    return Err(usage_error(core, &msg));
+                }
+                rest => rest.to_string(),
+            };
+
+            match c {
+                'i' => opts.text = value,
+                'p' => opts.prompt = Some(value),
+                'a' => opts.array = Some(value),
+                'd' => {
+                    opts.delim = match value.chars().next() {
+                        Some(d) => d.to_string(),
+                        None => "\0".to_string(),
+                    }
+                }
+                'n' | 'N' => match value.parse::<usize>() {
+                    Ok(n) => {
+                        opts.limit = n;
+                        opts.exact = c == 'N';
+                    }
+                    Err(_) => {
+                        let msg = fl_args("invalid-number", &[("num", &value)]);
+                        return Err(error_(1, "read", &msg, core));
+                    }
+                },
+                't' => match value.parse::<f64>() {
+                    Ok(t) if t >= 0.0 => opts.timeout = Some(t),
+                    _ => {
+                        let msg = fl_args("invalid-timeout", &[("spec", &value)]);
+                        return Err(error_(1, "read", &msg, core));
+                    }
+                },
+                _ => match value.parse::<RawFd>() {
+                    Ok(fd)
+                        if fcntl(unsafe { BorrowedFd::borrow_raw(fd) }, FcntlArg::F_GETFD)
+                            .is_ok() =>
+                    {
+                        opts.fd = fd
+                    }
+                    Ok(_) => {
+                        let msg = fl_args("invalid-fd", &[("fd", &value)]);
+                        return Err(error_(1, "read", &msg, core));
+                    }
+                    Err(_) => {
+                        let msg = fl_args("invalid-fd-spec", &[("fd", &value)]);
+                        return Err(error_(1, "read", &msg, core));
+                    }
+                },
+            }
+            break;
+        }
+    }
+
+    args.drain(1..i);
+    Ok(opts)
+}
+
+fn utf8_len(lead: u8) -> usize {
+    match lead {
+        0xc0..=0xdf => 2,
+        0xe0..=0xef => 3,
+        0xf0..=0xf7 => 4,
+        _ => 1,
+    }
+}
+
+fn wait_input(fd: RawFd, deadline: Option<Instant>) -> bool {
+    let ms = match deadline {
+        None => return true,
+        Some(d) => d.saturating_duration_since(Instant::now()).as_millis() as i32,
+    };
+
+    let mut pfd = libc::pollfd {
+        fd,
+        events: libc::POLLIN,
+        revents: 0,
+    };
+    unsafe { libc::poll(&mut pfd, 1, ms) > 0 }
+}
+
+/* returns the input and the exit status */
+fn read_input(core: &mut ShellCore, opts: &Options) -> (String, i32) {
+    let deadline = opts
+        .timeout
+        .map(|t| Instant::now() + Duration::from_secs_f64(t));
+    let delim = match opts.exact {
+        true => None,
+        false => opts.delim.bytes().next(),
+    };
+    let fd = unsafe { BorrowedFd::borrow_raw(opts.fd) };
+
+    let mut line = vec![];
+    let mut ch: [u8; 1] = Default::default();
+    let (mut chars, mut pending) = (0, 0);
+    let status = loop {
+        if chars == opts.limit && pending == 0 {
+            break 0;
+        }
+        if !wait_input(opts.fd, deadline) {
+            break 128 + Signal::SIGALRM as i32;
+        }
+
+        match nix::unistd::read(fd, &mut ch) {
+            Ok(0) => break 1,
+            Ok(_) => line.push(ch[0]),
+            Err(Errno::EINTR) if core.sigint.load(Relaxed) => break 130,
+            Err(Errno::EINTR) => continue,
+            Err(_) => break 1,
+        }
+
+        if pending > 0 {
+            pending -= 1;
+        } else {
+            chars += 1;
+            pending = utf8_len(ch[0]) - 1;
+        }
+
+        if Some(ch[0]) == delim && pending == 0 {
+            break 0;
+        }
+    };
+
+    (c_string::from_bytes(&line), status)
+}
+
+fn edit_input(core: &mut ShellCore, opts: &Options) -> (String, i32) {
+    let prompt = opts.prompt.clone().unwrap_or_default();
+    match terminal::read_line_with_text(core, &prompt, &opts.text) {
+        Ok(line) => (line, 0),
+        Err(InputError::Interrupt) => (String::new(), 130),
+        Err(_) => (String::new(), 1),
+    }
+}
+
+fn set_terminal(fd: RawFd, opts: &Options) -> Option<Termios> {
+    let fd = unsafe { BorrowedFd::borrow_raw(fd) };
+    let backup = termios::tcgetattr(fd).ok()?;
+
+    let mut attr = backup.clone();
+    if opts.silent {
+        attr.local_flags.remove(LocalFlags::ECHO);
+    }
+    if opts.limit != usize::MAX {
+        attr.local_flags.remove(LocalFlags::ICANON);
+        attr.control_chars[SpecialCharacterIndices::VMIN as usize] = 1;
+        attr.control_chars[SpecialCharacterIndices::VTIME as usize] = 0;
+    }
+    termios::tcsetattr(fd, SetArg::TCSADRAIN, &attr).ok()?;
+    Some(backup)
+}
+
+fn input(core: &mut ShellCore, opts: &Options) -> (String, i32) {
+    if !file_check::is_tty(opts.fd) {
+        return read_input(core, opts);
+    }
+
+    if opts.edit && opts.fd == 0 {
+        return edit_input(core, opts);
+    }
+
+    if let Some(p) = &opts.prompt {
+        eprint!("{}", p);
+        let _ = io::stderr().flush();
+    }
+
+    let backup = set_terminal(opts.fd, opts);
+    let ans = read_input(core, opts);
+    if let Some(attr) = backup {
+        let fd = unsafe { BorrowedFd::borrow_raw(opts.fd) };
+        let _ = termios::tcsetattr(fd, SetArg::TCSADRAIN, &attr);
+    }
+    ans
+}
+
 pub fn read(core: &mut ShellCore, args: &[String]) -> i32 {
     if args.is_empty() {
         return 0;
     }
 
-    let mut args = arg::dissolve_options(args);
-    let r_opt = arg::consume_arg("-r", &mut args);
-    let mut limit = usize::MAX;
-    let limit_str = arg::consume_with_next_arg("-n", &mut args);
-    let delim = match arg::consume_with_next_arg("-d", &mut args) {
-        Some(c) => c,
-        None => "\n".to_string(),
+    let mut args = args.to_vec();
+    let mut opts = match parse_options(core, &mut args) {
+        Ok(o) => o,
+        Err(exit_status) => return exit_status,
     };
 
-    if let Some(limit_str) = limit_str {
-        match limit_str.parse::<usize>() {
-            Ok(n) => limit = n,
-            Err(_) => {
-                let err = format!("{}: invalid number", &limit_str);
-                return error_(1, "read", &err, core);
-            }
+    if opts.timeout == Some(0.0) {
+        return match wait_input(opts.fd, Some(Instant::now())) {
+            true => 0,
+            false => 1,
         };
     }
 
-    if let Some(a) = arg::consume_with_next_arg("-a", &mut args) {
-        return read_a(core, &a, r_opt, &mut limit, &delim);
+    let (line, exit_status) = input(core, &opts);
+    if line.is_empty() && exit_status != 0 {
+        return exit_status;
     }
 
-    read_(core, &mut args, r_opt, &mut limit, &delim)
+    let result = match (&opts.array, opts.exact) {
+        (Some(a), _) => read_a(core, &a.clone(), &mut opts, line),
+        (None, true) => {
+            let name = args.get(1).cloned().unwrap_or("REPLY".to_string());
+            match Variable::parse_and_set(&name, &line, core) {
+                Ok(_) => 0,
+                Err(e) => error_(1, "read", &String::from(&e), core),
+            }
+        }
+        (None, false) => read_(core, &mut args, &mut opts, line),
+    };
+
+    match result {
+        0 => exit_status,
+        _ => result,
+    }
 }
 
 pub fn eat_word(
-    _core: &mut ShellCore,
     remaining: &mut String,
     ifs: &str,
     ignore_escape: bool,
     delim: &String,
+    fd: RawFd,
 ) -> Option<String> {
     let mut esc = false;
     let mut pos = 0;
@@ -189,10 +440,11 @@ pub fn eat_word(
             remaining.pop();
             remaining.pop();
 
-            let line = utils::read_line_stdin_unbuffered(delim).unwrap_or("".to_string());
+            let d = delim.bytes().next().unwrap_or(b'\n');
+            let line = utils::read_line_unbuffered(fd, d).unwrap_or("".to_string());
             if !line.is_empty() {
                 *remaining += &line;
-                return eat_word(_core, remaining, ifs, ignore_escape, delim);
+                return eat_word(remaining, ifs, ignore_escape, delim, fd);
             }
         }
     }
@@ -244,3 +496,28 @@ pub fn consume_ifs(remaining: &mut String, ifs: &str, limit: &mut usize) {
     let tail = remaining.split_off(pos);
     *remaining = tail;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|a| a.to_string()).collect()
+    }
+
+    #[test]
+    fn dash_values() {
+        let mut core = ShellCore::new();
+        let mut args = to_args(&["read", "-p", "-> ", "-rd", "-", "a", "b"]);
+        let opts = parse_options(&mut core, &mut args).unwrap();
+        assert_eq!(opts.prompt.as_deref(), Some("-> "));
+        assert_eq!(opts.delim, "-");
+        assert!(opts.ignore_escape);
+        assert_eq!(args, to_args(&["read", "a", "b"]));
+
+        let mut args = to_args(&["read", "-n2", "-i", "-x", "--", "-a"]);
+        let opts = parse_options(&mut core, &mut args).unwrap();
+        assert_eq!((opts.limit, opts.text.as_str()), (2, "-x"));
+        assert_eq!(args, to_args(&["read", "-a"]));
+    }
+}
@@ -2,22 +2,23 @@
 //SPDX-License-Identifier: BSD-3-Clause
 
 use crate::error::parse::ParseError;
-use crate::{file_check, Feeder, Script, ShellCore};
+use crate::i18n::{fl, fl_args};
+use crate::{file_check, signal, Feeder, Script, ShellCore};
 
 fn check_error(core: &mut ShellCore, args: &[String]) -> i32 {
     if core.db.flags.contains('r') && args[1].contains('/') {
-        let msg = format!("{}: restricted", &args[1]);
+        let msg = fl_args("exec-restricted", &[("name", &args[1])]);
         return super::error_(1, &args[0], &msg, core);
     }
 
     if args.len() < 2 {
-        eprintln!("sush: source: filename argument required");
-        eprintln!("source: usage: source filename [arguments]");
+        eprintln!("sush: source: {}", fl("filename-required"));
+        eprintln!("{}", super::help::usage("source"));
         return 2;
     }
 
     if file_check::is_dir(&args[1]) {
-        eprintln!("sush: source: {}: is a directory", &args[1]);
+        eprintln!("sush: source: {}", fl_args("is-a-directory", &[("name", &args[1])]));
         return 1;
     }
     0
@@ -57,7 +58,7 @@ pub fn source(core: &mut ShellCore, args: &[String]) -> i32 {
         }
 
         match Script::parse(&mut feeder, core, false) {
-            Ok(Some(mut s)) => {
+            Ok(Some(s)) => {
                 let _ = s.exec(core);
             }
             Err(e) => e.print(core),
@@ -65,6 +66,7 @@ pub fn source(core: &mut ShellCore, args: &[String]) -> i32 {
         }
     }
 
+    signal::run_return_trap(core);
     source.remove(0);
     let _ = core.db.init_array("BASH_SOURCE", Some(source), None, false);
     core.db.position_parameters.pop();
@@ -0,0 +1,30 @@
+//SPDX-FileCopyrightText: 2025 Ryuichi Ueda <ryuichiueda@gmail.com>
+//SPDX-License-Identifier: BSD-3-Clause
+
+use crate::i18n::fl_args;
+use crate::ShellCore;
+use nix::sys::resource;
+use nix::sys::resource::UsageWho;
+use nix::sys::time::{TimeVal, TimeValLike};
+
+fn format(t: &TimeVal) -> String {
+    let sec = t.tv_sec();
+    format!("{}m{}.{:03}s", sec / 60, sec % 60, t.tv_usec() / 1000)
+}
+
+pub fn times(core: &mut ShellCore, args: &[String]) -> i32 {
+    if let Some(a) = args.get(1).filter(|a| a.starts_with('-') && *a != "--") {
+        let msg = fl_args("invalid-option", &[("opt", a)]);
+        super::error_(2, &args[0], &msg, core);
+        eprintln!("{}", super::help::usage("times"));
+        return 2;
+    }
+
+    for who in [UsageWho::RUSAGE_SELF, UsageWho::RUSAGE_CHILDREN] {
+        match resource::getrusage(who) {
+            Ok(u) => println!("{} {}", format(&u.user_time()), format(&u.system_time())),
+            Err(_) => println!("{} {}", format(&TimeVal::zero()), format(&TimeVal::zero())),
+        }
+    }
+    0
+}
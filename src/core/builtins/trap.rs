@@ -3,37 +3,62 @@
 //SPDX-License-Identifier: BSD-3-Clause
 
 use crate::error::exec::ExecError;
+use crate::i18n::fl_args;
 use crate::signal;
 use crate::ShellCore;
 use nix::sys::signal::Signal;
-use signal_hook::iterator::Signals;
 use std::str::FromStr;
-use std::sync::atomic::AtomicBool;
-use std::sync::atomic::Ordering::Relaxed;
-use std::sync::Arc;
-use std::{thread, time};
 
-pub fn trap(core: &mut ShellCore, args: &[String]) -> i32 {
-    let args = args.to_owned();
-    if args.len() == 1 {
-        for e in &core.traplist {
-            if e.0 == 0 {
-                println!("trap -- '{}' EXIT", &e.1);
-            } else if let Ok(s) = Signal::try_from(e.0) {
-                println!("trap -- '{}' {}", &e.1, &s);
-            }
-        }
-        return 0;
+const DEBUG: i32 = -1;
+const ERR: i32 = -2;
+const RETURN: i32 = -3;
+
+fn usage() -> i32 {
+    eprintln!("{}", super::help::usage("trap"));
+    2
+}
+
+pub fn quote(script: &str) -> String {
+    format!("'{}'", script.replace('\'', "'\\''"))
+}
+
+fn name(n: i32) -> String {
+    match n {
+        0 => "EXIT".to_string(),
+        DEBUG => "DEBUG".to_string(),
+        ERR => "ERR".to_string(),
+        RETURN => "RETURN".to_string(),
+        _ => match Signal::try_from(n) {
+            Ok(s) => s.as_str().to_string(),
+            Err(_) => n.to_string(),
+        },
     }
+}
 
-    if args.len() < 3 {
-        // TODO: print the list of trap entries if args.len() == 1
-        eprintln!("trap: usage: trap arg signal_spec ...");
-        return 2;
+fn entries(core: &mut ShellCore) -> Vec<(i32, String)> {
+    let mut ans = vec![];
+    if !core.exit_script.is_empty() {
+        ans.push((0, core.exit_script.clone()));
     }
 
-    let forbiddens = Vec::from(signal_hook::consts::FORBIDDEN);
-    let signals = match args_to_nums(&args[2..], &forbiddens) {
+    let mut traps: Vec<(i32, String)> = core.traps.iter().map(|t| (*t.0, t.1.clone())).collect();
+    traps.sort();
+    ans.append(&mut traps);
+
+    for (n, script) in [
+        (DEBUG, &core.debug_script),
+        (ERR, &core.err_script),
+        (RETURN, &core.return_script),
+    ] {
+        if !script.is_empty() {
+            ans.push((n, script.clone()));
+        }
+    }
+    ans
+}
+
+fn print(core: &mut ShellCore, args: &[String]) -> i32 {
+    let nums = match args_to_nums(args, &[]) {
         Ok(v) => v,
         Err(e) => {
             e.print(core);
@@ -41,63 +66,103 @@ pub fn trap(core: &mut ShellCore, args: &[String]) -> i32 {
         }
     };
 
-    let mut exit = false;
-    let mut valid_signals = vec![];
-    for n in &signals {
-        if *n == 0 {
-            exit = true;
-            continue;
+    for (n, script) in entries(core) {
+        if nums.is_empty() || nums.contains(&n) {
+            println!("trap -- {} {}", quote(&script), name(n));
         }
+    }
+    0
+}
 
-        if let Ok(s) = TryFrom::try_from(*n) {
-            signal::ignore(s);
-            valid_signals.push(*n);
-            continue;
-        };
+fn print_signal_list() -> i32 {
+    let mut line = String::new();
+    for (i, s) in Signal::iterator().enumerate() {
+        line += &format!("{:2}) {}", s as i32, s.as_str());
+        if i % 5 == 4 {
+            println!("{}", &line);
+            line.clear();
+        } else {
+            line.push('\t');
+        }
+    }
+
+    if !line.is_empty() {
+        println!("{}", line.trim_end());
+    }
+    0
+}
 
-        let msg = format!("trap: {n}: invalid signal specification");
-        return super::error_(1, &args[0], &msg, core);
+fn set(core: &mut ShellCore, n: i32, script: Option<&String>) -> Result<(), ExecError> {
+    let script = script.map(|s| s.as_str());
+    match (n, script) {
+        (0, None) => core.exit_script.clear(),
+        (0, Some(s)) => core.exit_script = s.to_string(),
+        (DEBUG, s) => core.debug_script = s.unwrap_or_default().to_string(),
+        (ERR, s) => core.err_script = s.unwrap_or_default().to_string(),
+        (RETURN, s) => core.return_script = s.unwrap_or_default().to_string(),
+        (_, None) => signal::reset_trap(core, n),
+        (_, Some(s)) => signal::set_trap(core, n, s)?,
     }
+    Ok(())
+}
 
-    if !valid_signals.is_empty() {
-        for n in &valid_signals {
-            core.traplist.push((*n, args[1].to_string()));
+pub fn trap(core: &mut ShellCore, args: &[String]) -> i32 {
+    let mut args = args.to_owned();
+    match args.get(1).map(|a| a.as_str()) {
+        Some("-l") => return print_signal_list(),
+        Some("-p") => return print(core, &args[2..]),
+        Some("--") => {
+            args.remove(1);
         }
-        run_thread(valid_signals, &args[1], core);
+        _ => {}
     }
 
-    if exit {
-        core.traplist.push((0, args[1].to_string()));
-        core.exit_script = args[1].clone();
+    if args.len() == 1 {
+        return print(core, &[]);
     }
 
-    0
-}
+    if args[1].starts_with("-") && args[1] != "-" {
+        let msg = fl_args("invalid-option", &[("opt", &args[1])]);
+        super::error_(2, &args[0], &msg, core);
+        return usage();
+    }
 
-fn run_thread(signal_nums: Vec<i32>, script: &str, core: &mut ShellCore) {
-    core.trapped
-        .push((Arc::new(AtomicBool::new(false)), script.to_string()));
+    /* trap - SIG.., trap SIG, and trap N.. reset the signals */
+    let reset = args[1] == "-" || args.len() == 2 || args[1].parse::<u32>().is_ok();
+    let (script, sigs) = match (reset, args[1] == "-") {
+        (true, true) => (None, &args[2..]),
+        (true, false) => (None, &args[1..]),
+        (false, _) => (Some(&args[1]), &args[2..]),
+    };
 
-    let trap = Arc::clone(&core.trapped.last().unwrap().0);
+    if sigs.is_empty() {
+        return usage();
+    }
 
-    thread::spawn(move || {
-        let mut signals =
-            Signals::new(signal_nums.clone()).expect("sush(fatal): cannot prepare signal data");
+    let forbiddens = Vec::from(signal_hook::consts::FORBIDDEN);
+    let mut exit_status = 0;
+    for a in sigs {
+        let result = match arg_to_num(a, &forbiddens) {
+            Ok(n) => set(core, n, script),
+            Err(e) => Err(e),
+        };
 
-        loop {
-            thread::sleep(time::Duration::from_millis(5));
-            for signal in signals.pending() {
-                if signal_nums.contains(&signal) {
-                    trap.store(true, Relaxed);
-                }
-            }
+        if result.is_err() {
+            let msg = fl_args("invalid-signal-spec", &[("sig", a)]);
+            exit_status = super::error_(1, &args[0], &msg, core);
         }
-    });
+    }
+
+    exit_status
 }
 
 fn arg_to_num(arg: &str, forbiddens: &[i32]) -> Result<i32, ExecError> {
-    if arg == "EXIT" || arg == "0" {
-        return Ok(0);
+    match arg {
+        "EXIT" | "0" => return Ok(0),
+        "DEBUG" => return Ok(DEBUG),
+        "ERR" => return Ok(ERR),
+        "RETURN" => return Ok(RETURN),
+        _ => {}
     }
 
     if let Ok(n) = Signal::from_str(arg) {
@@ -111,14 +176,18 @@ fn arg_to_num(arg: &str, forbiddens: &[i32]) -> Result<i32, ExecError> {
     if let Ok(n) = arg.parse::<i32>() {
         if forbiddens.contains(&n) {
             return Err(ExecError::Other(format!(
-                "trap: {arg}: forbidden signal for trap"
+                "trap: {}",
+                fl_args("trap-forbidden-signal", &[("sig", arg)])
             )));
         }
-        return Ok(n);
+        if Signal::try_from(n).is_ok() {
+            return Ok(n);
+        }
     }
 
     Err(ExecError::Other(format!(
-        "trap: {arg}: invalid signal specification"
+        "trap: {}",
+        fl_args("invalid-signal-spec", &[("sig", arg)])
     )))
 }
 
@@ -2,6 +2,7 @@
 //SPDX-FileCopyrightText: 2023 @caro@mi.shellgei.org
 //SPDX-License-Identifier: BSD-3-Clause
 
+use crate::i18n::fl_args;
 use crate::utils::{arg, file};
 use crate::{file_check, utils, ShellCore};
 
@@ -17,7 +18,7 @@ fn type_no_opt_sub(core: &mut ShellCore, com: &String) -> i32 {
     }
     if core.db.functions.contains_key(com) {
         println!("{} is a function", &com);
-        if let Some(val) = core.db.functions.get_mut(com) {
+        if let Some(val) = core.db.functions.get(com) {
             val.pretty_print(0);
         };
         return 0;
@@ -35,7 +36,7 @@ fn type_no_opt_sub(core: &mut ShellCore, com: &String) -> i32 {
         return 0;
     }
 
-    let s = format!("{}: not found", &com);
+    let s = fl_args("not-found", &[("name", com)]);
     super::error_(1, "type", &s, core)
 }
 
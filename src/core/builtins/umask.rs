@@ -0,0 +1,145 @@
+//SPDX-FileCopyrightText: 2025 Ryuichi Ueda <ryuichiueda@gmail.com>
+//SPDX-License-Identifier: BSD-3-Clause
+
+use super::error_;
+use crate::i18n::fl_args;
+use crate::ShellCore;
+use nix::sys::stat::{umask as set_umask, Mode};
+
+fn get_umask() -> u32 {
+    let mask = set_umask(Mode::empty());
+    set_umask(mask);
+    mask.bits() as u32
+}
+
+fn symbolic(mask: u32) -> String {
+    let perm = !mask & 0o777;
+    let part = |shift: u32| {
+        let p = (perm >> shift) & 0o7;
+        [(4, 'r'), (2, 'w'), (1, 'x')]
+            .iter()
+            .filter(|(bit, _)| p & bit != 0)
+            .map(|(_, c)| *c)
+            .collect::<String>()
+    };
+    format!("u={},g={},o={}", part(6), part(3), part(0))
+}
+
+fn parse_octal(core: &mut ShellCore, mode: &str) -> Result<u32, i32> {
+    match u32::from_str_radix(mode, 8) {
+        Ok(n) if n <= 0o7777 => Ok(n & 0o777),
+        _ => {
+            let msg = fl_args("octal-out-of-range", &[("mode", mode)]);
+            Err(error_(1, "umask", &msg, core))
+        }
+    }
+}
+
+/* applies clauses like u=rwx,g+w,o-x to the permission bits */
+fn parse_symbolic(core: &mut ShellCore, mode: &str, mask: u32) -> Result<u32, i32> {
+    let mut perm = !mask & 0o777;
+
+    for clause in mode.split(',') {
+        let mut chars = clause.chars().peekable();
+        let mut who = 0;
+        while let Some(c) = chars.next_if(|c| "ugoa".contains(*c)) {
+            who |= match c {
+                'u' => 0o700,
+                'g' => 0o070,
+                'o' => 0o007,
+                _ => 0o777,
+            };
+        }
+        if who == 0 {
+            who = 0o777;
+        }
+
+        let op = match chars.next() {
+            Some(c) if "+-=".contains(c) => c,
+            None => continue,
+            Some(c) => {
+                let msg = fl_args("invalid-mode-operator", &[("char", &c.to_string())]);
+                return Err(error_(1, "umask", &msg, core));
+            }
+        };
+
+        let mut bits = 0;
+        for c in chars {
+            bits |= match c {
+                'r' => 0o444,
+                'w' => 0o222,
+                'x' => 0o111,
+                _ => {
+                    let msg = fl_args("invalid-mode-character", &[("char", &c.to_string())]);
+                    return Err(error_(1, "umask", &msg, core));
+                }
+            };
+        }
+
+        match op {
+            '+' => perm |= bits & who,
+            '-' => perm &= !(bits & who),
+            _ => perm = (perm & !who) | (bits & who),
+        }
+    }
+
+    Ok(!perm & 0o777)
+}
+
+pub fn umask(core: &mut ShellCore, args: &[String]) -> i32 {
+    let mut args = args.to_owned();
+    let mut print_symbolic = false;
+    let mut reusable = false;
+
+    while args.len() > 1 && args[1].starts_with('-') && args[1].len() > 1 {
+        let opt = args.remove(1);
+        if opt == "--" {
+            break;
+        }
+        for c in opt.chars().skip(1) {
+            match c {
+                'S' => print_symbolic = true,
+                'p' => reusable = true,
+                _ => {
+                    let msg = fl_args("invalid-option", &[("opt", &format!("-{}", c))]);
+                    error_(2, &args[0], &msg, core);
+                    eprintln!("{}", super::help::usage("umask"));
+                    return 2;
+                }
+            }
+        }
+    }
+
+    let mask = get_umask();
+    let mode = match args.get(1) {
+        Some(m) => m.clone(),
+        None => {
+            let prefix = match (reusable, print_symbolic) {
+                (true, true) => "umask -S ",
+                (true, false) => "umask ",
+                _ => "",
+            };
+            match print_symbolic {
+                true => println!("{}{}", prefix, symbolic(mask)),
+                false => println!("{}{:04o}", prefix, mask),
+            }
+            return 0;
+        }
+    };
+
+    let result = match mode.starts_with(|c: char| c.is_ascii_digit()) {
+        true => parse_octal(core, &mode),
+        false => parse_symbolic(core, &mode, mask),
+    };
+
+    match result {
+        Ok(m) => {
+            set_umask(Mode::from_bits_truncate(m as _));
+            if print_symbolic {
+                println!("{}", symbolic(m));
+            }
+            0
+        }
+        Err(exit_status) => exit_status,
+    }
+}
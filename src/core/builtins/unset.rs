@@ -1,6 +1,7 @@
 //SPDX-FileCopyrightText: 2024 Ryuichi Ueda <ryuichiueda@gmail.com>
 //SPDX-License-Identifier: BSD-3-Clause
 
+use crate::i18n::fl_args;
 use crate::{Feeder, ShellCore};
 use crate::error::exec::ExecError;
 use crate::elements::expr::arithmetic::ArithmeticExpr;
@@ -100,7 +101,7 @@ fn unset_one(core: &mut ShellCore, args: &mut Vec<String>) -> i32 {
             let mut index = name.split_off(pos);
 
             if !index.ends_with("]") {
-                let msg = format!("{}: invalid variable", &name);
+                let msg = fl_args("invalid-variable", &[("name", &name)]);
                 return super::error_(1, &args[0], &msg, core);
             }
 
@@ -112,7 +113,7 @@ fn unset_one(core: &mut ShellCore, args: &mut Vec<String>) -> i32 {
                 if let Err(_) = index.parse::<isize>() {
                     let mut f = Feeder::new(&index);
                     match ArithmeticExpr::parse(&mut f, core, false, "[") {
-                        Ok(Some(mut v)) => {
+                        Ok(Some(v)) => {
                             if !f.is_empty() {
                                 let e = ExecError::ArrayIndexInvalid(index.to_string());
                                 return super::error(1, &args[0], &e, core);
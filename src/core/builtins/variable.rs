@@ -6,8 +6,8 @@ mod set_value;
 
 use crate::elements::substitution::Substitution;
 use crate::error::exec::ExecError;
-use crate::utils::arg;
-use crate::{env, ShellCore};
+use crate::utils::{arg, c_string};
+use crate::ShellCore;
 
 pub fn local(core: &mut ShellCore, args: &[String], subs: &mut [Substitution]) -> i32 {
     let args = args.to_owned();
@@ -64,7 +64,7 @@ pub fn export(core: &mut ShellCore, args: &[String], subs: &mut [Substitution])
             return 1;
         }
         match core.db.get_param(&sub.left_hand.name) {
-            Ok(v) => unsafe{env::set_var(&sub.left_hand.name, v)},
+            Ok(v) => c_string::setenv(&sub.left_hand.name, &v),
             Err(e) => {
                 e.print(core);
                 return 1;
@@ -1,6 +1,7 @@
 //SPDX-FileCopyrightText: 2025 Ryuichi Ueda <ryuichiueda@gmail.com>
 //SPDX-License-Identifier: BSD-3-Clause
 
+use crate::i18n::fl;
 use crate::{builtins, ShellCore};
 use crate::elements::substitution::Substitution;
 use crate::utils::arg;
@@ -65,7 +66,7 @@ pub(super) fn names_match(core: &mut ShellCore, names: &mut Vec<String>,
 
     for n in names {
         if ! core.db.exist(n) && ! core.db.exist_nameref(n) {
-            return builtins::error_(1, n, "not found", core);
+            return builtins::error_(1, n, &fl("not-found-plain"), core);
         }
 
         output(core, n, args);
@@ -4,6 +4,7 @@
 use crate::ShellCore;
 use crate::elements::substitution::Substitution;
 use crate::error::exec::ExecError;
+use crate::i18n::fl_args;
 use crate::utils::arg;
 
 fn set_options_pre(core: &mut ShellCore, name: &String,
@@ -59,7 +60,7 @@ fn array_to_element_check(sub: &mut Substitution) -> Result<(), ExecError> {
     if let Some(r) = sub.right_hand.as_mut() {
         if sub.left_hand.index.is_some()
         && r.text.starts_with("(") {
-            let msg = format!("{}: cannot assign list to array member", sub.left_hand.text);
+            let msg = fl_args("cannot-assign-list", &[("name", &sub.left_hand.text)]);
             return Err(ExecError::Other(msg));
         }
     }
@@ -22,14 +22,16 @@ use self::data::single_int::IntData;
 use self::data::Data;
 use crate::elements::command::function_def::FunctionDefinition;
 use crate::error::exec::ExecError;
+use crate::i18n;
 use std::collections::HashMap;
+use std::rc::Rc;
 
 #[derive(Debug, Default)]
 pub struct DataBase {
     pub flags: String,
     pub params: Vec<HashMap<String, Box<dyn Data>>>,
     pub position_parameters: Vec<Vec<String>>,
-    pub functions: HashMap<String, FunctionDefinition>,
+    pub functions: HashMap<String, Rc<FunctionDefinition>>,
     pub exit_status: i32,
     pub last_arg: String,
     pub hash_counter: HashMap<String, usize>,
@@ -64,7 +66,11 @@ impl DataBase {
     }
 
     pub fn pop_local(&mut self) {
-        self.params.pop();
+        if let Some(params) = self.params.pop()
+            && i18n::LOCALE_VARS.iter().any(|v| params.contains_key(*v))
+        {
+            i18n::update_languages(self);
+        }
     }
 
     pub fn init(&mut self, name: &str, scope: usize) {
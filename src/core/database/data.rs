@@ -16,6 +16,7 @@ pub mod uninit;
 
 use crate::error::arith::ArithError;
 use crate::error::exec::ExecError;
+use crate::i18n::{fl, fl_args};
 use std::fmt;
 use std::fmt::Debug;
 use crate::utils;
@@ -84,31 +85,31 @@ pub trait Data {
         self.readonly_check(name)
     }
     fn get_as_single_num(&mut self) -> Result<isize, ExecError> {
-        Err(ExecError::Other("not a single variable".to_string()))
+        Err(ExecError::Other(fl("not-a-single-variable")))
     }
 
     fn set_as_array(&mut self, name: &str, _: &str, _: &str) -> Result<(), ExecError> {
         self.readonly_check(name)?;
-        Err(ExecError::Other("not an array".to_string()))
+        Err(ExecError::Other(fl("not-an-array")))
     }
     fn append_to_array_elem(&mut self, name: &str, _: &str,
                             _: &str) -> Result<(), ExecError> {
         self.readonly_check(name)?;
-        Err(ExecError::Other("not an array".to_string()))
+        Err(ExecError::Other(fl("not-an-array")))
     }
 
     fn set_as_assoc(&mut self, name: &str, _: &str, _: &str) -> Result<(), ExecError> {
         self.readonly_check(name)?;
-        Err(ExecError::Other("not an associative table".to_string()))
+        Err(ExecError::Other(fl("not-an-assoc")))
     }
     fn append_to_assoc_elem(&mut self, name: &str, _: &str,
                             _: &str) -> Result<(), ExecError> {
         self.readonly_check(name)?;
-        Err(ExecError::Other("not an associative table".to_string()))
+        Err(ExecError::Other(fl("not-an-assoc")))
     }
 
     fn get_as_single(&mut self) -> Result<String, ExecError> {
-        Err(ExecError::Other("not a single variable".to_string()))
+        Err(ExecError::Other(fl("not-a-single-variable")))
     }
     fn get_as_array(&mut self, key: &str, _: &str) -> Result<String, ExecError> {
         //TODO: change to ArithError
@@ -149,11 +150,11 @@ pub trait Data {
     }
 
     fn get_vec_from(&mut self, _: usize, _: bool) -> Result<Vec<String>, ExecError> {
-        Err(ExecError::Other("not an array".to_string()))
+        Err(ExecError::Other(fl("not-an-array")))
     }
 
     fn get_all_indexes_as_array(&mut self) -> Result<Vec<String>, ExecError> {
-        Err(ExecError::Other("not an array".to_string()))
+        Err(ExecError::Other(fl("not-an-array")))
     }
 
     fn is_special(&self) -> bool {
@@ -184,11 +185,11 @@ pub trait Data {
     }
 
     fn init_as_num(&mut self) -> Result<(), ExecError> {
-        Err(ExecError::Other("Undefined call init_as_num".to_string()))
+        Err(ExecError::Other(fl_args("undefined-call", &[("name", "init_as_num")])))
     }
 
     fn remove_elem(&mut self, _: &str) -> Result<(), ExecError> {
-        Err(ExecError::Other("Undefined call remove_elem".to_string()))
+        Err(ExecError::Other(fl_args("undefined-call", &[("name", "remove_elem")])))
     }
 
     fn readonly_check(&mut self, name: &str) -> Result<(), ExecError> {
@@ -3,6 +3,7 @@
 
 use super::{case_change, Data};
 use crate::error::exec::ExecError;
+use crate::i18n::fl;
 use crate::utils;
 use std::collections::HashMap;
 
@@ -144,7 +145,7 @@ impl Data for ArrayData {
         self.body
             .get(&0)
             .map(|v| Ok(v.clone()))
-            .ok_or(ExecError::Other("No entry".to_string()))?
+            .ok_or(ExecError::Other(fl("no-entry")))?
     }
 
     fn is_array(&self) -> bool {
@@ -4,6 +4,7 @@
 use super::array::ArrayData;
 use super::Data;
 use crate::error::exec::ExecError;
+use crate::i18n::fl;
 use std::collections::HashMap;
 
 #[derive(Debug, Clone)]
@@ -56,10 +57,7 @@ impl Data for IntArrayData {
     fn append_as_single(&mut self, name: &str, value: &str) -> Result<(), ExecError> {
         self.readonly_check(name)?;
 
-        let n = match value.parse::<isize>() {
-            Ok(n) => n,
-            Err(e) => return Err(ExecError::Other(e.to_string())),
-        };
+        let n = value.parse::<isize>()?;
 
         if let Some(v) = self.body.get(&0) {
             self.body.insert(0, v + n);
@@ -159,7 +157,7 @@ impl Data for IntArrayData {
         self.body
             .get(&0)
             .map(|v| Ok(v.to_string()))
-            .ok_or(ExecError::Other("No entry".to_string()))?
+            .ok_or(ExecError::Other(fl("no-entry")))?
     }
 
     fn get_str_type(&self) -> Box<dyn Data> {
@@ -207,7 +205,7 @@ impl Data for IntArrayData {
             self.body.remove(&n);
             return Ok(());
         }
-        Err(ExecError::Other("invalid index".to_string()))
+        Err(ExecError::Other(fl("invalid-index")))
     }
 
     fn set_flag(&mut self, flag: char) {
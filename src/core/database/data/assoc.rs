@@ -3,6 +3,7 @@
 
 use super::{case_change, Data};
 use crate::error::exec::ExecError;
+use crate::i18n::fl;
 use crate::utils;
 use std::collections::HashMap;
 
@@ -107,7 +108,7 @@ impl Data for AssocData {
 
         self.last
             .clone()
-            .ok_or(ExecError::Other("No last input".to_string()))
+            .ok_or(ExecError::Other(fl("no-last-input")))
     }
 
     fn is_assoc(&self) -> bool {
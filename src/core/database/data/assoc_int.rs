@@ -4,6 +4,7 @@
 use super::assoc::AssocData;
 use super::Data;
 use crate::error::exec::ExecError;
+use crate::i18n::fl;
 use crate::utils;
 use std::collections::HashMap;
 
@@ -94,7 +95,7 @@ impl Data for IntAssocData {
 
         self.last
             .clone()
-            .ok_or(ExecError::Other("No last input".to_string()))
+            .ok_or(ExecError::Other(fl("no-last-input")))
     }
 
     fn get_str_type(&self) -> Box<dyn Data> {
@@ -28,7 +28,7 @@ impl Data for IntData {
         match value.parse::<isize>() {
             Ok(n) => self.body = n,
             Err(e) => {
-                return Err(ExecError::Other(e.to_string()));
+                return Err(e.into());
             }
         }
         Ok(())
@@ -40,7 +40,7 @@ impl Data for IntData {
         match value.parse::<isize>() {
             Ok(n) => self.body += n,
             Err(e) => {
-                return Err(ExecError::Other(e.to_string()));
+                return Err(e.into());
             }
         }
         Ok(())
@@ -5,6 +5,8 @@ use std::env;
 use super::SingleData;
 use crate::core::DataBase;
 use crate::error::exec::ExecError;
+use crate::i18n::fl_args;
+use crate::utils::c_string;
 
 impl DataBase {
     pub fn append_param(
@@ -27,9 +29,9 @@ impl DataBase {
 
         if !self.flags.contains('r')
             && (self.flags.contains('a') || self.has_flag(name, 'x'))
-            && env::var(name).is_err()
+            && env::var_os(name).is_none()
         {
-            unsafe{env::set_var(name, "")};
+            c_string::setenv(name, "");
         }
 
         let scope = self.get_target_scope(name, scope);
@@ -45,9 +47,9 @@ impl DataBase {
 
         d.append_as_single(name, val)?;
 
-        if env::var(name).is_ok() {
+        if env::var_os(name).is_some() {
             let v = d.get_as_single()?;
-            unsafe{env::set_var(name, v)};
+            c_string::setenv(name, &v);
         }
 
         Ok(())
@@ -87,7 +89,7 @@ impl DataBase {
         let scope = self.get_target_scope(name, scope);
         match self.params[scope].get_mut(name) {
             Some(v) => v.append_to_assoc_elem(name, key, val),
-            _ => Err(ExecError::Other("TODO".to_string())),
+            _ => Err(ExecError::Other(fl_args("not-found", &[("name", name)]))),
         }
     }
 }
@@ -4,8 +4,8 @@
 use super::data::Data;
 use super::DataBase;
 use crate::error::exec::ExecError;
+use crate::utils::c_string;
 use std::collections::HashSet;
-use std::env;
 
 impl DataBase {
     pub fn get_ref(&mut self, name: &str) -> Option<&mut Box<dyn Data>> {
@@ -183,7 +183,7 @@ impl DataBase {
             return v.elem_len("0");
         }
 
-        if let Ok(v) = env::var(name) {
+        if let Some(v) = c_string::getenv(name) {
             return Ok(v.chars().count());
         }
 
@@ -225,7 +225,7 @@ impl DataBase {
             }
         }
 
-        if let Ok(v) = env::var(name) {
+        if let Some(v) = c_string::getenv(name) {
             let _ = self.set_param(name, &v, Some(0));
             return Ok(v);
         }
@@ -5,12 +5,12 @@ use crate::core::DataBase;
 use crate::core::database::{Data, IntData, Uninit, AssocData, IntAssocData, ArrayData, IntArrayData, OnDemandArray};
 use crate::error::exec::ExecError;
 use crate::utils;
-use crate::utils::clock;
+use crate::utils::{c_string, clock};
 use super::data::single_ondemand::OnDemandSingle;
 use super::data::random::RandomVar;
 use super::data::seconds::Seconds;
 use super::data::srandom::SRandomVar;
-use std::{env, process};
+use std::process;
 use nix::unistd;
 
 impl DataBase {
@@ -23,7 +23,7 @@ impl DataBase {
         self.params[0].insert("BASHPID".to_string(), Box::new(OnDemandSingle::new(bashpid)));
         self.set_flag("BASHPID", 'i', 0);
         self.set_param("BASH_SUBSHELL", "0", None)?;
-        self.set_param("HOME", &env::var("HOME").unwrap_or("/".to_string()), None)?;
+        self.set_param("HOME", &c_string::getenv("HOME").unwrap_or("/".to_string()), None)?;
         self.set_param("OPTIND", "1", None)?;
         self.set_param("IFS", " \t\n", None)?;
     
@@ -59,7 +59,7 @@ impl DataBase {
         if !value.is_empty() {
             match value.parse::<isize>() {
                 Ok(n) => data.body = n,
-                Err(e) => return Err(ExecError::Other(e.to_string())),
+                Err(e) => return Err(e.into()),
             }
         }
 
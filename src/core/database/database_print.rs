@@ -20,7 +20,7 @@ impl DataBase {
     }
 
     pub fn print_func(&mut self, name: &str) -> bool {
-        if let Some(f) = self.functions.get_mut(name) {
+        if let Some(f) = self.functions.get(name) {
             f.pretty_print(0);
             return true;
         }
@@ -7,6 +7,8 @@ use std::env;
 use super::{ArrayData, Data, IntData, SingleData, Uninit};
 use crate::core::DataBase;
 use crate::error::exec::ExecError;
+use crate::i18n;
+use crate::utils::c_string;
 
 impl DataBase {
     pub fn set_param(
@@ -29,7 +31,7 @@ impl DataBase {
         if !self.flags.contains('r')
         && (self.flags.contains('a')
        || self.has_flag(name, 'x')) {
-            unsafe{env::set_var(name, "")};
+            c_string::setenv(name, "");
         }
 
         let scope = self.get_target_scope(name, scope);
@@ -45,9 +47,13 @@ impl DataBase {
 
         d.set_as_single(name, val)?;
 
-        if env::var(name).is_ok() || self.flags.contains('a') {
+        if env::var_os(name).is_some() || self.flags.contains('a') {
             let v = d.get_as_single()?;
-            unsafe{env::set_var(name, &v)};
+            c_string::setenv(name, &v);
+        }
+
+        if i18n::LOCALE_VARS.contains(&name) {
+            i18n::update_languages(self);
         }
         Ok(())
     }
@@ -136,7 +142,7 @@ impl DataBase {
                 }
                 v.set_as_assoc(name, key, val)
             }
-            _ => Err(ExecError::Other("TODO".to_string())),
+            _ => Err(ExecError::Other(i18n::fl_args("not-found", &[("name", name)]))),
         }
     }
 
@@ -172,7 +178,7 @@ impl DataBase {
 
     pub fn set_scope_to_env(&mut self, scope: usize) {
         for (k, v) in &mut self.params[scope] {
-            unsafe{env::set_var(k, v.get_as_single().unwrap_or("".to_string()))};
+            c_string::setenv(k, &v.get_as_single().unwrap_or_default());
         }
     }
 }
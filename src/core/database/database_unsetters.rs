@@ -4,7 +4,8 @@
 use crate::core::DataBase;
 use crate::core::database::Uninit;
 use crate::error::exec::ExecError;
-use std::env;
+use crate::i18n;
+use crate::utils::c_string;
 
 impl DataBase {
     pub fn unset_flag(&mut self, name: &str, flag: char, scope: usize) {
@@ -67,7 +68,7 @@ impl DataBase {
             }
             res = self.remove_entry(scope, name)?;
 
-            unsafe{env::set_var(name, "")};
+            c_string::setenv(name, "");
             for scope in self.params.iter_mut() {
                 if let Some(d) = scope.get_mut(name) {
                     res = true;
@@ -98,6 +99,9 @@ impl DataBase {
     pub fn unset(&mut self, name: &str, called_scope: Option<usize>,
                  localvar_unset: bool) -> Result<(), ExecError> {
         if self.unset_var(name, called_scope, localvar_unset)? {
+            if i18n::LOCALE_VARS.contains(&name) {
+                i18n::update_languages(self);
+            }
             return Ok(());
         }
         self.unset_function(name);
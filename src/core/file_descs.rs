@@ -7,6 +7,8 @@ use libc::fcntl;
 use libc::{F_GETFD, F_DUPFD_CLOEXEC};
 
 use crate::error::exec::ExecError;
+use crate::i18n::fl;
+use nix::errno::Errno;
 use nix::unistd::Pid;
 use std::os::fd::{OwnedFd, FromRawFd, RawFd};
 use nix::unistd;
@@ -34,6 +36,12 @@ impl FileDescriptors {
     pub fn dupfd_cloexec(&mut self, from: RawFd,
                                 hereafter: RawFd) -> Result<RawFd, ExecError> {
         let fd = unsafe{fcntl(from, F_DUPFD_CLOEXEC, hereafter)};
+        if fd < 0 {
+            return Err(ExecError::Errno(Errno::last()));
+        }
+        if fd as usize >= self.fds.len() {
+            self.fds.resize_with(fd as usize + 1, || None);
+        }
         self.fds[fd as usize] = Some(unsafe { OwnedFd::from_raw_fd(fd) });
 
         Ok(fd)
@@ -50,7 +58,7 @@ impl FileDescriptors {
         if let Some(fd) = self.fds[fd as usize].as_mut() {
             return Ok(unistd::tcgetpgrp(fd)?);
         }
-        Err(ExecError::Other("cannot get process group".to_string()))
+        Err(ExecError::Other(fl("cannot-get-process-group")))
     }
 
     pub fn close(&mut self, fd: RawFd) {
@@ -72,6 +80,14 @@ impl FileDescriptors {
         (fd_recv, fd_send)
     }
 
+    pub fn is_open(&self, fd: RawFd) -> bool {
+        (unsafe{fcntl(fd, F_GETFD)}) != -1
+    }
+
+    pub fn unused_fd(&self, from: RawFd) -> Option<RawFd> {
+        (from..self.fds.len() as RawFd).find(|fd| ! self.is_open(*fd))
+    }
+
     pub fn backup(&mut self, from: RawFd) -> RawFd {
         //if fcntl::fcntl(from, fcntl::F_GETFD).is_err() {
         if unsafe{fcntl(from, F_GETFD)} == -1 {
@@ -81,12 +97,12 @@ impl FileDescriptors {
     }
 
     pub fn replace(&mut self, from: RawFd, to: RawFd) -> Result<(), ExecError> {
-        if from < 0 || to < 0 {
+        if from < 0 || to < 0 || from == to {
             return Ok(());
         }
 
         if unsafe{dup2(from, to)} < 0 {
-            return Err(ExecError::Other("dup2 error".to_string()));
+            return Err(ExecError::Other(fl("dup2-error")));
         }
 
         //unistd::dup2(from, to)?;
@@ -96,7 +112,7 @@ impl FileDescriptors {
 
     pub fn share(&mut self, from: RawFd, to: RawFd) -> Result<(), ExecError> {
         if from < 0 || to < 0 {
-            return Err(ExecError::Other("minus fd number".to_string()));
+            return Err(ExecError::Other(fl("negative-fd")));
         }
 
         if unsafe{dup2(from, to)} < 0 {
@@ -1,54 +1,236 @@
 //SPDXFileCopyrightText: 2024 Ryuichi Ueda ryuichiueda@gmail.com
 //SPDXLicense-Identifier: BSD-3-Clause
 
+pub mod expansion;
+
+use crate::i18n::{fl, fl_args};
+use crate::utils::{c_string, clock, file, glob};
 use crate::ShellCore;
-use rev_lines::RevLines;
 use std::fs::File;
 use std::fs::OpenOptions;
-use std::io::{BufReader, BufWriter, Write};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+
+/* a line like "#1700000000" before an entry in the history file */
+fn parse_timestamp(line: &str) -> Option<i64> {
+    let digits = line.strip_prefix('#')?;
+    match digits.starts_with(|c: char| c.is_ascii_digit()) {
+        true => digits.parse::<i64>().ok(),
+        false => None,
+    }
+}
+
+/* entries of a history file from the oldest one */
+pub fn read_entries(filename: &str) -> Option<Vec<(String, Option<i64>)>> {
+    let file = File::open(file::name_to_path(filename)).ok()?;
+    let mut ans = vec![];
+    let mut time = None;
+    for line in BufReader::new(file).split(b'\n').map_while(Result::ok) {
+        let line = c_string::from_bytes(&line);
+        match parse_timestamp(&line) {
+            Some(t) => time = Some(t),
+            None => ans.push((line, time.take())),
+        }
+    }
+    Some(ans)
+}
+
+/* bytes that are not UTF-8 are written back as they were read */
+fn write_entry(f: &mut impl Write, line: &str, time: Option<i64>) -> std::io::Result<()> {
+    if let Some(t) = time {
+        writeln!(f, "#{}", t)?;
+    }
+    f.write_all(&c_string::to_bytes(line))?;
+    f.write_all(b"\n")
+}
 
 impl ShellCore {
     pub fn fetch_history(&mut self, pos: usize, prev: usize, prev_str: String) -> String {
         if prev < self.history.len() {
             self.history[prev] = prev_str;
-        } else {
-            self.rewritten_history
-                .insert(prev + 1 - self.history.len(), prev_str);
         }
 
-        if pos < self.history.len() {
-            self.history[pos].clone()
-        } else {
-            self.fetch_history_file(pos + 1 - self.history.len())
+        match pos < self.history.len() {
+            true => self.history[pos].clone(),
+            false => String::new(),
         }
     }
 
-    pub fn fetch_history_file(&mut self, pos: usize) -> String {
-        if let Some(s) = self.rewritten_history.get(&pos) {
-            return s.to_string();
-        }
-        if pos == 0 {
-            return String::new();
+    /* all entries from the newest one except the line under editing */
+    pub fn history_entries(&mut self) -> Vec<String> {
+        self.history.iter().skip(1).cloned().collect()
+    }
+
+    /* the line under editing is history[0] until it is committed */
+    pub fn start_history_editing(&mut self) {
+        self.history.insert(0, String::new());
+        self.history_times.insert(0, None);
+    }
+
+    pub fn cancel_history_editing(&mut self) {
+        if !self.history.is_empty() {
+            self.history.remove(0);
+            self.history_times.remove(0);
         }
+    }
+
+    fn param_number(&mut self, name: &str) -> Option<usize> {
+        self.db.get_param(name).ok()?.parse::<usize>().ok()
+    }
+
+    fn history_ignored(&mut self, line: &str) -> bool {
+        let control = self.db.get_param("HISTCONTROL").unwrap_or_default();
+        let control: Vec<&str> = control.split(':').collect();
+        let ignore_space = control.contains(&"ignorespace") || control.contains(&"ignoreboth");
+        let ignore_dups = control.contains(&"ignoredups") || control.contains(&"ignoreboth");
 
-        let mut file_line = pos - 1;
-        if let Ok(n) = self
-            .db
-            .get_param("HISTFILESIZE")
-            .unwrap_or_default()
-            .parse::<usize>()
+        if line.is_empty()
+            || (ignore_space && line.starts_with(' '))
+            || (ignore_dups && self.history.first().is_some_and(|h| h == line))
         {
-            file_line %= n;
+            return true;
         }
 
-        if let Ok(hist_file) = File::open(self.db.get_param("HISTFILE").unwrap_or_default()) {
-            let mut rev_lines = RevLines::new(BufReader::new(hist_file));
-            if let Some(Ok(s)) = rev_lines.nth(file_line) {
-                return s;
+        let extglob = self.shopts.query("extglob");
+        let ignore = self.db.get_param("HISTIGNORE").unwrap_or_default();
+        for pattern in ignore.split(':').filter(|p| !p.is_empty()) {
+            let matched = match pattern {
+                "&" => self.history.first().is_some_and(|h| h == line),
+                _ => glob::parse_and_compare(line, pattern, extglob),
+            };
+            if matched {
+                return true;
             }
         }
+        false
+    }
+
+    fn erase_history(&mut self, line: &str) {
+        while let Some(pos) = self.history.iter().position(|h| h == line) {
+            self.delete_history(pos);
+        }
+    }
+
+    pub fn add_history(&mut self, line: &str) {
+        if self.history_ignored(line) {
+            return;
+        }
+        let control = self.db.get_param("HISTCONTROL").unwrap_or_default();
+        if control.split(':').any(|c| c == "erasedups") {
+            self.erase_history(line);
+        }
+
+        let now = clock::get_epochseconds().parse::<i64>().ok();
+        self.push_history(line, now);
+        self.history_new_lines += 1;
+        self.truncate_history();
+    }
 
-        String::new()
+    pub fn push_history(&mut self, line: &str, time: Option<i64>) {
+        self.history.insert(0, line.to_string());
+        self.history_times.insert(0, time);
+    }
+
+    /* pos counts from the newest entry */
+    pub fn delete_history(&mut self, pos: usize) {
+        self.history.remove(pos);
+        self.history_times.remove(pos);
+        if pos < self.history_new_lines {
+            self.history_new_lines -= 1;
+        }
+    }
+
+    pub fn clear_history(&mut self) {
+        self.history.clear();
+        self.history_times.clear();
+        self.history_new_lines = 0;
+    }
+
+    /* HISTSIZE */
+    pub fn truncate_history(&mut self) {
+        if let Some(size) = self.param_number("HISTSIZE") {
+            self.history.truncate(size);
+            self.history_times.truncate(size);
+            self.history_new_lines = std::cmp::min(self.history_new_lines, size);
+        }
+    }
+
+    pub fn history_file(&mut self, filename: Option<&String>) -> String {
+        match filename {
+            Some(f) => f.clone(),
+            None => self.db.get_param("HISTFILE").unwrap_or_default(),
+        }
+    }
+
+    /* reads the entries after the skip-th one */
+    pub fn read_history_file(&mut self, filename: &str, skip: usize) -> bool {
+        let entries = match read_entries(filename) {
+            Some(e) => e,
+            None => return false,
+        };
+
+        let now = clock::get_epochseconds().parse::<i64>().ok();
+        for (line, time) in entries.iter().skip(skip) {
+            self.push_history(line, time.or(now));
+        }
+        self.history_file_lines = entries.len();
+        self.truncate_history();
+        true
+    }
+
+    pub fn load_history(&mut self) {
+        if !self.db.flags.contains('i') {
+            return;
+        }
+        let filename = self.history_file(None);
+        self.read_history_file(&filename, 0);
+    }
+
+    /* writes the newest num entries */
+    pub fn write_history(&mut self, filename: &str, num: usize, append: bool) -> bool {
+        let file = match OpenOptions::new()
+            .create(true)
+            .write(true)
+            .append(append)
+            .truncate(!append)
+            .open(file::name_to_path(filename))
+        {
+            Ok(f) => f,
+            _ => return false,
+        };
+
+        let with_time = self.db.exist("HISTTIMEFORMAT");
+        let mut f = BufWriter::new(file);
+        let num = std::cmp::min(num, self.history.len());
+        for (h, t) in self.history[..num]
+            .iter()
+            .zip(&self.history_times[..num])
+            .rev()
+        {
+            let time = match with_time {
+                true => *t,
+                false => None,
+            };
+            let _ = write_entry(&mut f, h, time);
+        }
+        f.flush().is_ok()
+    }
+
+    /* HISTFILESIZE */
+    fn truncate_history_file(&mut self, filename: &str) {
+        let size = match self.param_number("HISTFILESIZE") {
+            Some(s) => s,
+            None => return,
+        };
+        let entries = match read_entries(filename) {
+            Some(e) if e.len() > size => e,
+            _ => return,
+        };
+
+        let mut text = vec![];
+        for (line, time) in &entries[entries.len() - size..] {
+            let _ = write_entry(&mut text, line, *time);
+        }
+        let _ = std::fs::write(file::name_to_path(filename), text);
     }
 
     pub fn write_history_to_file(&mut self) {
@@ -57,26 +239,44 @@ impl ShellCore {
         }
         let filename = self.db.get_param("HISTFILE").unwrap_or_default();
         if filename.is_empty() {
-            eprintln!("sush: HISTFILE is not set");
+            eprintln!("sush: {}", fl_args("not-set", &[("name", "HISTFILE")]));
             return;
         }
 
-        let file = match OpenOptions::new().create(true).append(true).open(&filename) {
-            Ok(f) => f,
-            _ => {
-                eprintln!("sush: invalid history file");
-                return;
-            }
+        /* histappend keeps lines written by other shells */
+        let ok = match self.shopts.query("histappend") {
+            true => self.write_history(&filename, self.history_new_lines, true),
+            false => self.write_history(&filename, self.history.len(), false),
         };
+        if !ok {
+            eprintln!("sush: {}", fl("invalid-history-file"));
+            return;
+        }
 
-        let mut f = BufWriter::new(file);
-        for h in self.history.iter().rev() {
-            if h.is_empty() {
-                continue;
-            }
-            let _ = f.write(h.as_bytes());
-            let _ = f.write(&[0x0A]);
+        self.history_new_lines = 0;
+        self.truncate_history_file(&filename);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn invalid_utf8_round_trip() {
+        let path = std::env::temp_dir().join(format!("sush-history-test.{}", std::process::id()));
+        let data = b"echo \xff\xfe\n#1700000000\nls caf\xc3\xa9 \xe9\n";
+        std::fs::write(&path, data).unwrap();
+
+        let entries = read_entries(path.to_str().unwrap()).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[1].1, Some(1700000000));
+
+        let mut written = vec![];
+        for (line, time) in &entries {
+            write_entry(&mut written, line, *time).unwrap();
         }
-        let _ = f.flush();
+        assert_eq!(written, data);
     }
 }
@@ -0,0 +1,455 @@
+//SPDX-FileCopyrightText: 2025 Ryuichi Ueda ryuichiueda@gmail.com
+//SPDX-License-Identifier: BSD-3-Clause
+
+use crate::i18n::fl_args;
+use crate::ShellCore;
+
+const EVENT_DELIMITERS: &str = ":;&|()<>\"'`";
+
+/* splits an event into words with the quoting rules of the shell */
+fn split_words(line: &str) -> Vec<String> {
+    let mut words = vec![];
+    let mut word = String::new();
+    let mut quote = None;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if let Some(q) = quote {
+            word.push(c);
+            if c == q {
+                quote = None;
+            } else if c == '\\' && q == '"' {
+                word.extend(chars.next());
+            }
+            continue;
+        }
+
+        match c {
+            '\\' => {
+                word.push(c);
+                word.extend(chars.next());
+            }
+            '\'' | '"' | '`' => {
+                quote = Some(c);
+                word.push(c);
+            }
+            ' ' | '\t' | '\n' => {
+                if !word.is_empty() {
+                    words.push(std::mem::take(&mut word));
+                }
+            }
+            ';' | '&' | '|' | '(' | ')' | '<' | '>' => {
+                if !word.is_empty() {
+                    words.push(std::mem::take(&mut word));
+                }
+                let mut op = c.to_string();
+                if "&|<>".contains(c) && chars.peek() == Some(&c) {
+                    op.extend(chars.next());
+                }
+                words.push(op);
+            }
+            _ => word.push(c),
+        }
+    }
+
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words
+}
+
+fn read_number(chars: &[char], pos: &mut usize) -> Option<usize> {
+    let start = *pos;
+    while *pos < chars.len() && chars[*pos].is_ascii_digit() {
+        *pos += 1;
+    }
+    chars[start..*pos].iter().collect::<String>().parse().ok()
+}
+
+/* reads a part of :s/old/new/ up to the delimiter, which is optional at the end */
+fn read_subst_part(chars: &[char], pos: &mut usize, delim: char) -> String {
+    let mut ans = String::new();
+    while *pos < chars.len() && chars[*pos] != delim && chars[*pos] != '\n' {
+        if chars[*pos] == '\\' && chars.get(*pos + 1) == Some(&delim) {
+            *pos += 1;
+        }
+        ans.push(chars[*pos]);
+        *pos += 1;
+    }
+    if *pos < chars.len() && chars[*pos] == delim {
+        *pos += 1;
+    }
+    ans
+}
+
+fn quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "'\\''"))
+}
+
+struct Expander<'a> {
+    core: &'a mut ShellCore,
+    search_word: String,
+    print_only: bool,
+}
+
+impl Expander<'_> {
+    fn previous(&self) -> Result<String, String> {
+        match self.core.history.first() {
+            Some(h) => Ok(h.replace("↵ \0", "\n")),
+            None => Err(fl_args("event-not-found", &[("event", "!!")])),
+        }
+    }
+
+    fn find(&self, pred: impl Fn(&str) -> bool) -> Option<String> {
+        self.core
+            .history
+            .iter()
+            .find(|h| pred(h))
+            .map(|h| h.replace("↵ \0", "\n"))
+    }
+
+    /* chars[*pos] is the character next to the expansion character */
+    fn event(&mut self, chars: &[char], pos: &mut usize, line: &str) -> Result<String, String> {
+        let start = *pos - 1;
+        let not_found = |pos: usize| {
+            let text: String = chars[start..pos].iter().collect();
+            fl_args("event-not-found", &[("event", &text)])
+        };
+
+        match chars[*pos] {
+            '!' => {
+                *pos += 1;
+                self.previous().map_err(|_| not_found(*pos))
+            }
+            '#' => {
+                *pos += 1;
+                Ok(line.to_string())
+            }
+            '$' | '^' | '*' | '%' | ':' => self.previous().map_err(|_| not_found(*pos)),
+            c if c.is_ascii_digit() || c == '-' => {
+                let minus = c == '-';
+                if minus {
+                    *pos += 1;
+                }
+                let n = read_number(chars, pos).unwrap_or(0);
+                let len = self.core.history.len();
+                let index = match minus {
+                    true => n.checked_sub(1),
+                    false => len.checked_sub(n),
+                };
+                match index.filter(|i| n > 0 && *i < len) {
+                    Some(i) => Ok(self.core.history[i].replace("↵ \0", "\n")),
+                    None => Err(not_found(*pos)),
+                }
+            }
+            '?' => {
+                *pos += 1;
+                let word = read_subst_part(chars, pos, '?');
+                self.search_word = word.clone();
+                self.find(|h| h.contains(&word))
+                    .ok_or_else(|| not_found(*pos))
+            }
+            _ => {
+                let begin = *pos;
+                while *pos < chars.len()
+                    && !chars[*pos].is_whitespace()
+                    && !EVENT_DELIMITERS.contains(chars[*pos])
+                {
+                    *pos += 1;
+                }
+                let prefix: String = chars[begin..*pos].iter().collect();
+                self.find(|h| h.starts_with(&prefix))
+                    .ok_or_else(|| not_found(*pos))
+            }
+        }
+    }
+
+    fn word_range(
+        &self,
+        chars: &[char],
+        pos: &mut usize,
+        words: &[String],
+    ) -> Option<(usize, usize)> {
+        let last = words.len().checked_sub(1)?;
+        let first = match *chars.get(*pos)? {
+            '^' => {
+                *pos += 1;
+                1
+            }
+            '$' => {
+                *pos += 1;
+                return Some((last, last));
+            }
+            '%' => {
+                *pos += 1;
+                let p = words.iter().position(|w| w.contains(&self.search_word))?;
+                return Some((p, p));
+            }
+            '*' => {
+                *pos += 1;
+                return Some((1, last));
+            }
+            '-' => 0,
+            c if c.is_ascii_digit() => read_number(chars, pos)?,
+            _ => return None,
+        };
+
+        match chars.get(*pos) {
+            Some('*') => {
+                *pos += 1;
+                Some((first, last))
+            }
+            Some('-') => {
+                *pos += 1;
+                match chars.get(*pos) {
+                    Some('$') => {
+                        *pos += 1;
+                        Some((first, last))
+                    }
+                    Some(c) if c.is_ascii_digit() => Some((first, read_number(chars, pos)?)),
+                    _ => Some((first, last.checked_sub(1)?)),
+                }
+            }
+            _ => Some((first, first)),
+        }
+    }
+
+    fn words(&mut self, chars: &[char], pos: &mut usize, event: String) -> Result<String, String> {
+        let start = *pos;
+        match chars.get(*pos) {
+            Some(':')
+                if chars
+                    .get(*pos + 1)
+                    .is_some_and(|c| "^$*%-0123456789".contains(*c)) =>
+            {
+                *pos += 1;
+            }
+            Some('^' | '$' | '*' | '%') => {}
+            _ => return Ok(event),
+        }
+
+        let words = split_words(&event);
+        let spec_end = |pos: usize| -> String { chars[start..pos].iter().collect() };
+        match self.word_range(chars, pos, &words) {
+            Some((first, last)) if first <= last && last < words.len() => {
+                Ok(words[first..=last].join(" "))
+            }
+            Some((first, last)) if first == last + 1 && first <= words.len() => Ok(String::new()),
+            _ => Err(fl_args("bad-word-specifier", &[("spec", &spec_end(*pos))])),
+        }
+    }
+
+    fn substitute(
+        &mut self,
+        text: &str,
+        chars: &[char],
+        pos: &mut usize,
+        global: bool,
+        each_word: bool,
+    ) -> Result<String, String> {
+        let start = *pos;
+        let (old, new) = match chars.get(*pos) {
+            Some('&') => {
+                *pos += 1;
+                self.core.history_subst.clone().unwrap_or_default()
+            }
+            Some(_) if *pos + 1 < chars.len() => {
+                let delim = chars[*pos + 1];
+                *pos += 2;
+                let mut old = read_subst_part(chars, pos, delim);
+                let new = read_subst_part(chars, pos, delim);
+                if old.is_empty() {
+                    old = match &self.core.history_subst {
+                        Some((o, _)) => o.clone(),
+                        None => self.search_word.clone(),
+                    };
+                }
+                let new = new
+                    .replace("\\&", "\0")
+                    .replace('&', &old)
+                    .replace('\0', "&");
+                (old, new)
+            }
+            _ => {
+                *pos += 1;
+                (String::new(), String::new())
+            }
+        };
+        self.core.history_subst = Some((old.clone(), new.clone()));
+
+        let failed = || {
+            let spec: String = chars[start..*pos].iter().collect();
+            fl_args("substitution-failed", &[("spec", &format!(":{}", spec))])
+        };
+        if old.is_empty() || !text.contains(&old) {
+            return Err(failed());
+        }
+
+        let replace = |s: &str| match global {
+            true => s.replace(&old, &new),
+            false => s.replacen(&old, &new, 1),
+        };
+        match each_word {
+            true => Ok(text
+                .split(' ')
+                .map(replace)
+                .collect::<Vec<String>>()
+                .join(" ")),
+            false => Ok(replace(text)),
+        }
+    }
+
+    fn modifiers(
+        &mut self,
+        chars: &[char],
+        pos: &mut usize,
+        mut text: String,
+    ) -> Result<String, String> {
+        while chars.get(*pos) == Some(&':') && *pos + 1 < chars.len() {
+            *pos += 1;
+            let c = chars[*pos];
+            *pos += 1;
+            text = match c {
+                'h' => match text.rfind('/') {
+                    Some(p) => text[..p].to_string(),
+                    None => text,
+                },
+                't' => match text.rfind('/') {
+                    Some(p) => text[p + 1..].to_string(),
+                    None => text,
+                },
+                'r' => match text.rfind('.').filter(|p| !text[*p..].contains('/')) {
+                    Some(p) => text[..p].to_string(),
+                    None => text,
+                },
+                'e' => match text.rfind('.').filter(|p| !text[*p..].contains('/')) {
+                    Some(p) => text[p..].to_string(),
+                    None => text,
+                },
+                'p' => {
+                    self.print_only = true;
+                    text
+                }
+                'q' => quote(&text),
+                'x' => text
+                    .split_whitespace()
+                    .map(quote)
+                    .collect::<Vec<String>>()
+                    .join(" "),
+                's' | '&' => {
+                    *pos -= 1;
+                    self.substitute(&text, chars, pos, false, false)?
+                }
+                'g' | 'a' | 'G' if matches!(chars.get(*pos), Some('s' | '&')) => {
+                    self.substitute(&text, chars, pos, c != 'G', c == 'G')?
+                }
+                _ => {
+                    let c = c.to_string();
+                    return Err(fl_args(
+                        "unrecognized-history-modifier",
+                        &[("modifier", &c)],
+                    ));
+                }
+            };
+        }
+        Ok(text)
+    }
+
+    fn expand_one(
+        &mut self,
+        chars: &[char],
+        pos: &mut usize,
+        line: &str,
+    ) -> Result<String, String> {
+        let event = self.event(chars, pos, line)?;
+        let text = self.words(chars, pos, event)?;
+        self.modifiers(chars, pos, text)
+    }
+
+    /* ^old^new^ is equal to !!:s^old^new^ */
+    fn quick_substitution(&mut self, chars: &[char], pos: &mut usize) -> Result<String, String> {
+        let prev = self.previous()?;
+        let mut subst = vec!['s'];
+        subst.extend_from_slice(chars);
+        let mut p = 0;
+        let ans = self.substitute(&prev, &subst, &mut p, false, false)?;
+        *pos = p - 1;
+        self.modifiers(chars, pos, ans)
+    }
+}
+
+fn histchars(core: &mut ShellCore) -> Vec<char> {
+    match core.db.exist("histchars") {
+        true => core
+            .db
+            .get_param("histchars")
+            .unwrap_or_default()
+            .chars()
+            .collect(),
+        false => vec!['!', '^', '#'],
+    }
+}
+
+/* returns the expanded line and whether it is only printed */
+pub fn expand(core: &mut ShellCore, line: &str) -> Result<(String, bool), String> {
+    let hc = histchars(core);
+    let bang = match hc.first() {
+        Some(c) => *c,
+        None => return Ok((line.to_string(), false)),
+    };
+    let hat = hc.get(1).copied();
+    let comment = hc.get(2).copied();
+
+    let chars: Vec<char> = line.chars().collect();
+    let mut exp = Expander {
+        core,
+        search_word: String::new(),
+        print_only: false,
+    };
+
+    let mut ans = String::new();
+    let mut pos = 0;
+    if hat.is_some() && chars.first() == hat.as_ref() {
+        ans = exp.quick_substitution(&chars, &mut pos)?;
+    }
+
+    let (mut squote, mut dquote) = (false, false);
+    while pos < chars.len() {
+        let c = chars[pos];
+        let after_blank = pos == 0 || chars[pos - 1].is_whitespace();
+        if c == '\\' && !squote && pos + 1 < chars.len() {
+            ans.push(c);
+            ans.push(chars[pos + 1]);
+            pos += 2;
+            continue;
+        }
+
+        match c {
+            '\'' if !dquote => squote = !squote,
+            '"' if !squote => dquote = !dquote,
+            _ => {}
+        }
+
+        if Some(c) == comment && after_blank && !squote && !dquote {
+            ans.extend(&chars[pos..]);
+            break;
+        }
+
+        let literal = squote
+            || c != bang
+            || chars
+                .get(pos + 1)
+                .is_none_or(|n| n.is_whitespace() || "=(\"".contains(*n));
+        if literal {
+            ans.push(c);
+            pos += 1;
+            continue;
+        }
+
+        pos += 1;
+        let text = exp.expand_one(&chars, &mut pos, &ans)?;
+        ans += &text;
+    }
+
+    Ok((ans, exp.print_only))
+}
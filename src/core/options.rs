@@ -2,6 +2,7 @@
 //SPDXLicense-Identifier: BSD-3-Clause
 
 use crate::error::exec::ExecError;
+use crate::i18n::fl_args;
 use std::collections::HashMap;
 
 #[derive(Debug, Default)]
@@ -14,13 +15,18 @@ impl Options {
     pub fn new_as_basic_opts() -> Options {
         let mut options = Options::default();
         options.opts.insert("allexport".to_string(), false);
+        options.opts.insert("emacs".to_string(), true);
+        options.opts.insert("errtrace".to_string(), false);
+        options.opts.insert("functrace".to_string(), false);
         options.opts.insert("pipefail".to_string(), false);
         options.opts.insert("monitor".to_string(), true);
         options.opts.insert("noclobber".to_string(), false);
         options.opts.insert("noglob".to_string(), false);
         options.opts.insert("onecmd".to_string(), false);
+        options.opts.insert("physical".to_string(), false);
         options.opts.insert("posix".to_string(), false);
-        options.opts.insert("history".to_string(), false); //TODO: still dummy
+        options.opts.insert("vi".to_string(), false);
+        options.opts.insert("history".to_string(), false);
         options
     }
 
@@ -78,7 +84,7 @@ impl Options {
             options.opts.insert(opt.to_string(), false);
         }
 
-        let true_list = ["extglob", "progcomp", "globskipdots"];
+        let true_list = ["extglob", "progcomp", "globskipdots", "promptvars"];
         for opt in true_list {
             options.opts.insert(opt.to_string(), true);
         }
@@ -98,6 +104,14 @@ impl Options {
             "assoc_expand_once",
             "localvar_inherit",
             "localvar_unset",
+            "autocd",
+            "cdable_vars",
+            "cdspell",
+            "dirspell",
+            "histappend",
+            "histreedit",
+            "histverify",
+            "promptvars",
         ]
         .iter()
         .map(|s| s.to_string())
@@ -131,7 +145,10 @@ impl Options {
     pub fn print_opt(&self, opt: &str, set_format: bool) -> bool {
         match self.opts.get_key_value(opt) {
             None => {
-                eprintln!("sush: shopt: {opt}: invalid shell option name");
+                eprintln!(
+                    "sush: shopt: {}",
+                    fl_args("invalid-shell-option", &[("name", opt)])
+                );
                 false
             }
             Some(kv) => {
@@ -182,7 +199,7 @@ impl Options {
 
     pub fn set(&mut self, opt: &str, onoff: bool) -> Result<(), ExecError> {
         if !self.opts.contains_key(opt) {
-            let msg = format!("{opt}: invalid option name");
+            let msg = fl_args("invalid-option-name", &[("name", opt)]);
             return Err(ExecError::Other(msg));
         }
 
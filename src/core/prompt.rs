@@ -0,0 +1,283 @@
+//SPDX-FileCopyrightText: 2024 Ryuichi Ueda ryuichiueda@gmail.com
+//SPDX-License-Identifier: BSD-3-Clause
+
+///// Decoding of PS0, PS1, PS2 and PS4 /////
+
+mod git;
+
+use self::git::GitStatus;
+use super::builtins;
+use crate::elements::subword;
+use crate::elements::subword::filler::FillerSubword;
+use crate::elements::word::{Word, WordMode};
+use crate::utils::{clock, file};
+use crate::{Feeder, ShellCore};
+use nix::unistd;
+use nix::unistd::User;
+use std::io;
+
+fn user_name() -> String {
+    match User::from_uid(unistd::getuid()) {
+        Ok(Some(u)) => u.name,
+        _ => "".to_string(),
+    }
+}
+
+fn hostname() -> String {
+    match unistd::gethostname() {
+        Ok(h) => file::oss_to_name(&h),
+        _ => "".to_string(),
+    }
+}
+
+fn tty_name() -> String {
+    match unistd::ttyname(io::stdin()) {
+        Ok(p) => file::buf_to_name(&p)
+            .rsplit('/')
+            .next()
+            .unwrap_or_default()
+            .to_string(),
+        _ => "tty".to_string(),
+    }
+}
+
+/* three octal digits after a backslash */
+fn octal(chars: &[char]) -> Option<char> {
+    let digits: String = chars.iter().take(3).collect();
+    if digits.len() != 3 || !digits.chars().all(|c| ('0'..='7').contains(&c)) {
+        return None;
+    }
+    char::from_u32(u32::from_str_radix(&digits, 8).ok()?)
+}
+
+/* ~/a/b/c/d -> ~/.../c/d when PROMPT_DIRTRIM=2 */
+fn trim_dir(path: &str, num: usize) -> String {
+    let (head, rest) = match path.strip_prefix("~/") {
+        Some(rest) => ("~/", rest),
+        None => ("", path.trim_start_matches('/')),
+    };
+
+    let dirs: Vec<&str> = rest.split('/').collect();
+    if num == 0 || dirs.len() <= num {
+        return path.to_string();
+    }
+    format!("{}.../{}", head, dirs[dirs.len() - num..].join("/"))
+}
+
+impl ShellCore {
+    fn prompt_pwd(&mut self) -> String {
+        let pwd = self.db.get_param("PWD").unwrap_or_default();
+        match pwd.is_empty() {
+            true => unistd::getcwd()
+                .map(|p| file::buf_to_name(&p))
+                .unwrap_or_default(),
+            false => pwd,
+        }
+    }
+
+    fn prompt_cwd(&mut self, basename: bool) -> String {
+        let mut cwd = self.prompt_pwd();
+
+        let home = self.db.get_param("HOME").unwrap_or_default();
+        if !home.is_empty() && home != "/" {
+            if cwd == home {
+                cwd = "~".to_string();
+            } else if cwd.starts_with(&(home.clone() + "/")) {
+                cwd = cwd.replacen(&home, "~", 1);
+            }
+        }
+
+        if basename {
+            return match cwd.as_str() {
+                "/" | "~" => cwd,
+                _ => cwd.rsplit('/').next().unwrap_or_default().to_string(),
+            };
+        }
+
+        let dirtrim = self.db.get_param("PROMPT_DIRTRIM").unwrap_or_default();
+        trim_dir(&cwd, dirtrim.parse::<usize>().unwrap_or(0))
+    }
+
+    fn prompt_escape(&mut self, c: char, chars: &[char], pos: &mut usize) -> Option<String> {
+        let now = clock::get_epochseconds().parse::<i64>().unwrap_or(0);
+        let version = env!("CARGO_PKG_VERSION");
+
+        let ans = match c {
+            'a' => "\x07".to_string(),
+            'e' => "\x1b".to_string(),
+            'n' => "\n".to_string(),
+            'r' => "\r".to_string(),
+            'd' => clock::strftime("%a %b %d", now),
+            't' => clock::strftime("%H:%M:%S", now),
+            'T' => clock::strftime("%I:%M:%S", now),
+            '@' => clock::strftime("%I:%M %p", now),
+            'A' => clock::strftime("%H:%M", now),
+            'D' if chars.get(*pos) == Some(&'{') => {
+                let len = chars[*pos..].iter().position(|c| *c == '}')?;
+                let format: String = chars[*pos + 1..*pos + len].iter().collect();
+                *pos += len + 1;
+                match format.is_empty() {
+                    true => clock::strftime("%X", now),
+                    false => clock::strftime(&format, now),
+                }
+            }
+            'h' => hostname().split('.').next().unwrap_or_default().to_string(),
+            'H' => hostname(),
+            'j' => self.job_table.len().to_string(),
+            'l' => tty_name(),
+            's' => {
+                let name = self.db.get_param("0").unwrap_or_default();
+                name.rsplit('/').next().unwrap_or_default().to_string()
+            }
+            'u' => user_name(),
+            'v' => version
+                .rsplitn(2, '.')
+                .last()
+                .unwrap_or_default()
+                .to_string(),
+            'V' => version.to_string(),
+            'w' => self.prompt_cwd(false),
+            'W' => self.prompt_cwd(true),
+            'b' => match GitStatus::new(&self.prompt_pwd()) {
+                Some(g) => g.head_label() + "🌵",
+                None => "".to_string(),
+            },
+            'g' => match GitStatus::new(&self.prompt_pwd()) {
+                Some(mut g) => {
+                    g.read_details();
+                    g.segment()
+                }
+                None => "".to_string(),
+            },
+            '!' => (self.history.len() + 1).to_string(),
+            '#' => (self.command_number + 1).to_string(),
+            '$' => match unistd::geteuid().is_root() {
+                true => "#".to_string(),
+                false => "$".to_string(),
+            },
+            '\\' => "\\".to_string(),
+            '0'..='7' => {
+                let ch = octal(&chars[*pos - 1..])?;
+                *pos += 2;
+                ch.to_string()
+            }
+            _ => return None,
+        };
+        Some(ans)
+    }
+
+    /* \[ and \] are left for the line editor */
+    pub fn decode_prompt(&mut self, raw: &str) -> String {
+        let promptvars = self.shopts.query("promptvars");
+        let chars: Vec<char> = raw.chars().collect();
+        let mut ans = String::new();
+        let mut pos = 0;
+
+        while pos < chars.len() {
+            let c = chars[pos];
+            pos += 1;
+            if c != '\\' || pos == chars.len() {
+                ans.push(c);
+                continue;
+            }
+
+            let e = chars[pos];
+            pos += 1;
+            match self.prompt_escape(e, &chars, &mut pos) {
+                Some(s) if promptvars => {
+                    ans += &s
+                        .replace('\\', "\\\\")
+                        .replace('$', "\\$")
+                        .replace('`', "\\`")
+                }
+                Some(s) => ans += &s,
+                None => {
+                    ans.push('\\');
+                    ans.push(e);
+                }
+            }
+        }
+
+        match promptvars {
+            true => self.expand_prompt(&ans),
+            false => ans,
+        }
+    }
+
+    /* expanded in the same way as a here document */
+    fn expand_prompt(&mut self, decoded: &str) -> String {
+        let mut feeder = Feeder::new(decoded);
+        let mut word = Word::default();
+
+        while !feeder.is_empty() {
+            match subword::parse(&mut feeder, self, &Some(WordMode::Heredoc)) {
+                Ok(Some(mut sw)) => {
+                    sw.set_heredoc_flag();
+                    word.text += sw.get_text();
+                    word.subwords.push(sw);
+                }
+                Ok(None) => {
+                    let len = feeder.scanner_char();
+                    if len == 0 {
+                        break;
+                    }
+                    let c = feeder.consume(len);
+                    word.text += &c;
+                    word.subwords.push(Box::new(FillerSubword { text: c }));
+                }
+                Err(e) => {
+                    e.print(self);
+                    return decoded.to_string();
+                }
+            }
+        }
+
+        match word.eval_as_alter(self) {
+            Ok(s) => s,
+            Err(e) => {
+                e.print(self);
+                decoded.to_string()
+            }
+        }
+    }
+
+    pub fn make_prompt(&mut self, name: &str) -> String {
+        let raw = self.db.get_param(name).unwrap_or_default();
+        self.decode_prompt(&raw)
+    }
+
+    /* SUSH_GIT_* are unset outside a repository */
+    fn set_git_variables(&mut self) {
+        let values = GitStatus::new(&self.prompt_pwd()).map(|mut g| {
+            g.read_details();
+            g.values()
+        });
+
+        for (i, name) in git::VARIABLES.iter().enumerate() {
+            let _ = match &values {
+                Some(v) => self.db.set_param(name, &v[i], None),
+                None => self.db.unset(name, None, false),
+            };
+        }
+    }
+
+    /* PROMPT_COMMAND can be an array since Bash 5.1 */
+    pub fn run_prompt_command(&mut self) {
+        self.set_git_variables();
+        let commands = match self.db.is_array("PROMPT_COMMAND") {
+            true => self.db.get_vec("PROMPT_COMMAND", true).unwrap_or_default(),
+            false => vec![self.db.get_param("PROMPT_COMMAND").unwrap_or_default()],
+        };
+
+        let exit_status = self.db.exit_status;
+        for com in commands.into_iter().filter(|c| !c.is_empty()) {
+            builtins::eval(self, &["eval".to_string(), com]);
+        }
+        self.db.exit_status = exit_status;
+    }
+
+    pub fn print_ps0(&mut self) {
+        let ps0 = self.make_prompt("PS0");
+        eprint!("{}", ps0.replace("\\[", "").replace("\\]", ""));
+    }
+}
@@ -0,0 +1,430 @@
+//SPDX-FileCopyrightText: 2024 Ryuichi Ueda ryuichiueda@gmail.com
+//SPDX-License-Identifier: BSD-3-Clause
+
+///// Repository status for prompts, read from the git dir without running git /////
+
+use crate::utils::{c_string, file};
+use std::ffi::OsStr;
+use std::fs;
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::fs::MetadataExt;
+use std::path::{Path, PathBuf};
+
+pub const VARIABLES: [&str; 11] = [
+    "SUSH_GIT_DIR",
+    "SUSH_GIT_BRANCH",
+    "SUSH_GIT_COMMIT",
+    "SUSH_GIT_DETACHED",
+    "SUSH_GIT_STATE",
+    "SUSH_GIT_STEP",
+    "SUSH_GIT_TOTAL",
+    "SUSH_GIT_UPSTREAM",
+    "SUSH_GIT_DIRTY",
+    "SUSH_GIT_STASH",
+    "SUSH_GIT_CONFLICT",
+];
+
+#[derive(Debug, Default)]
+pub struct GitStatus {
+    git_dir: PathBuf,
+    common_dir: PathBuf,
+    work_tree: PathBuf,
+    branch: String,
+    commit: String,
+    detached: bool,
+    state: String,
+    step: String,
+    total: String,
+    upstream: String,
+    dirty: bool,
+    stash: bool,
+    conflict: bool,
+}
+
+fn read(path: &Path) -> String {
+    let data = fs::read(path).unwrap_or_default();
+    c_string::from_bytes(&data).trim_end().to_string()
+}
+
+/* a .git file of a worktree or a submodule has "gitdir: <path>" */
+fn find_git_dir(cwd: &str) -> Option<(PathBuf, PathBuf)> {
+    let cwd = file::name_to_path(cwd);
+    let mut dir = cwd.as_path();
+    loop {
+        let dot_git = dir.join(".git");
+        if dot_git.is_dir() {
+            return Some((dot_git, dir.to_path_buf()));
+        }
+        if dot_git.is_file() {
+            let gitdir = read(&dot_git);
+            let path = gitdir.strip_prefix("gitdir: ")?;
+            return Some((dir.join(file::name_to_path(path)), dir.to_path_buf()));
+        }
+        dir = dir.parent()?;
+    }
+}
+
+fn be32(data: &[u8], pos: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(data.get(pos..pos + 4)?.try_into().ok()?))
+}
+
+/* the offset encoding used for path prefixes in index v4 */
+fn varint(data: &[u8], pos: &mut usize) -> Option<usize> {
+    let mut c = *data.get(*pos)?;
+    *pos += 1;
+    let mut ans = (c & 0x7f) as usize;
+    while c & 0x80 != 0 {
+        c = *data.get(*pos)?;
+        *pos += 1;
+        ans = ((ans + 1) << 7) + (c & 0x7f) as usize;
+    }
+    Some(ans)
+}
+
+impl GitStatus {
+    /* HEAD and the operation in progress; cheap enough for \b */
+    pub fn new(cwd: &str) -> Option<Self> {
+        let (git_dir, work_tree) = find_git_dir(cwd)?;
+        if !git_dir.join("HEAD").is_file() {
+            return None;
+        }
+
+        let common_dir = match read(&git_dir.join("commondir")) {
+            c if c.is_empty() => git_dir.clone(),
+            c => git_dir.join(file::name_to_path(&c)),
+        };
+        let mut ans = Self {
+            git_dir,
+            common_dir,
+            work_tree,
+            ..Default::default()
+        };
+        ans.read_head();
+        ans.read_state();
+        Some(ans)
+    }
+
+    /* the upstream, the stash and the index for \g and SUSH_GIT_* */
+    pub fn read_details(&mut self) {
+        self.read_upstream();
+        self.stash = self.resolve_ref("refs/stash").is_some();
+        let check_dirty = self.config("bash", "showDirtyState").as_deref() != Some("false");
+        self.read_index(check_dirty);
+    }
+
+    fn read_head(&mut self) {
+        let head = read(&self.git_dir.join("HEAD"));
+        match head.strip_prefix("ref: ") {
+            Some(name) => {
+                self.branch = name.strip_prefix("refs/heads/").unwrap_or(name).to_string();
+                self.commit = self.resolve_ref(name).unwrap_or_default();
+            }
+            None => {
+                self.detached = true;
+                self.commit = head;
+            }
+        }
+    }
+
+    fn read_state(&mut self) {
+        let g = self.git_dir.clone();
+        let (dir, state) = if g.join("rebase-merge").is_dir() {
+            ("rebase-merge", "REBASE")
+        } else if g.join("rebase-apply").is_dir() {
+            let state = match (
+                g.join("rebase-apply/rebasing").exists(),
+                g.join("rebase-apply/applying").exists(),
+            ) {
+                (true, _) => "REBASE",
+                (_, true) => "AM",
+                _ => "AM/REBASE",
+            };
+            ("rebase-apply", state)
+        } else {
+            let files = [
+                ("MERGE_HEAD", "MERGING"),
+                ("CHERRY_PICK_HEAD", "CHERRY-PICKING"),
+                ("REVERT_HEAD", "REVERTING"),
+                ("BISECT_LOG", "BISECTING"),
+            ];
+            let state = files
+                .iter()
+                .find(|f| g.join(f.0).exists())
+                .map_or("", |f| f.1);
+            ("", state)
+        };
+
+        self.state = state.to_string();
+        if dir.is_empty() {
+            return;
+        }
+
+        let (step, total) = match dir {
+            "rebase-merge" => ("msgnum", "end"),
+            _ => ("next", "last"),
+        };
+        self.step = read(&g.join(dir).join(step));
+        self.total = read(&g.join(dir).join(total));
+
+        let head_name = read(&g.join(dir).join("head-name"));
+        if let Some(b) = head_name.strip_prefix("refs/heads/") {
+            self.branch = b.to_string();
+        }
+    }
+
+    /* per-worktree refs are in the git dir, and the others in the common dir */
+    fn resolve_ref(&self, name: &str) -> Option<String> {
+        for dir in [&self.git_dir, &self.common_dir] {
+            let id = read(&dir.join(name));
+            if !id.is_empty() && !id.starts_with("ref: ") {
+                return Some(id);
+            }
+        }
+        self.packed_refs()
+            .into_iter()
+            .find(|r| r.1 == name)
+            .map(|r| r.0)
+    }
+
+    /* peeled ids of annotated tags are given as "<tag>^{}" */
+    fn packed_refs(&self) -> Vec<(String, String)> {
+        let mut ans: Vec<(String, String)> = vec![];
+        for line in read(&self.common_dir.join("packed-refs")).lines() {
+            if let Some(peeled) = line.strip_prefix('^') {
+                if let Some(last) = ans.last() {
+                    let name = last.1.clone() + "^{}";
+                    ans.push((peeled.to_string(), name));
+                }
+            } else if !line.starts_with('#')
+                && let Some((id, name)) = line.split_once(' ')
+            {
+                ans.push((id.to_string(), name.to_string()));
+            }
+        }
+        ans
+    }
+
+    fn exact_tag(&self) -> Option<String> {
+        if let Ok(entries) = fs::read_dir(self.common_dir.join("refs/tags")) {
+            for e in entries.flatten() {
+                if read(&e.path()) == self.commit {
+                    return Some(e.file_name().to_string_lossy().to_string());
+                }
+            }
+        }
+
+        self.packed_refs()
+            .into_iter()
+            .filter(|r| r.0 == self.commit)
+            .find_map(|r| r.1.strip_prefix("refs/tags/").map(|t| t.to_string()))
+            .map(|t| t.trim_end_matches("^{}").to_string())
+    }
+
+    /* git config is read only for a few keys; the last definition wins */
+    fn config(&self, section: &str, key: &str) -> Option<String> {
+        let mut current = String::new();
+        let mut ans = None;
+        for line in read(&self.common_dir.join("config")).lines() {
+            let line = line.trim();
+            if let Some(header) = line.strip_prefix('[').and_then(|h| h.strip_suffix(']')) {
+                current = match header.split_once(' ') {
+                    Some((s, sub)) => {
+                        format!("{}.{}", s.to_lowercase(), sub.trim().trim_matches('"'))
+                    }
+                    None => header.to_lowercase(),
+                };
+            } else if current == section
+                && let Some((k, v)) = line.split_once('=')
+                && k.trim().eq_ignore_ascii_case(key)
+            {
+                ans = Some(v.trim().trim_matches('"').to_string());
+            }
+        }
+        ans
+    }
+
+    fn upstream_ref(&self) -> Option<String> {
+        let section = format!("branch.{}", self.branch);
+        let remote = self.config(&section, "remote")?;
+        let merge = self.config(&section, "merge")?;
+        match remote.as_str() {
+            "." => Some(merge),
+            _ => {
+                let name = merge.strip_prefix("refs/heads/")?;
+                Some(format!("refs/remotes/{}/{}", remote, name))
+            }
+        }
+    }
+
+    /* reflogs stand in for a walk of the history, which needs the object store */
+    fn in_reflog(&self, name: &str, commit: &str) -> bool {
+        read(&self.common_dir.join("logs").join(name))
+            .lines()
+            .any(|line| line.split(' ').take(2).any(|id| id == commit))
+    }
+
+    fn read_upstream(&mut self) {
+        if self.detached || self.commit.is_empty() {
+            return;
+        }
+        let Some(upstream) = self.upstream_ref() else {
+            return;
+        };
+        let Some(up_commit) = self.resolve_ref(&upstream) else {
+            return;
+        };
+
+        let local = format!("refs/heads/{}", self.branch);
+        self.upstream = if up_commit == self.commit {
+            "="
+        } else if self.in_reflog(&local, &up_commit) {
+            ">"
+        } else if self.in_reflog(&upstream, &self.commit) {
+            "<"
+        } else {
+            "<>"
+        }
+        .to_string();
+    }
+
+    /* stat data in the index is compared with the work tree as git does
+     * before it reads the files, so a touched file counts as dirty */
+    fn read_index(&mut self, check_dirty: bool) {
+        let Ok(index) = fs::read(self.git_dir.join("index")) else {
+            return;
+        };
+        if !index.starts_with(b"DIRC") {
+            return;
+        }
+        let (Some(version), Some(num)) = (be32(&index, 4), be32(&index, 8)) else {
+            return;
+        };
+        if !(2..=4).contains(&version) {
+            return;
+        }
+
+        let mut pos = 12;
+        let mut path: Vec<u8> = vec![];
+        for _ in 0..num {
+            let (Some(mtime), Some(mode), Some(size)) = (
+                be32(&index, pos + 8),
+                be32(&index, pos + 24),
+                be32(&index, pos + 36),
+            ) else {
+                return;
+            };
+            let Some(flags) = index.get(pos + 60..pos + 62) else {
+                return;
+            };
+            let flags = u16::from_be_bytes([flags[0], flags[1]]);
+
+            let mut name_pos = pos + 62;
+            if flags & 0x4000 != 0 {
+                name_pos += 2;
+            }
+            if version == 4 {
+                let Some(strip) = varint(&index, &mut name_pos) else {
+                    return;
+                };
+                path.truncate(path.len().saturating_sub(strip));
+            } else {
+                path.clear();
+            }
+            let Some(len) = index
+                .get(name_pos..)
+                .and_then(|s| s.iter().position(|b| *b == 0))
+            else {
+                return;
+            };
+            path.extend_from_slice(&index[name_pos..name_pos + len]);
+
+            pos = match version {
+                4 => name_pos + len + 1,
+                _ => pos + ((name_pos - pos + len + 8) & !7),
+            };
+
+            if flags & 0x3000 != 0 {
+                self.conflict = true;
+            } else if check_dirty && !self.dirty && mode & 0o170000 != 0o160000 {
+                self.dirty = self.is_modified(&path, mtime, size);
+            }
+        }
+    }
+
+    fn is_modified(&self, path: &[u8], mtime: u32, size: u32) -> bool {
+        let file = self.work_tree.join(OsStr::from_bytes(path));
+        match fs::symlink_metadata(file) {
+            Ok(m) => m.mtime() as u32 != mtime || m.size() as u32 != size,
+            Err(_) => true,
+        }
+    }
+
+    /* branch name, or a tag or a short id in parentheses */
+    pub fn head_label(&self) -> String {
+        if !self.branch.is_empty() {
+            return self.branch.clone();
+        }
+        match self.exact_tag() {
+            Some(tag) => format!("({})", tag),
+            None => format!("({}...)", self.commit.chars().take(7).collect::<String>()),
+        }
+    }
+
+    /* main *$|REBASE 1/3|CONFLICT<> in the same order as git-prompt.sh */
+    pub fn segment(&self) -> String {
+        let mut ans = self.head_label();
+
+        let flags = self.flag_marks();
+        if !flags.is_empty() {
+            ans += &format!(" {}", flags);
+        }
+        if !self.state.is_empty() {
+            ans += &format!("|{}", self.state);
+        }
+        if !self.step.is_empty() && !self.total.is_empty() {
+            ans += &format!(" {}/{}", self.step, self.total);
+        }
+        if self.conflict {
+            ans += "|CONFLICT";
+        }
+        ans + &self.upstream
+    }
+
+    fn flag_marks(&self) -> String {
+        let mut ans = String::new();
+        if self.dirty {
+            ans.push('*');
+        }
+        if self.stash {
+            ans.push('$');
+        }
+        ans
+    }
+
+    pub fn values(&self) -> [String; 11] {
+        let flag = |b: bool| match b {
+            true => "1".to_string(),
+            false => "".to_string(),
+        };
+
+        [
+            self.git_dir.to_string_lossy().to_string(),
+            self.branch.clone(),
+            self.commit.clone(),
+            flag(self.detached),
+            self.state.clone(),
+            self.step.clone(),
+            self.total.clone(),
+            self.upstream.clone(),
+            match self.dirty {
+                true => "*".to_string(),
+                false => "".to_string(),
+            },
+            match self.stash {
+                true => "$".to_string(),
+                false => "".to_string(),
+            },
+            flag(self.conflict),
+        ]
+    }
+}
@@ -5,6 +5,7 @@ use crate::elements::subword::escaped_char::EscapedChar;
 use crate::elements::subword::simple::SimpleSubword;
 use crate::elements::subword::Subword;
 use crate::error::parse::ParseError;
+use crate::utils::c_string;
 use crate::{Feeder, ShellCore};
 
 #[derive(Clone, Debug)]
@@ -31,8 +32,7 @@ impl AnsiCToken {
                 }
 
                 if num >= 128 {
-                    num += 0xE000;
-                    char::from_u32(num).unwrap().to_string()
+                    c_string::from_byte(num as u8).to_string()
                 } else {
                     char::from(num as u8).to_string()
                 }
@@ -52,8 +52,7 @@ impl AnsiCToken {
                 }
 
                 if num >= 128 {
-                    num += 0xE000;
-                    char::from_u32(num).unwrap().to_string()
+                    c_string::from_byte(num as u8).to_string()
                 } else {
                     char::from(num as u8).to_string()
                 }
@@ -61,19 +60,16 @@ impl AnsiCToken {
             AnsiCToken::Unicode4(s) => {
                 let num = u32::from_str_radix(s, 16).unwrap();
                 match char::from_u32(num) {
-                    Some(c) => c.to_string(),
+                    Some(c) => c_string::from_chars(&c.to_string()),
                     _ => "U+".to_owned() + s,
                 }
             }
             AnsiCToken::Unicode8(s) => {
-                let num = u64::from_str_radix(s, 16).unwrap();
-                let mut ans = String::new();
-                for i in (0..4).rev() {
-                    let n = (((num >> (i * 8)) & 0xFF) + 0xE000 + ((i + 1) << 8)) as u32;
-                    ans.push(char::from_u32(n).unwrap());
+                let num = u32::from_str_radix(s, 16).unwrap();
+                match char::from_u32(num) {
+                    Some(c) => c_string::from_chars(&c.to_string()),
+                    _ => String::new(),
                 }
-                //unsafe { char::from_u32_unchecked(num as u32) }.to_string()
-                ans
             }
             AnsiCToken::Control(c) => {
                 let num = if *c == '@' {
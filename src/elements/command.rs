@@ -10,6 +10,7 @@ pub mod function_def;
 pub mod r#if;
 pub mod paren;
 pub mod repeat;
+pub mod select;
 pub mod simple;
 pub mod test;
 pub mod r#while;
@@ -24,9 +25,10 @@ use self::r#for::ForCommand;
 use self::r#if::IfCommand;
 use self::r#while::WhileCommand;
 use self::repeat::RepeatCommand;
+use self::select::SelectCommand;
 use self::simple::SimpleCommand;
 use self::test::TestCommand;
-use super::io::redirect::Redirect;
+use super::io::redirect::{Redirect, RedirectFds};
 use super::{io, Pipe};
 use crate::error::exec::ExecError;
 use crate::error::parse::ParseError;
@@ -50,7 +52,7 @@ impl Clone for Box<dyn Command> {
 }
 
 pub trait Command {
-    fn exec(&mut self, core: &mut ShellCore, pipe: &mut Pipe) -> Result<Option<Pid>, ExecError> {
+    fn exec(&self, core: &mut ShellCore, pipe: &mut Pipe) -> Result<Option<Pid>, ExecError> {
         if core.break_counter > 0 || core.continue_counter > 0 {
             return Ok(None);
         }
@@ -65,60 +67,26 @@ pub trait Command {
         }
     }
 
-    fn fork_exec_child(&mut self, core: &mut ShellCore, pipe: &mut Pipe) -> Result<(), ExecError> {
-        core.initialize_as_subshell(Pid::from_raw(0), pipe.pgid);
-        io::connect(pipe, self.get_redirects(), core)?;
-        self.run(core, true)
-    }
-
     fn fork_exec(
-        &mut self,
+        &self,
         core: &mut ShellCore,
         pipe: &mut Pipe,
     ) -> Result<Option<Pid>, ExecError> {
-        match unsafe { unistd::fork()? } {
-            ForkResult::Child => {
-                if let Err(e) = self.fork_exec_child(core, pipe) {
-                    e.print(core);
-                    core.db.exit_status = 1;
-                }
-
-                exit::normal(core)
-            }
-            ForkResult::Parent { child } => {
-                proc_ctrl::set_pgid(core, child, pipe.pgid);
-                pipe.parent_close(core);
-                Ok(Some(child))
-            }
-        }
+        fork_exec_with(core, pipe, self.get_redirects(), |core| self.run(core, true))
     }
 
-    fn nofork_exec(&mut self, core: &mut ShellCore) -> Result<Option<Pid>, ExecError> {
-        let mut result = Ok(None);
-        for r in self.get_redirects().iter_mut() {
-            if let Err(e) = r.connect(true, core) {
-                result = Err(e);
-            }
-        }
-
-        if result.is_ok() {
-            let _ = self.run(core, false);
-        } else {
-            core.db.exit_status = 1;
-        }
-        for r in self.get_redirects().iter_mut().rev() {
-            r.restore(core)?;
-        }
-        result
+    fn nofork_exec(&self, core: &mut ShellCore) -> Result<Option<Pid>, ExecError> {
+        nofork_exec_with(core, self.get_redirects(), |core| self.run(core, false))
     }
 
-    fn run(&mut self, _: &mut ShellCore, fork: bool) -> Result<(), ExecError>;
+    fn run(&self, _: &mut ShellCore, fork: bool) -> Result<(), ExecError>;
     fn get_text(&self) -> String;
     fn get_one_line_text(&self) -> String {
         self.get_text().replace("\n", " ")
     }
-    fn get_redirects(&mut self) -> &mut Vec<Redirect>;
-    fn get_lineno(&mut self) -> usize;
+    fn get_redirects(&self) -> &[Redirect];
+    fn get_redirects_mut(&mut self) -> &mut Vec<Redirect>;
+    fn get_lineno(&self) -> usize;
     fn set_force_fork(&mut self);
     fn boxed_clone(&self) -> Box<dyn Command>;
     fn force_fork(&self) -> bool;
@@ -129,7 +97,7 @@ pub trait Command {
         core: &mut ShellCore,
     ) -> Result<(), ParseError> {
         let lineno = self.get_lineno();
-        for r in self.get_redirects().iter_mut() {
+        for r in self.get_redirects_mut().iter_mut() {
             if r.called_as_heredoc {
                 continue;
             }
@@ -143,7 +111,7 @@ pub trait Command {
         Ok(())
     }
 
-    fn pretty_print(&mut self, indent_num: usize) {
+    fn pretty_print(&self, indent_num: usize) {
         for _ in 0..indent_num {
             print!("    ");
         }
@@ -151,6 +119,56 @@ pub trait Command {
     }
 }
 
+/* the args of a simple command are given through the run closure */
+pub fn fork_exec_with(
+    core: &mut ShellCore,
+    pipe: &mut Pipe,
+    redirects: &[Redirect],
+    run: impl FnOnce(&mut ShellCore) -> Result<(), ExecError>,
+) -> Result<Option<Pid>, ExecError> {
+    match unsafe { unistd::fork()? } {
+        ForkResult::Child => {
+            core.initialize_as_subshell(Pid::from_raw(0), pipe.pgid);
+            if let Err(e) = io::connect(pipe, redirects, core).and_then(|_| run(core)) {
+                e.print(core);
+                core.db.exit_status = 1;
+            }
+
+            exit::normal(core)
+        }
+        ForkResult::Parent { child } => {
+            proc_ctrl::set_pgid(core, child, pipe.pgid);
+            pipe.parent_close(core);
+            Ok(Some(child))
+        }
+    }
+}
+
+/* the fds saved by redirects belong to each execution */
+pub fn nofork_exec_with(
+    core: &mut ShellCore,
+    redirects: &[Redirect],
+    run: impl FnOnce(&mut ShellCore) -> Result<(), ExecError>,
+) -> Result<Option<Pid>, ExecError> {
+    let mut fds: Vec<RedirectFds> = redirects.iter().map(|_| RedirectFds::new()).collect();
+    let mut result = Ok(None);
+    for (r, fd) in redirects.iter().zip(fds.iter_mut()) {
+        if let Err(e) = r.connect(fd, true, core) {
+            result = Err(e);
+        }
+    }
+
+    if result.is_ok() {
+        let _ = run(core);
+    } else {
+        core.db.exit_status = 1;
+    }
+    for fd in fds.iter_mut().rev() {
+        fd.restore(core)?;
+    }
+    result
+}
+
 pub fn eat_inner_script(
     feeder: &mut Feeder,
     core: &mut ShellCore,
@@ -266,6 +284,8 @@ pub fn parse(
         Ok(Some(Box::new(a)))
     } else if let Some(a) = WhileCommand::parse(feeder, core)? {
         Ok(Some(Box::new(a)))
+    } else if let Some(a) = SelectCommand::parse(feeder, core)? {
+        Ok(Some(Box::new(a)))
     } else if let Some(a) = RepeatCommand::parse(feeder, core)? {
         Ok(Some(Box::new(a)))
     } else if let Some(a) = CaseCommand::parse(feeder, core)? {
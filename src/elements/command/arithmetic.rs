@@ -17,7 +17,7 @@ pub struct ArithmeticCommand {
 }
 
 impl Command for ArithmeticCommand {
-    fn run(&mut self, core: &mut ShellCore, _: bool) -> Result<(), ExecError> {
+    fn run(&self, core: &mut ShellCore, _: bool) -> Result<(), ExecError> {
         let mut err = None;
 
         let exit_status = match self.eval(core) {
@@ -58,10 +58,13 @@ impl Command for ArithmeticCommand {
     fn get_text(&self) -> String {
         self.text.clone()
     }
-    fn get_redirects(&mut self) -> &mut Vec<Redirect> {
+    fn get_redirects(&self) -> &[Redirect] {
+        &self.redirects
+    }
+    fn get_redirects_mut(&mut self) -> &mut Vec<Redirect> {
         &mut self.redirects
     }
-    fn get_lineno(&mut self) -> usize {
+    fn get_lineno(&self) -> usize {
         self.lineno
     }
     fn set_force_fork(&mut self) {
@@ -76,9 +79,9 @@ impl Command for ArithmeticCommand {
 }
 
 impl ArithmeticCommand {
-    pub fn eval(&mut self, core: &mut ShellCore) -> Result<String, ExecError> {
+    pub fn eval(&self, core: &mut ShellCore) -> Result<String, ExecError> {
         let mut ans = String::new();
-        for a in &mut self.expressions {
+        for a in &self.expressions {
             ans = a.eval(core)?;
         }
 
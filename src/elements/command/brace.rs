@@ -18,9 +18,9 @@ pub struct BraceCommand {
 }
 
 impl Command for BraceCommand {
-    fn run(&mut self, core: &mut ShellCore, _: bool) -> Result<(), ExecError> {
+    fn run(&self, core: &mut ShellCore, _: bool) -> Result<(), ExecError> {
         match self.script {
-            Some(ref mut s) => s.exec(core)?,
+            Some(ref s) => s.exec(core)?,
             _ => exit::internal(" (ParenCommand::exec)"),
         }
         Ok(())
@@ -29,10 +29,13 @@ impl Command for BraceCommand {
     fn get_text(&self) -> String {
         self.text.clone()
     }
-    fn get_redirects(&mut self) -> &mut Vec<Redirect> {
+    fn get_redirects(&self) -> &[Redirect] {
+        &self.redirects
+    }
+    fn get_redirects_mut(&mut self) -> &mut Vec<Redirect> {
         &mut self.redirects
     }
-    fn get_lineno(&mut self) -> usize {
+    fn get_lineno(&self) -> usize {
         self.lineno
     }
     fn set_force_fork(&mut self) {
@@ -45,9 +48,9 @@ impl Command for BraceCommand {
         self.force_fork
     }
 
-    fn pretty_print(&mut self, indent_num: usize) {
+    fn pretty_print(&self, indent_num: usize) {
         println!("{{ ");
-        for s in self.script.iter_mut() {
+        for s in self.script.iter() {
             s.pretty_print(indent_num + 1);
         }
         println!("}}");
@@ -20,11 +20,11 @@ pub struct CaseCommand {
 }
 
 impl Command for CaseCommand {
-    fn run(&mut self, core: &mut ShellCore, _: bool) -> Result<(), ExecError> {
+    fn run(&self, core: &mut ShellCore, _: bool) -> Result<(), ExecError> {
         core.db
             .set_param("LINENO", &self.lineno.to_string(), None)?;
         let mut next = false;
-        let word = self.word.clone().unwrap();
+        let word = self.word.as_ref().unwrap();
 
         if core.db.flags.contains('x') {
             let ps4 = core.get_ps4();
@@ -38,8 +38,8 @@ impl Command for CaseCommand {
 
         let extglob = core.shopts.query("extglob");
 
-        for e in &mut self.patterns_script_end {
-            for pattern in &mut e.0 {
+        for e in &self.patterns_script_end {
+            for pattern in &e.0 {
                 let mut exec_script = false;
                 if !next {
                     let p = match pattern.eval_for_case_pattern(core) {
@@ -69,10 +69,13 @@ impl Command for CaseCommand {
     fn get_text(&self) -> String {
         self.text.clone()
     }
-    fn get_redirects(&mut self) -> &mut Vec<Redirect> {
+    fn get_redirects(&self) -> &[Redirect] {
+        &self.redirects
+    }
+    fn get_redirects_mut(&mut self) -> &mut Vec<Redirect> {
         &mut self.redirects
     }
-    fn get_lineno(&mut self) -> usize {
+    fn get_lineno(&self) -> usize {
         self.lineno
     }
     fn set_force_fork(&mut self) {
@@ -9,6 +9,7 @@ use crate::elements::command::{
 };
 use crate::error::exec::ExecError;
 use crate::error::parse::ParseError;
+use crate::i18n::fl_args;
 use crate::utils;
 use crate::{Feeder, ShellCore};
 //use nix::unistd;
@@ -29,7 +30,7 @@ pub struct Coprocess {
 }
 
 impl Command for Coprocess {
-    fn exec(&mut self, core: &mut ShellCore, _: &mut Pipe) -> Result<Option<Pid>, ExecError> {
+    fn exec(&self, core: &mut ShellCore, _: &mut Pipe) -> Result<Option<Pid>, ExecError> {
         if core.break_counter > 0 || core.continue_counter > 0 {
             return Ok(None);
         }
@@ -83,8 +84,8 @@ impl Command for Coprocess {
         entry.coproc_fds = fds.clone();
 
         if let Some(pid) = core.get_jobentry_pid_by_coproc_name(&self.name) {
-            let msg = format!("warning: execute_coproc: coproc [{}:{}] still exists",
-                              &pid, &self.name);
+            let msg = fl_args("coproc-still-exists",
+                              &[("pid", &pid.to_string()), ("name", &self.name)]);
             let err = ExecError::Other(msg);
             err.print(core);
         }
@@ -110,16 +111,19 @@ impl Command for Coprocess {
         Ok(None)
     }
 
-    fn run(&mut self, _: &mut ShellCore, _: bool) -> Result<(), ExecError> {
+    fn run(&self, _: &mut ShellCore, _: bool) -> Result<(), ExecError> {
         Ok(())
     }
     fn get_text(&self) -> String {
         self.text.clone()
     }
-    fn get_redirects(&mut self) -> &mut Vec<Redirect> {
+    fn get_redirects(&self) -> &[Redirect] {
+        &self._dummy
+    }
+    fn get_redirects_mut(&mut self) -> &mut Vec<Redirect> {
         &mut self._dummy
     }
-    fn get_lineno(&mut self) -> usize {
+    fn get_lineno(&self) -> usize {
         self.lineno
     }
     fn set_force_fork(&mut self) {
@@ -132,15 +136,15 @@ impl Command for Coprocess {
         self.force_fork
     }
 
-    fn pretty_print(&mut self, indent_num: usize) {
+    fn pretty_print(&self, indent_num: usize) {
         self.pretty_print(indent_num);
     }
 }
 
 impl Coprocess {
-    pub fn pretty_print(&mut self, indent_num: usize) {
+    pub fn pretty_print(&self, indent_num: usize) {
         println!("{} () ", self.name);
-        for com in self.command.iter_mut() {
+        for com in self.command.iter() {
             com.pretty_print(indent_num);
         }
     }
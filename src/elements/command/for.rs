@@ -26,7 +26,7 @@ pub struct ForCommand {
 }
 
 impl Command for ForCommand {
-    fn run(&mut self, core: &mut ShellCore, _: bool) -> Result<(), ExecError> {
+    fn run(&self, core: &mut ShellCore, _: bool) -> Result<(), ExecError> {
         core.loop_level += 1;
 
         let ok = match self.has_arithmetic {
@@ -48,10 +48,13 @@ impl Command for ForCommand {
     fn get_text(&self) -> String {
         self.text.clone()
     }
-    fn get_redirects(&mut self) -> &mut Vec<Redirect> {
+    fn get_redirects(&self) -> &[Redirect] {
+        &self.redirects
+    }
+    fn get_redirects_mut(&mut self) -> &mut Vec<Redirect> {
         &mut self.redirects
     }
-    fn get_lineno(&mut self) -> usize {
+    fn get_lineno(&self) -> usize {
         self.lineno
     }
     fn set_force_fork(&mut self) {
@@ -66,9 +69,9 @@ impl Command for ForCommand {
 }
 
 impl ForCommand {
-    fn eval_values(&mut self, core: &mut ShellCore) -> Option<Vec<String>> {
+    fn eval_values(&self, core: &mut ShellCore) -> Option<Vec<String>> {
         let mut ans = vec![];
-        for w in &mut self.values {
+        for w in &self.values {
             match w.eval(core) {
                 Ok(mut ws) => ans.append(&mut ws),
                 Err(e) => {
@@ -81,7 +84,7 @@ impl ForCommand {
         Some(ans)
     }
 
-    fn run_with_values(&mut self, core: &mut ShellCore) -> bool {
+    fn run_with_values(&self, core: &mut ShellCore) -> bool {
         let values = match self.has_in {
             true => match self.eval_values(core) {
                 Some(vs) => vs,
@@ -114,7 +117,7 @@ impl ForCommand {
                 core.continue_counter -= 1;
             }
 
-            if let Some(mut s) = self.do_script.clone() {
+            if let Some(s) = &self.do_script {
                 let _ = s.exec(core);
             }
 
@@ -132,7 +135,7 @@ impl ForCommand {
         true
     }
 
-    fn eval_arithmetic(a: &mut Option<ArithmeticExpr>, core: &mut ShellCore) -> (bool, String) {
+    fn eval_arithmetic(a: &Option<ArithmeticExpr>, core: &mut ShellCore) -> (bool, String) {
         match a {
             None => (true, "1".to_string()),
             Some(arith) => match arith.eval(core) {
@@ -142,8 +145,8 @@ impl ForCommand {
         }
     }
 
-    fn run_with_arithmetic(&mut self, core: &mut ShellCore) -> bool {
-        let (ok, _) = Self::eval_arithmetic(&mut self.arithmetics[0], core);
+    fn run_with_arithmetic(&self, core: &mut ShellCore) -> bool {
+        let (ok, _) = Self::eval_arithmetic(&self.arithmetics[0], core);
         if !ok {
             return false;
         }
@@ -153,7 +156,7 @@ impl ForCommand {
                 return false;
             }
 
-            let (ok, val) = Self::eval_arithmetic(&mut self.arithmetics[1], core);
+            let (ok, val) = Self::eval_arithmetic(&self.arithmetics[1], core);
             if val == "0" {
                 return ok;
             }
@@ -162,7 +165,7 @@ impl ForCommand {
                 core.continue_counter -= 1;
             }
 
-            if let Some(mut s) = self.do_script.clone() {
+            if let Some(s) = &self.do_script {
                 let _ = s.exec(core);
             }
 
@@ -174,7 +177,7 @@ impl ForCommand {
                 break;
             }
 
-            let (ok, _) = Self::eval_arithmetic(&mut self.arithmetics[2], core);
+            let (ok, _) = Self::eval_arithmetic(&self.arithmetics[2], core);
             if !ok {
                 return false;
             }
@@ -7,42 +7,47 @@ use crate::elements::command::{BraceCommand, IfCommand, ParenCommand, WhileComma
 use crate::error::exec::ExecError;
 use crate::error::parse::ParseError;
 use crate::utils;
-use crate::{Feeder, ShellCore};
+use crate::{signal, Feeder, ShellCore};
 use nix::unistd::Pid;
+use std::rc::Rc;
 
 #[derive(Debug, Clone, Default)]
 pub struct FunctionDefinition {
     pub text: String,
     pub file: String,
     name: String,
-    command: Option<Box<dyn Command>>,
+    command: Option<Rc<dyn Command>>,
     force_fork: bool,
     _dummy: Vec<Redirect>,
     lineno: usize,
 }
 
 impl Command for FunctionDefinition {
-    fn exec(&mut self, core: &mut ShellCore, _: &mut Pipe) -> Result<Option<Pid>, ExecError> {
+    fn exec(&self, core: &mut ShellCore, _: &mut Pipe) -> Result<Option<Pid>, ExecError> {
         if core.break_counter > 0 || core.continue_counter > 0 {
             return Ok(None);
         }
 
         core.db
             .functions
-            .insert(self.name.to_string(), self.clone());
+            .insert(self.name.to_string(), Rc::new(self.clone()));
+        core.db.exit_status = 0;
         Ok(None)
     }
 
-    fn run(&mut self, _: &mut ShellCore, _: bool) -> Result<(), ExecError> {
+    fn run(&self, _: &mut ShellCore, _: bool) -> Result<(), ExecError> {
         Ok(())
     }
     fn get_text(&self) -> String {
         self.text.clone()
     }
-    fn get_redirects(&mut self) -> &mut Vec<Redirect> {
+    fn get_redirects(&self) -> &[Redirect] {
+        &self._dummy
+    }
+    fn get_redirects_mut(&mut self) -> &mut Vec<Redirect> {
         &mut self._dummy
     }
-    fn get_lineno(&mut self) -> usize {
+    fn get_lineno(&self) -> usize {
         self.lineno
     }
     fn set_force_fork(&mut self) {
@@ -55,20 +60,20 @@ impl Command for FunctionDefinition {
         self.force_fork
     }
 
-    fn pretty_print(&mut self, indent_num: usize) {
+    fn pretty_print(&self, indent_num: usize) {
         self.pretty_print(indent_num);
     }
 }
 
 impl FunctionDefinition {
-    pub fn pretty_print(&mut self, indent_num: usize) {
+    pub fn pretty_print(&self, indent_num: usize) {
         println!("{} () ", self.name);
-        for com in self.command.iter_mut() {
+        for com in self.command.iter() {
             com.pretty_print(indent_num);
         }
     }
 
-    pub fn run_as_command(&mut self, args: &mut [String], core: &mut ShellCore) {
+    pub fn run_as_command(&self, args: &mut [String], core: &mut ShellCore) {
         if ! core.db.exist("FUNCNAME") {
             if  core.script_name == "-" {
                 let _ = core.db.init_array("FUNCNAME", None, Some(0), false);
@@ -96,12 +101,16 @@ impl FunctionDefinition {
 
         let mut dummy = Pipe::new("|".to_string());
 
+        let traps = signal::hide_traps_from_function(core);
         core.source_function_level += 1;
-        if let Err(e) = self.command.as_mut().unwrap().exec(core, &mut dummy) {
+        if let Err(e) = self.command.as_ref().unwrap().exec(core, &mut dummy) {
             e.print(core);
         }
+        signal::run_return_trap(core);
         core.return_flag = false;
+        core.err_trap_fired = false; // the call itself can fire ERR
         core.source_function_level -= 1;
+        signal::restore_traps_after_function(core, traps);
 
         core.db.position_parameters.pop();
 
@@ -117,6 +126,7 @@ impl FunctionDefinition {
         source.remove(0);
         let _ = core.db.init_array("BASH_LINENO", Some(linenos), None, false);
         let _ = core.db.init_array("BASH_SOURCE", Some(source), None, false);
+        let _ = core.db.set_param("LINENO", &lineno, None);
     }
 
     fn eat_header(&mut self, feeder: &mut Feeder, core: &mut ShellCore) -> bool {
@@ -147,13 +157,13 @@ impl FunctionDefinition {
 
     fn eat_body(&mut self, feeder: &mut Feeder, core: &mut ShellCore) -> Result<(), ParseError> {
         self.command = if let Some(a) = IfCommand::parse(feeder, core)? {
-            Some(Box::new(a))
+            Some(Rc::new(a))
         } else if let Some(a) = ParenCommand::parse(feeder, core, false)? {
-            Some(Box::new(a))
+            Some(Rc::new(a))
         } else if let Some(a) = BraceCommand::parse(feeder, core)? {
-            Some(Box::new(a))
+            Some(Rc::new(a))
         } else if let Some(a) = WhileCommand::parse(feeder, core)? {
-            Some(Box::new(a))
+            Some(Rc::new(a))
         } else {
             None
         };
@@ -191,3 +201,21 @@ impl FunctionDefinition {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::elements::script::Script;
+    use crate::{Feeder, ShellCore};
+
+    #[test]
+    fn definition_succeeds() {
+        let mut core = ShellCore::new();
+        core.set_builtins();
+        let mut feeder = Feeder::new("n=0; trap 'n=$((n+1))' ERR; false; f(){ :; }");
+        let script = Script::parse(&mut feeder, &mut core, false).unwrap().unwrap();
+        let _ = script.exec(&mut core);
+
+        assert_eq!(core.db.exit_status, 0);
+        assert_eq!(core.db.get_param("n").unwrap(), "1");
+    }
+}
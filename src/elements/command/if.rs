@@ -20,9 +20,14 @@ pub struct IfCommand {
 }
 
 impl Command for IfCommand {
-    fn run(&mut self, core: &mut ShellCore, _: bool) -> Result<(), ExecError> {
+    fn run(&self, core: &mut ShellCore, _: bool) -> Result<(), ExecError> {
         for i in 0..self.if_elif_scripts.len() {
-            self.if_elif_scripts[i].exec(core)?;
+            let susp_e_option = core.suspend_e_option;
+            core.suspend_e_option = true;
+            let result = self.if_elif_scripts[i].exec(core);
+            core.suspend_e_option = susp_e_option;
+            result?;
+
             if core.db.exit_status == 0 {
                 let _ = self.then_scripts[i].exec(core);
                 return Ok(());
@@ -31,7 +36,7 @@ impl Command for IfCommand {
             }
         }
 
-        if let Some(s) = self.else_script.as_mut() {
+        if let Some(s) = self.else_script.as_ref() {
             s.exec(core)?
         }
         Ok(())
@@ -40,10 +45,13 @@ impl Command for IfCommand {
     fn get_text(&self) -> String {
         self.text.clone()
     }
-    fn get_redirects(&mut self) -> &mut Vec<Redirect> {
+    fn get_redirects(&self) -> &[Redirect] {
+        &self.redirects
+    }
+    fn get_redirects_mut(&mut self) -> &mut Vec<Redirect> {
         &mut self.redirects
     }
-    fn get_lineno(&mut self) -> usize {
+    fn get_lineno(&self) -> usize {
         self.lineno
     }
     fn set_force_fork(&mut self) {
@@ -49,7 +49,7 @@ pub struct ParenCommand {
 }
 
 impl Command for ParenCommand {
-    fn exec(&mut self, core: &mut ShellCore, pipe: &mut Pipe) -> Result<Option<Pid>, ExecError> {
+    fn exec(&self, core: &mut ShellCore, pipe: &mut Pipe) -> Result<Option<Pid>, ExecError> {
         if core.break_counter > 0 || core.continue_counter > 0 {
             return Ok(None);
         }
@@ -57,13 +57,13 @@ impl Command for ParenCommand {
         self.fork_exec(core, pipe)
     }
 
-    fn run(&mut self, core: &mut ShellCore, fork: bool) -> Result<(), ExecError> {
+    fn run(&self, core: &mut ShellCore, fork: bool) -> Result<(), ExecError> {
         if !fork {
             exit::internal(" (no fork for subshell)");
         }
 
         match self.script {
-            Some(ref mut s) => s.exec(core)?,
+            Some(ref s) => s.exec(core)?,
             _ => exit::internal(" (ParenCommand::exec)"),
         }
 
@@ -73,10 +73,13 @@ impl Command for ParenCommand {
     fn get_text(&self) -> String {
         self.text.clone()
     }
-    fn get_redirects(&mut self) -> &mut Vec<Redirect> {
+    fn get_redirects(&self) -> &[Redirect] {
+        &self.redirects
+    }
+    fn get_redirects_mut(&mut self) -> &mut Vec<Redirect> {
         &mut self.redirects
     }
-    fn get_lineno(&mut self) -> usize {
+    fn get_lineno(&self) -> usize {
         self.lineno
     }
     fn set_force_fork(&mut self) {}
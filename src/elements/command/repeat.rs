@@ -20,7 +20,7 @@ pub struct RepeatCommand {
 }
 
 impl Command for RepeatCommand {
-    fn run(&mut self, core: &mut ShellCore, _: bool) -> Result<(), ExecError> {
+    fn run(&self, core: &mut ShellCore, _: bool) -> Result<(), ExecError> {
         let n = utils::string_to_calculated_string(&self.times.text, core)?.parse::<usize>()?;
         /*
         let mut f = Feeder::new(&self.times.text);
@@ -31,7 +31,7 @@ impl Command for RepeatCommand {
         };*/
 
         for _ in 0..n {
-            self.job.clone().exec(core, false)?;
+            self.job.exec(core, false)?;
         }
 
         Ok(())
@@ -40,10 +40,13 @@ impl Command for RepeatCommand {
     fn get_text(&self) -> String {
         self.text.clone()
     }
-    fn get_redirects(&mut self) -> &mut Vec<Redirect> {
+    fn get_redirects(&self) -> &[Redirect] {
+        &self._dummy
+    }
+    fn get_redirects_mut(&mut self) -> &mut Vec<Redirect> {
         &mut self._dummy
     }
-    fn get_lineno(&mut self) -> usize {
+    fn get_lineno(&self) -> usize {
         self.lineno
     }
     fn set_force_fork(&mut self) {
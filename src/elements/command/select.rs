@@ -0,0 +1,310 @@
+//SPDX-FileCopyrightText: 2025 Ryuichi Ueda ryuichiueda@gmail.com
+//SPDX-License-Identifier: BSD-3-Clause
+
+use crate::{Feeder, Script, ShellCore};
+
+use super::{Command, Redirect};
+use crate::core::builtins::read;
+use crate::elements::command;
+use crate::elements::word::Word;
+use crate::error::exec::ExecError;
+use crate::error::parse::ParseError;
+use std::io::Write;
+use std::sync::atomic::Ordering::Relaxed;
+use unicode_width::UnicodeWidthStr;
+
+#[derive(Debug, Clone, Default)]
+pub struct SelectCommand {
+    text: String,
+    name: String,
+    has_in: bool,
+    values: Vec<Word>,
+    do_script: Option<Script>,
+    redirects: Vec<Redirect>,
+    force_fork: bool,
+    lineno: usize,
+}
+
+impl Command for SelectCommand {
+    fn run(&self, core: &mut ShellCore, _: bool) -> Result<(), ExecError> {
+        let values = match self.has_in {
+            true => match self.eval_values(core) {
+                Some(vs) => vs,
+                None => {
+                    core.db.exit_status = 1;
+                    return Ok(());
+                }
+            },
+            false => core.db.get_position_params(),
+        };
+
+        if values.is_empty() {
+            core.db.exit_status = 0;
+            return Ok(());
+        }
+
+        core.loop_level += 1;
+        self.run_menu(core, &values);
+        core.loop_level -= 1;
+        if core.loop_level == 0 {
+            core.break_counter = 0;
+        }
+        Ok(())
+    }
+
+    fn get_text(&self) -> String {
+        self.text.clone()
+    }
+    fn get_redirects(&self) -> &[Redirect] {
+        &self.redirects
+    }
+    fn get_redirects_mut(&mut self) -> &mut Vec<Redirect> {
+        &mut self.redirects
+    }
+    fn get_lineno(&self) -> usize {
+        self.lineno
+    }
+    fn set_force_fork(&mut self) {
+        self.force_fork = true;
+    }
+    fn boxed_clone(&self) -> Box<dyn Command> {
+        Box::new(self.clone())
+    }
+    fn force_fork(&self) -> bool {
+        self.force_fork
+    }
+}
+
+impl SelectCommand {
+    fn eval_values(&self, core: &mut ShellCore) -> Option<Vec<String>> {
+        let mut ans = vec![];
+        for w in &self.values {
+            match w.eval(core) {
+                Ok(mut ws) => ans.append(&mut ws),
+                Err(e) => {
+                    e.print(core);
+                    return None;
+                }
+            }
+        }
+
+        Some(ans)
+    }
+
+    fn run_menu(&self, core: &mut ShellCore, values: &[String]) {
+        let mut show_menu = true;
+
+        while !core.return_flag {
+            if core.sigint.load(Relaxed) {
+                core.db.exit_status = 130;
+                return;
+            }
+
+            let choice = match Self::query(core, values, show_menu) {
+                Some(c) => c,
+                None => {
+                    println!();
+                    core.db.exit_status = 1;
+                    return;
+                }
+            };
+            show_menu = false;
+
+            let result = match core.db.has_flag(&self.name, 'n') {
+                true => core.db.set_nameref(&self.name, &choice, None),
+                false => core.db.set_param(&self.name, &choice, None),
+            };
+            if let Err(e) = result {
+                core.db.exit_status = 1;
+                e.print(core);
+            }
+
+            if core.continue_counter > 0 {
+                core.continue_counter -= 1;
+            }
+
+            if let Some(s) = &self.do_script
+                && let Err(e) = s.exec(core)
+            {
+                e.print(core);
+                break;
+            }
+
+            if core.break_counter > 0 {
+                core.break_counter -= 1;
+                break;
+            }
+            if core.continue_counter > 1 {
+                break;
+            }
+        }
+        if core.continue_counter > 0 {
+            core.continue_counter -= 1;
+        }
+    }
+
+    fn query(core: &mut ShellCore, values: &[String], mut show_menu: bool) -> Option<String> {
+        loop {
+            if show_menu {
+                Self::print_menu(core, values);
+            }
+            let prompt = match core.db.exist("PS3") {
+                true => core.db.get_param("PS3").unwrap_or_default(),
+                false => "#? ".to_string(),
+            };
+            eprint!("{}", &prompt);
+            let _ = std::io::stderr().flush();
+
+            if read::read(core, &["read".to_string()]) != 0 {
+                return None;
+            }
+
+            let reply = core.db.get_param("REPLY").unwrap_or_default();
+            if reply.is_empty() {
+                show_menu = true;
+                continue;
+            }
+
+            return match reply.parse::<usize>() {
+                Ok(n) if n >= 1 && n <= values.len() => Some(values[n - 1].clone()),
+                _ => Some("".to_string()),
+            };
+        }
+    }
+
+    /* the layout follows print_select_list of Bash */
+    fn print_menu(core: &mut ShellCore, values: &[String]) {
+        let number_len = |n: usize| n.to_string().len();
+        let columns = match core.db.get_param("COLUMNS") {
+            Ok(s) => s.parse::<usize>().unwrap_or(80),
+            _ => 80,
+        };
+
+        let index_len = number_len(values.len());
+        let max_len = values.iter().map(|v| v.width()).max().unwrap_or(0) + index_len + 4;
+
+        let mut cols = std::cmp::max(columns / max_len, 1);
+        let mut rows = values.len().div_ceil(cols);
+        cols = values.len().div_ceil(rows);
+        if rows == 1 {
+            rows = cols;
+        }
+
+        let first_index_len = number_len(rows);
+        let mut menu = String::new();
+        for row in 0..rows {
+            let mut i = row;
+            let mut pos = 0;
+            loop {
+                let len = if pos == 0 { first_index_len } else { index_len };
+                menu += &format!("{:>len$}) {}", i + 1, &values[i]);
+                let elem_len = values[i].width() + len + 2;
+
+                i += rows;
+                if i >= values.len() {
+                    break;
+                }
+                Self::indent(&mut menu, pos + elem_len, pos + max_len);
+                pos += max_len;
+            }
+            menu.push('\n');
+        }
+        eprint!("{}", &menu);
+    }
+
+    fn indent(menu: &mut String, mut from: usize, to: usize) {
+        while from < to {
+            if to / 8 > from / 8 {
+                menu.push('\t');
+                from += 8 - from % 8;
+            } else {
+                menu.push(' ');
+                from += 1;
+            }
+        }
+    }
+
+    fn eat_name(feeder: &mut Feeder, ans: &mut Self, core: &mut ShellCore) -> bool {
+        command::eat_blank_with_comment(feeder, core, &mut ans.text);
+
+        let len = feeder.scanner_name(core);
+        if len == 0 {
+            return false;
+        }
+
+        ans.name = feeder.consume(len);
+        ans.text += &ans.name.clone();
+        command::eat_blank_with_comment(feeder, core, &mut ans.text);
+        true
+    }
+
+    fn eat_in_part(
+        feeder: &mut Feeder,
+        ans: &mut Self,
+        core: &mut ShellCore,
+    ) -> Result<(), ParseError> {
+        if !feeder.starts_with("in") {
+            return Ok(());
+        }
+
+        ans.text += &feeder.consume(2);
+        ans.has_in = true;
+
+        loop {
+            command::eat_blank_with_comment(feeder, core, &mut ans.text);
+            match Word::parse(feeder, core, None)? {
+                Some(w) => {
+                    ans.text += &w.text.clone();
+                    ans.values.push(w);
+                }
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    fn eat_end(feeder: &mut Feeder, ans: &mut Self, core: &mut ShellCore) -> bool {
+        command::eat_blank_with_comment(feeder, core, &mut ans.text);
+        if feeder.starts_with(";") || feeder.starts_with("\n") {
+            ans.text += &feeder.consume(1);
+            command::eat_blank_with_comment(feeder, core, &mut ans.text);
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn parse(feeder: &mut Feeder, core: &mut ShellCore) -> Result<Option<Self>, ParseError> {
+        if !feeder.starts_with("select") {
+            return Ok(None);
+        }
+        let mut ans = Self {
+            lineno: feeder.lineno,
+            text: feeder.consume(6),
+            ..Default::default()
+        };
+
+        if !Self::eat_name(feeder, &mut ans, core) {
+            return Err(ParseError::UnexpectedSymbol("select".to_string()));
+        }
+        Self::eat_in_part(feeder, &mut ans, core)?;
+
+        if !Self::eat_end(feeder, &mut ans, core) {
+            return Ok(None);
+        }
+
+        command::eat_blank_lines(feeder, core, &mut ans.text)?;
+
+        if command::eat_inner_script(feeder, core, "do", vec!["done"], &mut ans.do_script, false)? {
+            ans.text.push_str("do");
+            if let Some(ref mut s) = ans.do_script {
+                ans.text.push_str(&s.get_text());
+            }
+            ans.text.push_str(&feeder.consume(4)); //done
+
+            command::eat_redirects(feeder, core, &mut ans.redirects, &mut ans.text)?;
+            Ok(Some(ans))
+        } else {
+            Ok(None)
+        }
+    }
+}
@@ -6,9 +6,12 @@ pub mod hash;
 pub mod parser;
 pub mod run_internal;
 
-use crate::{proc_ctrl, ShellCore};
+use crate::i18n::fl_args;
+use crate::core::builtins::exec;
+use crate::{file_check, proc_ctrl, signal, ShellCore};
 
-use super::{Command, Pipe, Redirect};
+use super::{Command, Pipe, Redirect, RedirectFds};
+use crate::elements::command;
 use crate::elements::substitution::Substitution;
 use crate::elements::word::Word;
 use crate::error::exec::ExecError;
@@ -36,7 +39,6 @@ pub struct SimpleCommand {
     text: String,
     substitutions: Vec<Substitution>,
     words: Vec<Word>,
-    pub args: Vec<String>,
     redirects: Vec<Redirect>,
     force_fork: bool,
     substitutions_as_args: Vec<SubsArgType>,
@@ -44,58 +46,50 @@ pub struct SimpleCommand {
     pub lineno: usize,
     continue_alias_check: bool,
     invalid_alias: bool,
-    command_path: String,
 }
 
 impl Command for SimpleCommand {
-    fn exec(&mut self, core: &mut ShellCore, pipe: &mut Pipe) -> Result<Option<Pid>, ExecError> {
+    fn exec(&self, core: &mut ShellCore, pipe: &mut Pipe) -> Result<Option<Pid>, ExecError> {
         core.db
             .set_param("LINENO", &self.lineno.to_string(), None)?;
         if Self::break_continue_or_return(core) {
             return Ok(None);
         }
 
-        core.db.set_param("BASH_COMMAND", &self.text, None)?;
+        if !core.pseudo_trap_running {
+            core.db.set_param("BASH_COMMAND", &self.text, None)?;
+            signal::run_debug_trap(core);
+        }
 
-        self.args.clear();
-        let mut words = self.words.to_vec();
-        for w in words.iter_mut() {
-            w.set_pipe(core); //for >()
-            self.set_arg(w, core)?;
+        /* the parsed command is shared; args and fds belong to this execution */
+        let mut args = vec![];
+        for w in self.words.iter() {
+            Self::set_arg(w, &mut args, core)?;
         }
 
-        if !self.args.is_empty() && self.args[0].starts_with("%") {
-            self.redirects.clear();
-            self.args.insert(0, "fg".to_string());
+        let mut redirects = &self.redirects[..];
+        if !args.is_empty() && args[0].starts_with("%") {
+            redirects = &[];
+            args.insert(0, "fg".to_string());
         }
 
-        match self.args.len() {
+        match args.len() {
             0 => self.exec_set_param(core),
-            _ => self.exec_command(core, pipe),
+            _ => self.exec_command(args, redirects, core, pipe),
         }
     }
 
-    fn run(&mut self, core: &mut ShellCore, fork: bool) -> Result<(), ExecError> {
-        core.db.push_local();
-        let _ = self.set_local_params(core);
-
-        if !run_internal::run(self, core)? {
-            self.set_environment_variables(core)?;
-            proc_ctrl::exec_command(&self.args, core, &self.command_path);
-        };
-
-        core.db.pop_local();
-
-        match fork {
-            true => exit::normal(core),
-            false => Ok(()),
-        }
+    fn run(&self, _: &mut ShellCore, _: bool) -> Result<(), ExecError> {
+        exit::internal(" (SimpleCommand::run without args)")
     }
 
     fn get_text(&self) -> String {
         self.text.clone()
     }
-    fn get_redirects(&mut self) -> &mut Vec<Redirect> {
+    fn get_redirects(&self) -> &[Redirect] {
+        &self.redirects
+    }
+    fn get_redirects_mut(&mut self) -> &mut Vec<Redirect> {
         &mut self.redirects
     }
     fn set_force_fork(&mut self) {
@@ -108,7 +102,7 @@ impl Command for SimpleCommand {
         self.force_fork
     }
 
-    fn get_lineno(&mut self) -> usize {
+    fn get_lineno(&self) -> usize {
         self.lineno
     }
 }
@@ -119,56 +113,61 @@ impl SimpleCommand {
     }
 
     pub fn exec_command(
-        &mut self,
+        &self,
+        mut args: Vec<String>,
+        redirects: &[Redirect],
         core: &mut ShellCore,
         pipe: &mut Pipe,
     ) -> Result<Option<Pid>, ExecError> {
         Self::check_sigint(core)?;
 
-        core.db.last_arg = self.args.last().unwrap().clone();
-        self.option_x_output(core);
+        core.db.last_arg = args.last().unwrap().clone();
+        self.option_x_output(&args, core);
 
-        if core.db.flags.contains('r') && self.args[0].contains('/') {
-            let msg = format!(
-                "{}: restricted: cannot specify `/' in command names",
-                &self.args[0]
-            );
+        if core.db.flags.contains('r') && args[0].contains('/') {
+            let msg = fl_args("restricted-slash-in-command", &[("name", &args[0])]);
             return Err(ExecError::Other(msg));
         }
 
-        if self.args[0] == "command" && self.args.len() > 1 {
-            if core.subst_builtins.contains_key(&self.args[1])
-            || core.db.functions.contains_key(&self.args[1]) {
-                self.args.remove(0);
-            }
+        if args[0] == "command" && args.len() > 1
+            && (core.subst_builtins.contains_key(&args[1])
+                || core.db.functions.contains_key(&args[1])) {
+            args.remove(0);
         }
 
-        let internal = core.builtins.contains_key(&self.args[0])
-                       || core.subst_builtins.contains_key(&self.args[0])
-                       || core.db.functions.contains_key(&self.args[0]);
+        let internal = core.builtins.contains_key(&args[0])
+                       || core.subst_builtins.contains_key(&args[0])
+                       || core.db.functions.contains_key(&args[0]);
+
+        if !internal && Self::is_autocd(&args[0], core) {
+            eprintln!("cd -- {}", &args[0]);
+            args.insert(0, "--".to_string());
+            args.insert(0, "cd".to_string());
+            return self.exec_command(args, redirects, core, pipe);
+        }
 
         if self.force_fork
             || (!pipe.lastpipe && pipe.is_connected())
             || !internal {
-            if ! internal {
-                self.command_path = hash::get_and_regist(self, core)?;
-            }
-            self.fork_exec(core, pipe)
-        } else if self.args.len() == 1 && self.args[0] == "exec" {
-            for r in self.get_redirects().iter_mut() {
-                if let Err(e) = r.connect(true, core) {
-                    e.print(core);
-                    core.db.exit_status = 1;
-                    break;
-                }
-            }
+            let command_path = match internal {
+                true => String::new(),
+                false => hash::get_and_regist(&args[0], core)?,
+            };
+            command::fork_exec_with(core, pipe, redirects, |core| {
+                self.run_command(&mut args, &command_path, core, true)
+            })
+        } else if args[0] == "exec" && exec::has_no_command(&args) {
+            Self::exec_redirects(redirects, core);
             Ok(None)
         } else {
             if let Err(e) = pipe.connect_lastpipe(core) {
                 e.print(core);
                 core.db.exit_status = 1;
             }
-            if let Err(e) = self.nofork_exec(core) {
+            let result = command::nofork_exec_with(core, redirects, |core| {
+                self.run_command(&mut args, "", core, false)
+            });
+            if let Err(e) = result {
                 e.print(core);
                 core.db.exit_status = 1;
             }
@@ -176,6 +175,54 @@ impl SimpleCommand {
         }
     }
 
+    fn run_command(
+        &self,
+        args: &mut [String],
+        command_path: &str,
+        core: &mut ShellCore,
+        fork: bool,
+    ) -> Result<(), ExecError> {
+        core.db.push_local();
+        let _ = self.set_local_params(core);
+
+        if !run_internal::run(self, args, core)? {
+            self.set_environment_variables(core)?;
+            proc_ctrl::exec_command(args, core, command_path);
+        };
+
+        core.db.pop_local();
+
+        match fork {
+            true => exit::normal(core),
+            false => Ok(()),
+        }
+    }
+
+    /* all of them are undone when one fails */
+    fn exec_redirects(redirects: &[Redirect], core: &mut ShellCore) {
+        let mut fds: Vec<RedirectFds> = vec![];
+        for r in redirects {
+            fds.push(RedirectFds::new());
+            if let Err(e) = r.connect(fds.last_mut().unwrap(), true, core) {
+                e.print(core);
+                for fd in fds.iter_mut().rev() {
+                    let _ = fd.restore(core);
+                }
+                core.db.exit_status = 1;
+                return;
+            }
+        }
+
+        fds.iter_mut().for_each(|fd| fd.persist(core));
+        core.db.exit_status = 0;
+    }
+
+    fn is_autocd(name: &str, core: &mut ShellCore) -> bool {
+        core.shopts.query("autocd")
+            && core.db.flags.contains('i')
+            && file_check::is_dir(name)
+    }
+
     fn check_sigint(core: &mut ShellCore) -> Result<(), ExecError> {
         if core.sigint.load(Relaxed) {
             core.db.exit_status = 130;
@@ -184,11 +231,11 @@ impl SimpleCommand {
         Ok(())
     }
 
-    fn exec_set_param(&mut self, core: &mut ShellCore) -> Result<Option<Pid>, ExecError> {
+    fn exec_set_param(&self, core: &mut ShellCore) -> Result<Option<Pid>, ExecError> {
         core.db.last_arg = String::new();
-        self.option_x_output(core);
+        self.option_x_output(&[], core);
 
-        for s in self.substitutions.iter_mut() {
+        for s in self.substitutions.iter() {
             if let Err(e) = s.eval(core, None, false) {
                 core.db.exit_status = 1;
                 if !core.db.flags.contains('i') {
@@ -205,30 +252,40 @@ impl SimpleCommand {
         Ok(None)
     }
 
-    fn set_local_params(&mut self, core: &mut ShellCore) -> Result<(), ExecError> {
+    fn set_local_params(&self, core: &mut ShellCore) -> Result<(), ExecError> {
         let scope = core.db.get_scope_num() - 1;
         if core.options.query("posix") {
-            for s in self.substitutions.clone().iter_mut() {
+            for s in self.substitutions.iter() {
                 s.eval(core, None, false)?;
             }
         }
-        for s in self.substitutions.iter_mut() {
+        for s in self.substitutions.iter() {
             s.eval(core, Some(scope), false)?;
         }
 
         Ok(())
     }
 
-    fn set_environment_variables(&mut self, core: &mut ShellCore) -> Result<(), ExecError> {
+    fn set_environment_variables(&self, core: &mut ShellCore) -> Result<(), ExecError> {
         let scope = core.db.get_scope_num() - 1;
         core.db.set_scope_to_env(scope);
         Ok(())
     }
 
-    fn set_arg(&mut self, word: &mut Word, core: &mut ShellCore) -> Result<(), ExecError> {
-        match word.eval(core) {
+    fn set_arg(word: &Word, args: &mut Vec<String>, core: &mut ShellCore) -> Result<(), ExecError> {
+        /* >() needs a pipe of its own */
+        let result = match word.text.contains(">(") {
+            true => {
+                let mut w = word.clone();
+                w.set_pipe(core);
+                w.eval(core)
+            }
+            false => word.eval(core),
+        };
+
+        match result {
             Ok(ws) => {
-                self.args.extend(ws);
+                args.extend(ws);
                 Ok(())
             }
             Err(e) => {
@@ -241,7 +298,7 @@ impl SimpleCommand {
         }
     }
 
-    fn option_x_output(&self, core: &mut ShellCore) {
+    fn option_x_output(&self, args: &[String], core: &mut ShellCore) {
         if !core.db.flags.contains('x') {
             return;
         }
@@ -251,12 +308,12 @@ impl SimpleCommand {
             eprintln!("\r{} {}\r", &ps4, &s.text);
         }
 
-        if self.args.is_empty() {
+        if args.is_empty() {
             return;
         }
 
         eprint!("{}", &ps4);
-        for a in &self.args {
+        for a in args {
             match a.contains(" ") {
                 false => eprint!(" {}", &a),
                 true => eprint!(" '{}'", &a),
@@ -1,25 +1,21 @@
 //SPDX-FileCopyrightText: 2025 Ryuichi Ueda ryuichiueda@gmail.com
 //SPDX-License-Identifier: BSD-3-Clause
 
-use super::SimpleCommand;
 use crate::elements::command::ExecError;
 use crate::{utils, ShellCore};
 
-pub fn get_and_regist(com: &mut SimpleCommand, core: &mut ShellCore) -> Result<String, ExecError> {
-    if ["/", "./", "../"]
-        .iter()
-        .any(|p| com.args[0].starts_with(p))
-    {
-        return Ok(com.args[0].clone());
+pub fn get_and_regist(name: &str, core: &mut ShellCore) -> Result<String, ExecError> {
+    if ["/", "./", "../"].iter().any(|p| name.starts_with(p)) {
+        return Ok(name.to_string());
     }
 
-    let mut path = core.db.get_elem("BASH_CMDS", &com.args[0])?;
+    let mut path = core.db.get_elem("BASH_CMDS", name)?;
 
     if path.is_empty() {
-        path = resolve_path(&com.args[0], core)?;
+        path = resolve_path(name, core)?;
     }
 
-    count_up(&com.args[0], core);
+    count_up(name, core);
     Ok(path)
 }
 
@@ -6,50 +6,53 @@ use super::SubsArgType;
 use crate::elements::substitution::Substitution;
 use crate::error::exec::ExecError;
 use crate::{Feeder, ShellCore};
+use std::rc::Rc;
 
-pub fn run(com: &mut SimpleCommand, core: &mut ShellCore) -> Result<bool, ExecError> {
-    let ans = run_function(&mut com.args, core)
-        || run_substitution_builtin(com, core)?
-        || run_builtin(com, core)?;
+pub fn run(com: &SimpleCommand, args: &mut [String], core: &mut ShellCore) -> Result<bool, ExecError> {
+    let ans = run_function(args, core)
+        || run_substitution_builtin(com, args, core)?
+        || run_builtin(args, core)?;
     Ok(ans)
 }
 
 fn run_function(args: &mut [String], core: &mut ShellCore) -> bool {
-    match core.db.functions.get_mut(&args[0]) {
+    /* the definition is shared, not copied */
+    match core.db.functions.get(&args[0]) {
         Some(f) => {
-            f.clone().run_as_command(args, core);
+            Rc::clone(f).run_as_command(args, core);
             true
         }
         None => false,
     }
 }
 
-pub fn run_builtin(com: &mut SimpleCommand, core: &mut ShellCore) -> Result<bool, ExecError> {
-    if com.args.is_empty() {
+pub fn run_builtin(args: &[String], core: &mut ShellCore) -> Result<bool, ExecError> {
+    if args.is_empty() {
         eprintln!("ShellCore::run_builtin");
         return Ok(false);
     }
 
-    if !core.builtins.contains_key(&com.args[0]) {
+    if !core.builtins.contains_key(&args[0]) {
         return Ok(false);
     }
 
-    let func = core.builtins[&com.args[0]];
-    core.db.exit_status = func(core, &com.args[..]);
+    let func = core.builtins[&args[0]];
+    core.db.exit_status = func(core, args);
     Ok(true)
 }
 
 pub fn run_substitution_builtin(
-    com: &mut SimpleCommand,
+    com: &SimpleCommand,
+    com_args: &[String],
     core: &mut ShellCore,
 ) -> Result<bool, ExecError> {
-    if !core.subst_builtins.contains_key(&com.args[0]) {
+    if !core.subst_builtins.contains_key(&com_args[0]) {
         return Ok(false);
     }
 
-    let mut args = vec![com.args[0].clone()];
+    let mut args = vec![com_args[0].clone()];
     let mut subs = vec![];
-    for sub in com.substitutions_as_args.iter_mut() {
+    for sub in com.substitutions_as_args.iter() {
         match sub {
             SubsArgType::Subs(s) => subs.push((**s).clone()),
             SubsArgType::Other(w) => {
@@ -64,7 +67,7 @@ pub fn run_substitution_builtin(
         }
     }
 
-    let func = core.subst_builtins[&com.args[0]];
+    let func = core.subst_builtins[&com_args[0]];
     core.db.exit_status = func(core, &args[..], &mut subs[..]);
     Ok(true)
 }
@@ -7,6 +7,7 @@ use crate::elements::expr::conditional::elem::CondElem;
 use crate::elements::expr::conditional::ConditionalExpr;
 use crate::error::exec::ExecError;
 use crate::error::parse::ParseError;
+use crate::i18n::fl;
 use crate::{Feeder, ShellCore};
 
 #[derive(Debug, Clone, Default)]
@@ -19,13 +20,13 @@ pub struct TestCommand {
 }
 
 impl Command for TestCommand {
-    fn run(&mut self, core: &mut ShellCore, _: bool) -> Result<(), ExecError> {
+    fn run(&self, core: &mut ShellCore, _: bool) -> Result<(), ExecError> {
         if core.db.flags.contains('x') {
             let ps4 = core.get_ps4();
             eprint!("\r{} [[ ", &ps4);
         }
 
-        match self.cond.clone().unwrap().eval(core) {
+        match self.cond.as_ref().unwrap().eval(core) {
             Ok(CondElem::Ans(true)) => core.db.exit_status = 0,
             Ok(CondElem::Ans(false)) => core.db.exit_status = 1,
             Err(err_msg) => {
@@ -34,7 +35,7 @@ impl Command for TestCommand {
             }
             _ => {
                 core.db.exit_status = 2;
-                return Err(ExecError::Other("unknown error".to_string()));
+                return Err(ExecError::Other(fl("unknown-error")));
             }
         };
         Ok(())
@@ -43,10 +44,13 @@ impl Command for TestCommand {
     fn get_text(&self) -> String {
         self.text.clone()
     }
-    fn get_redirects(&mut self) -> &mut Vec<Redirect> {
+    fn get_redirects(&self) -> &[Redirect] {
+        &self.redirects
+    }
+    fn get_redirects_mut(&mut self) -> &mut Vec<Redirect> {
         &mut self.redirects
     }
-    fn get_lineno(&mut self) -> usize {
+    fn get_lineno(&self) -> usize {
         self.lineno
     }
     fn set_force_fork(&mut self) {
@@ -13,12 +13,13 @@ pub struct WhileCommand {
     pub while_script: Option<Script>,
     pub do_script: Option<Script>,
     pub redirects: Vec<Redirect>,
+    until: bool,
     force_fork: bool,
     lineno: usize,
 }
 
 impl Command for WhileCommand {
-    fn run(&mut self, core: &mut ShellCore, _: bool) -> Result<(), ExecError> {
+    fn run(&self, core: &mut ShellCore, _: bool) -> Result<(), ExecError> {
         if core.return_flag {
             return Ok(());
         }
@@ -28,15 +29,15 @@ impl Command for WhileCommand {
                 core.continue_counter -= 1;
             }
             core.suspend_e_option = true;
-            self.while_script.clone().as_mut().unwrap().exec(core)?;
+            self.while_script.as_ref().unwrap().exec(core)?;
 
             core.suspend_e_option = false;
-            if core.db.exit_status != 0 {
+            if (core.db.exit_status != 0) != self.until {
                 core.db.exit_status = 0;
                 break;
             }
 
-            self.do_script.clone().as_mut().unwrap().exec(core)?;
+            self.do_script.as_ref().unwrap().exec(core)?;
 
             if core.break_counter > 0 {
                 core.break_counter -= 1;
@@ -60,10 +61,13 @@ impl Command for WhileCommand {
     fn get_text(&self) -> String {
         self.text.clone()
     }
-    fn get_redirects(&mut self) -> &mut Vec<Redirect> {
+    fn get_redirects(&self) -> &[Redirect] {
+        &self.redirects
+    }
+    fn get_redirects_mut(&mut self) -> &mut Vec<Redirect> {
         &mut self.redirects
     }
-    fn get_lineno(&mut self) -> usize {
+    fn get_lineno(&self) -> usize {
         self.lineno
     }
     fn set_force_fork(&mut self) {
@@ -81,13 +85,19 @@ impl WhileCommand {
     pub fn parse(feeder: &mut Feeder, core: &mut ShellCore) -> Result<Option<Self>, ParseError> {
         let mut ans = Self {
             lineno: feeder.lineno,
+            until: feeder.starts_with("until"),
             ..Default::default()
         };
 
+        let keyword = match ans.until {
+            true => "until",
+            false => "while",
+        };
+
         if !command::eat_inner_script(
             feeder,
             core,
-            "while",
+            keyword,
             vec!["do"],
             &mut ans.while_script,
             false,
@@ -97,7 +107,7 @@ impl WhileCommand {
         while command::eat_blank_with_comment(feeder, core, &mut ans.text) {}
 
         if command::eat_inner_script(feeder, core, "do", vec!["done"], &mut ans.do_script, false)? {
-            ans.text.push_str("while");
+            ans.text.push_str(keyword);
             ans.text
                 .push_str(&ans.while_script.as_mut().unwrap().get_text());
             ans.text.push_str("do");
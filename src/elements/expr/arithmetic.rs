@@ -11,6 +11,7 @@ use self::elem::ArithElem;
 use crate::elements::word::Word;
 use crate::error::arith::ArithError;
 use crate::error::exec::ExecError;
+use crate::i18n::fl_args;
 use crate::utils::exit;
 use crate::{Feeder, ShellCore};
 
@@ -21,10 +22,24 @@ pub struct ArithmeticExpr {
     output_base: String,
     hide_base: bool,
     in_ternary: bool,
+    expanded: bool,
 }
 
 impl ArithmeticExpr {
     pub fn eval_doller(&mut self, core: &mut ShellCore) -> Result<(), ExecError> {
+        if !self.expanded {
+            self.expand_words(core)?;
+        }
+
+        if core.db.flags.contains('x') {
+            let ps4 = core.get_ps4();
+            eprintln!("\r{} (( {} ))\r", ps4, &self.text);
+        }
+
+        Ok(())
+    }
+
+    fn expand_words(&mut self, core: &mut ShellCore) -> Result<(), ExecError> {
         let mut txt = String::new();
         for e in &self.elements {
             match e {
@@ -46,16 +61,10 @@ impl ArithmeticExpr {
             self.text = a.text;
             self.elements = a.elements;
         }
-
-        if core.db.flags.contains('x') {
-            let ps4 = core.get_ps4();
-            eprintln!("\r{} (( {} ))\r", ps4, &self.text);
-        }
-
         Ok(())
     }
 
-    pub fn eval(&mut self, core: &mut ShellCore) -> Result<String, ExecError> {
+    pub fn eval(&self, core: &mut ShellCore) -> Result<String, ExecError> {
         let mut cp = self.clone();
         cp.eval_doller(core)?;
 
@@ -89,8 +98,11 @@ impl ArithmeticExpr {
         match self.eval_elems(core, true)? {
             ArithElem::Integer(n) => Ok(n),
             ArithElem::Float(f) => {
-                let msg = format!("sush: {}: Not integer. {}", &self.text, f);
-                Err(ExecError::Other(msg))
+                let msg = fl_args(
+                    "not-integer",
+                    &[("expr", &self.text), ("num", &f.to_string())],
+                );
+                Err(ExecError::Other(format!("sush: {msg}")))
             }
             _ => exit::internal("invalid calculation result"),
         }
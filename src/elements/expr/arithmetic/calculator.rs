@@ -6,6 +6,7 @@ use super::elem::{float, int, ternary, variable};
 use super::rev_polish;
 use crate::error::arith::ArithError;
 use crate::error::exec::ExecError;
+use crate::i18n::fl;
 use crate::utils::exit;
 use crate::ShellCore;
 
@@ -18,7 +19,7 @@ pub fn pop_operand(
         return Ok(e);
     }
 
-    Err(ExecError::Other("no operand 2".to_string()))
+    Err(ExecError::Other(fl("no-second-operand")))
 }
 
 pub fn pop_operands(
@@ -36,7 +37,7 @@ pub fn pop_operands(
             return Ok((left_v, right_v));
         }
     }
-    Err(ExecError::Other("no operand 2".to_string()))
+    Err(ExecError::Other(fl("no-second-operand")))
 }
 
 fn bin_operation(
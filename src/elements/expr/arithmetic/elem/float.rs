@@ -5,6 +5,7 @@ use super::variable;
 use super::ArithElem;
 use crate::error::arith::ArithError;
 use crate::error::exec::ExecError;
+use crate::i18n::{fl, fl_args};
 use crate::{error, ShellCore};
 
 pub fn unary_calc(op: &str, num: f64, stack: &mut Vec<ArithElem>) -> Result<(), ExecError> {
@@ -12,9 +13,10 @@ pub fn unary_calc(op: &str, num: f64, stack: &mut Vec<ArithElem>) -> Result<(),
         "+" => stack.push(ArithElem::Float(num)),
         "-" => stack.push(ArithElem::Float(-num)),
         _ => {
-            return Err(ExecError::Other(
-                "not supported operator for float number".to_string(),
-            ))
+            return Err(ExecError::Other(fl_args(
+                "float-unsupported-operator",
+                &[("op", op)],
+            )))
         }
     }
     Ok(())
@@ -46,7 +48,7 @@ pub fn bin_calc(
         "!=" => stack.push(bool_to_01(left != right)),
         "/" => {
             if right == 0.0 {
-                return Err(ExecError::Other("divided by 0".to_string()));
+                return Err(ExecError::Other(fl("float-division-by-zero")));
             }
             stack.push(ArithElem::Float(left / right));
         }
@@ -58,9 +60,10 @@ pub fn bin_calc(
             }
         }
         _ => {
-            return Err(ExecError::Other(
-                "not supported operator for float numbers".to_string(),
-            ))
+            return Err(ExecError::Other(fl_args(
+                "float-unsupported-operator",
+                &[("op", op)],
+            )))
         }
     }
 
@@ -5,6 +5,7 @@ use super::super::calculator;
 use super::super::{ArithElem, ArithmeticExpr};
 use crate::error::arith::ArithError;
 use crate::error::exec::ExecError;
+use crate::i18n::fl;
 use crate::ShellCore;
 
 pub fn operation(
@@ -35,11 +36,7 @@ pub fn operation(
 
     let ans = match calculator::pop_operand(stack, core)? {
         ArithElem::Integer(0) => right.eval_in_cond(core)?,
-        ArithElem::Float(_) => {
-            return Err(ExecError::Other(
-                "float condition is not permitted".to_string(),
-            ))
-        }
+        ArithElem::Float(_) => return Err(ExecError::Other(fl("float-condition-not-permitted"))),
         _ => left.eval_in_cond(core)?,
     };
 
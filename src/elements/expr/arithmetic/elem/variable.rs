@@ -6,6 +6,7 @@ use super::{float, int};
 use crate::elements::expr::arithmetic::ArithmeticExpr;
 use crate::error::arith::ArithError;
 use crate::error::exec::ExecError;
+use crate::i18n::fl_args;
 use crate::utils;
 use crate::utils::exit;
 use crate::{Feeder, ShellCore};
@@ -186,6 +187,9 @@ fn subs(
         (ArithElem::Integer(cur), ArithElem::Float(right)) => {
             Ok(float::substitute(op, &name, sub, cur as f64, *right, core)?)
         }
-        _ => Err(ExecError::Other("not supported yet".to_string())),
+        _ => Err(ExecError::Other(fl_args(
+            "not-supported-yet",
+            &[("name", op)],
+        ))),
     }
 }
@@ -365,6 +365,13 @@ impl ArithmeticExpr {
                 break;
             }
         }
+
+        /* words without any expansion give the same result at every evaluation */
+        let is_static =
+            |c: char| c.is_ascii_alphanumeric() || " \t\n_+-*/%<>=!&|^~?:,()".contains(c);
+        if left != "[" && ans.text.chars().all(is_static) {
+            ans.expanded = ans.expand_words(core).is_ok();
+        }
         Ok(Some(ans))
     }
 }
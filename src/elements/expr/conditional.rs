@@ -12,6 +12,7 @@ use crate::elements::substitution::variable::Variable;
 use crate::elements::word::Word;
 use crate::error::arith::ArithError;
 use crate::error::exec::ExecError;
+use crate::i18n::{fl, fl_args};
 use crate::utils::{file_check, glob};
 use crate::{utils, Feeder, ShellCore};
 use regex::Regex;
@@ -30,7 +31,7 @@ fn pop_operand(
     glob: bool,
 ) -> Result<CondElem, ExecError> {
     match stack.pop() {
-        Some(CondElem::InParen(mut expr)) => expr.eval(core),
+        Some(CondElem::InParen(expr)) => expr.eval(core),
         Some(CondElem::Word(mut w)) => {
             if glob {
                 let p = w.eval_for_case_pattern(core)?;
@@ -50,7 +51,7 @@ pub struct ConditionalExpr {
 }
 
 impl ConditionalExpr {
-    pub fn eval(&mut self, core: &mut ShellCore) -> Result<CondElem, ExecError> {
+    pub fn eval(&self, core: &mut ShellCore) -> Result<CondElem, ExecError> {
         let mut cp = self.clone();
 
         let mut from = 0;
@@ -84,9 +85,10 @@ impl ConditionalExpr {
                         (CondElem::And, CondElem::Ans(ans)) => *ans,
                         (CondElem::Or, CondElem::Ans(ans)) => !ans,
                         _ => {
-                            return Err(ExecError::Other(
-                                "Internal error conditional.rs:55".to_string(),
-                            ))
+                            return Err(ExecError::Other(fl_args(
+                                "internal-error",
+                                &[("msg", "conditional.rs")],
+                            )))
                         }
                     };
                 }
@@ -161,7 +163,7 @@ impl ConditionalExpr {
                         stack.push(CondElem::Ans(s.is_empty()));
                         Ok(())
                     }
-                    _ => Err(ExecError::Other("no operand to negate".to_string())),
+                    _ => Err(ExecError::Other(fl("no-operand-to-negate"))),
                 },
                 // _ => Err(ExecError::Other( error::syntax("TODO"))),
                 _ => Err(ArithError::OperandExpected("TODO".to_string()).into()),
@@ -176,12 +178,9 @@ impl ConditionalExpr {
         if stack.len() != 1 {
             let mut err = "syntax error".to_string();
             if stack.len() > 1 {
-                err = format!(
-                    "syntax error in conditional expression: unexpected token `{}'",
-                    &stack[0].to_string()
-                );
+                err = fl_args("cond-unexpected-token", &[("token", &stack[0].to_string())]);
                 ExecError::Other(err).print(core);
-                err = format!("syntax error near `{}'", &stack[0].to_string());
+                err = fl_args("syntax-error-near", &[("token", &stack[0].to_string())]);
             }
             return Err(ExecError::Other(err));
         }
@@ -196,7 +195,7 @@ impl ConditionalExpr {
     ) -> Result<(), ExecError> {
         let operand = match pop_operand(stack, core, false) {
             Ok(CondElem::Operand(v)) => v,
-            Ok(_) => return Err(ExecError::Other("unknown operand".to_string())),
+            Ok(_) => return Err(ExecError::Other(fl("unknown-operand"))),
             Err(e) => return Err(e),
         };
 
@@ -204,7 +203,7 @@ impl ConditionalExpr {
             let ans = match op {
                 "-o" => core.options.query(&operand),
                 "-v" => {
-                    if env::var(&operand).is_ok() {
+                    if env::var_os(&operand).is_some() {
                         true
                     } else {
                         let mut f = Feeder::new(&operand);
@@ -230,19 +229,19 @@ impl ConditionalExpr {
     fn regex_operation(stack: &mut Vec<CondElem>, core: &mut ShellCore) -> Result<(), ExecError> {
         let right = match pop_operand(stack, core, false) {
             Ok(CondElem::Regex(right)) => right,
-            Ok(_) => return Err(ExecError::Other("Invalid operand".to_string())),
+            Ok(_) => return Err(ExecError::Other(fl("invalid-operand"))),
             Err(e) => return Err(e),
         };
 
         let left = match pop_operand(stack, core, false) {
             Ok(CondElem::Operand(name)) => name,
-            Ok(_) => return Err(ExecError::Other("Invalid operand".to_string())),
+            Ok(_) => return Err(ExecError::Other(fl("invalid-operand"))),
             Err(e) => return Err(e),
         };
 
         let right_eval = match right.eval_for_regex(core) {
             Some(r) => r,
-            None => return Err(ExecError::Other("Invalid operand".to_string())),
+            None => return Err(ExecError::Other(fl("invalid-operand"))),
         };
 
         let re = match Regex::new(&right_eval) {
@@ -271,7 +270,7 @@ impl ConditionalExpr {
 
     fn resolve_arithmetic_op(name: &str, core: &mut ShellCore) -> Result<ArithElem, ArithError> {
         let mut f = Feeder::new(name);
-        let mut parsed = match ArithmeticExpr::parse(&mut f, core, false, "") {
+        let parsed = match ArithmeticExpr::parse(&mut f, core, false, "") {
             Ok(Some(p)) => p,
             _ => return Err(ArithError::OperandExpected(name.to_string())),
         };
@@ -304,13 +303,13 @@ impl ConditionalExpr {
     ) -> Result<(), ExecError> {
         let right = match pop_operand(stack, core, true) {
             Ok(CondElem::Operand(name)) => name,
-            Ok(_) => return Err(ExecError::Other("Invalid operand".to_string())),
+            Ok(_) => return Err(ExecError::Other(fl("invalid-operand"))),
             Err(e) => return Err(e),
         };
 
         let left = match pop_operand(stack, core, false) {
             Ok(CondElem::Operand(name)) => name,
-            Ok(_) => return Err(ExecError::Other("Invalid operand".to_string())),
+            Ok(_) => return Err(ExecError::Other(fl("invalid-operand"))),
             Err(e) => return Err(e),
         };
 
@@ -332,17 +331,13 @@ impl ConditionalExpr {
             let lnum = match Self::resolve_arithmetic_op(&left, core)? {
                 ArithElem::Integer(n) => n,
                 _ => {
-                    return Err(ExecError::Other(
-                        "non integer number is not supported".to_string(),
-                    ))
+                    return Err(ExecError::Other(fl("non-integer-unsupported")))
                 }
             };
             let rnum = match Self::resolve_arithmetic_op(&right, core)? {
                 ArithElem::Integer(n) => n,
                 _ => {
-                    return Err(ExecError::Other(
-                        "non integer number is not supported".to_string(),
-                    ))
+                    return Err(ExecError::Other(fl("non-integer-unsupported")))
                 }
             };
 
@@ -378,7 +373,7 @@ impl ConditionalExpr {
             "-b" | "-c" | "-g" | "-k" | "-p" | "-s" | "-u" | "-G" | "-N" | "-O" | "-S" => {
                 file_check::metadata_check(s, op)
             }
-            _ => return Err(ExecError::Other("unsupported option".to_string())),
+            _ => return Err(ExecError::Other(fl("unsupported-option"))),
         };
 
         stack.push(CondElem::Ans(result));
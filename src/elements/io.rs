@@ -4,20 +4,20 @@
 pub mod pipe;
 pub mod redirect;
 
-use crate::elements::io::redirect::Redirect;
+use crate::elements::io::redirect::{Redirect, RedirectFds};
 use crate::elements::Pipe;
 use crate::error::exec::ExecError;
 use crate::ShellCore;
 
 pub fn connect(
     pipe: &mut Pipe,
-    rs: &mut [Redirect],
+    rs: &[Redirect],
     core: &mut ShellCore,
 ) -> Result<(), ExecError> {
     pipe.connect(core)?;
 
-    for r in rs.iter_mut() {
-        r.connect(false, core)?;
+    for r in rs {
+        r.connect(&mut RedirectFds::new(), false, core)?;
     }
     Ok(())
 }
@@ -7,8 +7,10 @@ use crate::elements::word::Word;
 use crate::elements::word::WordMode;
 use crate::error::exec::ExecError;
 use crate::error::parse::ParseError;
-use crate::utils::{exit, file_check};
+use crate::i18n::fl_args;
+use crate::utils::{c_string, exit, file, file_check};
 use crate::{error, Feeder, ShellCore};
+use nix::errno::Errno;
 use nix::unistd;
 use nix::unistd::ForkResult;
 use std::fs::{File, OpenOptions};
@@ -24,20 +26,70 @@ pub struct Redirect {
     pub symbol: String,
     pub right: Word,
     pub left: String,
+    left_var: String,
+    here_data: Word,
+    here_quoted: bool,
+    pub called_as_heredoc: bool,
+}
+
+/* the fds of one execution; the parsed redirect is shared */
+#[derive(Debug)]
+pub struct RedirectFds {
     left_fd: RawFd,
     left_backup: RawFd,
     extra_left_backup: RawFd, // &>, &>>用
-    here_data: Word,
-    pub called_as_heredoc: bool,
+}
+
+impl RedirectFds {
+    pub fn new() -> Self {
+        Self {
+            left_fd: -1,
+            left_backup: -1,
+            extra_left_backup: -1,
+        }
+    }
+
+    pub fn restore(&mut self, core: &mut ShellCore) -> Result<(), ExecError> {
+        if self.left_backup >= 0 && self.left_fd >= 0 {
+            if self.left_backup == self.left_fd {
+                core.fds.close(self.left_fd);
+            } else {
+                core.fds.replace(self.left_backup, self.left_fd)?;
+            }
+        }
+        if self.extra_left_backup >= 0 {
+            core.fds.replace(self.extra_left_backup, 2)?;
+        }
+        Ok(())
+    }
+
+    /* exec without a command leaves the redirections */
+    pub fn persist(&mut self, core: &mut ShellCore) {
+        if self.left_backup >= 0 && self.left_backup != self.left_fd {
+            core.fds.close(self.left_backup);
+        }
+        if self.extra_left_backup >= 0 {
+            core.fds.close(self.extra_left_backup);
+        }
+        self.left_backup = -1;
+        self.extra_left_backup = -1;
+    }
 }
 
 impl Redirect {
-    pub fn connect(&mut self, restore: bool, core: &mut ShellCore) -> Result<(), ExecError> {
+    pub fn connect(&self, fds: &mut RedirectFds, restore: bool,
+                   core: &mut ShellCore) -> Result<(), ExecError> {
+        /* the fd of {varname}>file is left open after the command */
+        let (left, restore) = match self.left_var.is_empty() {
+            true => (self.left.parse::<RawFd>().ok(), restore),
+            false => (Some(self.set_left_var(core)?), false),
+        };
+
         if self.symbol == "<<" || self.symbol == "<<-" {
-            return self.redirect_heredocument(core, restore);
+            return self.redirect_heredocument(fds, core, restore);
         }
         if self.symbol == "<<<" {
-            return self.redirect_herestring(core, restore);
+            return self.redirect_herestring(fds, core, restore);
         }
 
         let args = self.right.eval(core)?;
@@ -48,163 +100,149 @@ impl Redirect {
         if core.db.flags.contains('r') {
             match self.symbol.as_str() {
                 ">" | ">|" | "<>" | ">&" | "&>" | ">>" => {
-                    let msg = format!("{}: restricted: cannot redirect output", &args[0]);
+                    let msg = fl_args("restricted-redirect", &[("name", &args[0])]);
                     return Err(ExecError::Other(msg));
                 }
                 _ => {}
             }
         }
 
-        self.right.text = args[0].clone();
+        let path = &args[0];
 
         if core.options.query("noclobber")
             && (self.symbol.as_str() == ">" || self.symbol.as_str() == ">>")
-            && file_check::exists(&self.right.text)
+            && file_check::exists(path)
         {
-            return Err(ExecError::CannotOverwriteExistingFile(
-                self.right.text.clone(),
-            ));
+            return Err(ExecError::CannotOverwriteExistingFile(path.clone()));
         }
 
         match self.symbol.as_str() {
-            "<" => self.redirect_simple_input(restore, core),  // <
-            ">" => self.redirect_simple_output(restore, core), // >
-            ">&" => self.redirect_output_fd(restore, core),    // >&2
-            "<&" => self.redirect_input_fd(restore, core),     // <&2
-            ">>" => self.redirect_append(restore, core),
-            "&>" => self.redirect_both_output(restore, core),
+            "<" => {
+                fds.left_fd = left.unwrap_or(0);
+                Self::connect_to_file(File::open(file::name_to_path(path)), path, fds, restore, core)
+            }
+            ">" => {
+                fds.left_fd = left.unwrap_or(1);
+                Self::connect_to_file(File::create(file::name_to_path(path)), path, fds, restore, core)
+            }
+            ">&" => Self::redirect_fd(path, left.unwrap_or(1), fds, restore, core), // >&2
+            "<&" => Self::redirect_fd(path, left.unwrap_or(0), fds, restore, core), // <&2
+            ">>" => Self::redirect_append(path, left.unwrap_or(1), fds, restore, core),
+            "&>" => Self::redirect_both_output(path, fds, restore, core),
             _ => exit::internal(" (Unknown redirect symbol)"),
         }
     }
 
-    fn set_left_fd(&mut self, default_fd: RawFd) {
-        self.left_fd = match self.left.len() {
-            0 => default_fd,
-            _ => self.left.parse().unwrap(),
+    fn set_left_var(&self, core: &mut ShellCore) -> Result<RawFd, ExecError> {
+        if self.right.text == "-" && (self.symbol == ">&" || self.symbol == "<&") {
+            return match core.db.get_param(&self.left_var)?.parse::<RawFd>() {
+                Ok(fd) => Ok(fd),
+                Err(_) => Err(ExecError::AmbiguousRedirect(self.left_var.clone())),
+            };
         }
+
+        let fd = core.fds.unused_fd(10).ok_or(Errno::EMFILE)?;
+        core.db.set_param(&self.left_var, &fd.to_string(), None)?;
+        Ok(fd)
     }
 
     fn connect_to_file(
-        &mut self,
         file_open_result: Result<File, Error>,
+        path: &str,
+        fds: &mut RedirectFds,
         restore: bool,
         core: &mut ShellCore,
     ) -> Result<(), ExecError> {
         if restore {
-            self.left_backup = core.fds.backup(self.left_fd);
+            fds.left_backup = core.fds.backup(fds.left_fd);
         }
 
-        if self.left_fd < 0 {
-            return Err(ExecError::BadFd(self.left_fd));
+        if fds.left_fd < 0 {
+            return Err(ExecError::BadFd(fds.left_fd));
         }
 
         match file_open_result {
             Ok(file) => {
                 let fd = file.into_raw_fd();
-                if let Err(e) = core.fds.replace(fd, self.left_fd) {
+                /* the fd was not open before the file was opened */
+                if restore && fd == fds.left_fd {
+                    core.fds.close(fds.left_backup);
+                    fds.left_backup = fd;
+                }
+                if let Err(e) = core.fds.replace(fd, fds.left_fd) {
                     core.fds.close(fd);
-                    self.left_fd = -1;
+                    fds.left_fd = -1;
                     return Err(e);
                 }
                 Ok(())
             }
-            _ => {
-                let msg = format!("{}: {}", &self.right.text, Error::last_os_error().kind());
-                Err(ExecError::Other(msg))
-            }
+            Err(e) => Err(ExecError::from_io(path, &e)),
         }
     }
 
-    fn redirect_simple_input(&mut self, restore: bool,
-                             core: &mut ShellCore) -> Result<(), ExecError> {
-        self.set_left_fd(0);
-        self.connect_to_file(File::open(&self.right.text), restore, core)
-    }
-
-    fn redirect_simple_output(&mut self, restore: bool, core: &mut ShellCore) -> Result<(), ExecError> {
-        self.set_left_fd(1);
-        self.connect_to_file(File::create(&self.right.text), restore, core)
-    }
-
-    fn redirect_output_fd(&mut self, restore: bool, core: &mut ShellCore) -> Result<(), ExecError> {
-        if self.right.text == "-" {
-            self.set_left_fd(1);
-            core.fds.close(self.left_fd);
+    fn redirect_fd(path: &str, left_fd: RawFd, fds: &mut RedirectFds,
+                   restore: bool, core: &mut ShellCore) -> Result<(), ExecError> {
+        if path == "-" {
+            core.fds.close(left_fd);
             return Ok(());
         }
 
-        let right_fd = match self.right.text.parse::<RawFd>() {
+        let right_fd = match path.parse::<RawFd>() {
             Ok(n) => n,
-            _ => return Err(ExecError::AmbiguousRedirect(self.right.text.clone())),
+            _ => return Err(ExecError::AmbiguousRedirect(path.to_string())),
         };
-        self.set_left_fd(1);
-
-        if restore {
-            self.left_backup = core.fds.backup(self.left_fd);
-        }
-
-        core.fds.share(right_fd, self.left_fd)
-    }
-
-    fn redirect_input_fd(&mut self, restore: bool,
-                         core: &mut ShellCore) -> Result<(), ExecError> {
-        if self.right.text == "-" {
-            self.set_left_fd(0);
-            core.fds.close(self.left_fd);
-            return Ok(());
+        if !core.fds.is_open(right_fd) {
+            return Err(ExecError::BadFd(right_fd));
         }
-
-        let right_fd = match self.right.text.parse::<RawFd>() {
-            Ok(n) => n,
-            _ => return Err(ExecError::AmbiguousRedirect(self.right.text.clone())),
-        };
-        self.set_left_fd(0);
+        fds.left_fd = left_fd;
 
         if restore {
-            self.left_backup = core.fds.backup(self.left_fd);
+            fds.left_backup = core.fds.backup(fds.left_fd);
         }
 
-        core.fds.share(right_fd, self.left_fd)
+        core.fds.share(right_fd, fds.left_fd)
     }
 
-    fn redirect_append(&mut self, restore: bool, core: &mut ShellCore) -> Result<(), ExecError> {
-        self.set_left_fd(1);
-        self.connect_to_file(
+    fn redirect_append(path: &str, left_fd: RawFd, fds: &mut RedirectFds,
+                       restore: bool, core: &mut ShellCore) -> Result<(), ExecError> {
+        fds.left_fd = left_fd;
+        Self::connect_to_file(
             OpenOptions::new()
                 .create(true)
                 .append(true)
-                .open(&self.right.text),
+                .open(file::name_to_path(path)),
+            path,
+            fds,
             restore,
             core,
         )
     }
 
-    fn redirect_both_output(&mut self, restore: bool, core: &mut ShellCore) -> Result<(), ExecError> {
-        self.left_fd = 1;
-        self.connect_to_file(File::create(&self.right.text), restore, core)?;
+    fn redirect_both_output(path: &str, fds: &mut RedirectFds,
+                            restore: bool, core: &mut ShellCore) -> Result<(), ExecError> {
+        fds.left_fd = 1;
+        Self::connect_to_file(File::create(file::name_to_path(path)), path, fds, restore, core)?;
 
         if restore {
-            self.extra_left_backup = core.fds.backup(2);
+            fds.extra_left_backup = core.fds.backup(2);
         }
         core.fds.share(1, 2)
     }
 
     fn redirect_heredocument(
-        &mut self,
+        &self,
+        fds: &mut RedirectFds,
         core: &mut ShellCore,
         restore: bool,
     ) -> Result<(), ExecError> {
-        self.left_fd = 0;
+        fds.left_fd = 0;
         let (recv, send) = core.fds.pipe();
 
         if restore {
-            self.left_backup = core.fds.backup(0);
+            fds.left_backup = core.fds.backup(0);
         }
 
-        let right = self.right.make_unquoted_word().unwrap_or("".to_string());
-        let quoted = right != self.right.text;
-
-        let text = match quoted {
+        let text = match self.here_quoted {
             false => self.here_data.eval_as_alter(core)?, // TODO: make it precise
             true => self.here_data.text.clone(),
         };
@@ -213,7 +251,7 @@ impl Redirect {
             ForkResult::Child => {
                 core.fds.close(recv);
                 let mut f = unsafe { File::from_raw_fd(send) };
-                let _ = write!(&mut f, "{}", &text);
+                let _ = f.write_all(&c_string::to_bytes(&text));
                 f.flush().unwrap();
                 core.fds.close(send);
                 process::exit(0);
@@ -227,15 +265,16 @@ impl Redirect {
     }
 
     fn redirect_herestring(
-        &mut self,
+        &self,
+        fds: &mut RedirectFds,
         core: &mut ShellCore,
         restore: bool,
     ) -> Result<(), ExecError> {
-        self.left_fd = 0;
+        fds.left_fd = 0;
         let (recv, send) = core.fds.pipe();
 
         if restore {
-            self.left_backup = core.fds.backup(0);
+            fds.left_backup = core.fds.backup(0);
         }
 
         let text = self.right.eval_as_herestring(core)?;
@@ -244,7 +283,7 @@ impl Redirect {
             ForkResult::Child => {
                 core.fds.close(recv);
                 let mut f = unsafe { File::from_raw_fd(send) };
-                let _ = writeln!(&mut f, "{}", &text);
+                let _ = f.write_all(&c_string::to_bytes(&(text + "\n")));
                 f.flush().unwrap();
                 core.fds.close(send);
                 process::exit(0);
@@ -257,31 +296,13 @@ impl Redirect {
         Ok(())
     }
 
-    pub fn restore(&mut self, core: &mut ShellCore) -> Result<(), ExecError> {
-        if self.left_backup >= 0 && self.left_fd >= 0 {
-            if self.left_backup == self.left_fd {
-                core.fds.close(self.left_fd);
-            } else {
-                core.fds.replace(self.left_backup, self.left_fd)?;
-            }
-        }
-        if self.extra_left_backup >= 0 {
-            core.fds.replace(self.extra_left_backup, 2)?;
-        }
-        Ok(())
-    }
-
     pub fn new() -> Redirect {
-        Redirect {
-            left_fd: -1,
-            left_backup: -1,
-            extra_left_backup: -1,
-            ..Default::default()
-        }
+        Redirect::default()
     }
 
     fn show_heredoc_warning(&self, lineno: usize, feeder_lineno: usize, core: &mut ShellCore) {
-        let msg = format!("warning: here-document at line {} delimited by end-of-file (wanted `{}')", lineno, &self.right.text.replace("\\", ""));
+        let msg = fl_args("heredoc-delimited-by-eof",
+                          &[("lineno", &lineno.to_string()), ("word", &self.right.text.replace("\\", ""))]);
         let _ = core.db.set_param("LINENO", &feeder_lineno.to_string(), None);
         error::print(&msg, core);
     }
@@ -293,6 +314,8 @@ impl Redirect {
         lineno: usize,
     ) -> Result<(), ParseError> {
         let remove_tab = self.symbol == "<<-";
+        let right = self.right.make_unquoted_word().unwrap_or("".to_string());
+        self.here_quoted = right != self.right.text;
         let end = match self.right.eval_as_value(core) {
             Ok(s) => s,
             Err(_) => return Err(ParseError::UnexpectedSymbol(self.right.text.clone())),
@@ -382,6 +405,14 @@ impl Redirect {
     }
 
     fn eat_left(feeder: &mut Feeder, ans: &mut Self, core: &mut ShellCore) -> bool {
+        let len = feeder.scanner_redirect_var();
+        if len > 0 {
+            let var = feeder.consume(len);
+            ans.text += &var;
+            ans.left_var = var[1..len - 1].to_string();
+            return true;
+        }
+
         let len = feeder.scanner_uint(core);
         if len == 0 {
             return true; //左側なし（文法上OK）
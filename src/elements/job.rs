@@ -21,7 +21,7 @@ pub struct Job {
 }
 
 impl Job {
-    pub fn exec(&mut self, core: &mut ShellCore, bg: bool) -> Result<(), ExecError> {
+    pub fn exec(&self, core: &mut ShellCore, bg: bool) -> Result<(), ExecError> {
         let pgid = match core.is_subshell {
             true => unistd::getpgrp(),
             false => Pid::from_raw(0),
@@ -34,13 +34,13 @@ impl Job {
         Ok(())
     }
 
-    fn exec_fg(&mut self, core: &mut ShellCore, pgid: Pid) -> Result<(), ExecError> {
+    fn exec_fg(&self, core: &mut ShellCore, pgid: Pid) -> Result<(), ExecError> {
         let mut do_next = true;
         let susp_e_option = core.suspend_e_option;
 
         signal::check_trap(core);
 
-        for (pipeline, end) in self.pipelines.iter_mut().zip(self.pipeline_ends.iter()) {
+        for (pipeline, end) in self.pipelines.iter().zip(self.pipeline_ends.iter()) {
             if core.return_flag {
                 break;
             }
@@ -52,6 +52,7 @@ impl Job {
             core.suspend_e_option = susp_e_option || end == "&&" || end == "||";
             if do_next {
                 core.jobtable_check_status()?;
+                core.err_trap_fired = false;
                 let (pids, exclamation, time, err) = pipeline.exec(core, pgid);
                 let waitstatuses = proc_ctrl::wait_pipeline(core, pids.clone(), exclamation, time);
 
@@ -90,14 +91,18 @@ impl Job {
         }
     }
 
-    fn exec_bg(&mut self, core: &mut ShellCore, pgid: Pid) {
+    /* a single command with & is forked even if it is a builtin */
+    pub fn set_background(&mut self) {
+        if self.pipelines.len() == 1 && self.pipelines[0].commands.len() == 1 {
+            self.pipelines[0].commands[0].set_force_fork();
+        }
+    }
+
+    fn exec_bg(&self, core: &mut ShellCore, pgid: Pid) {
         let backup = core.tty_fd.clone();//core.tty_fd.as_ref().map(|fd| fd.try_clone().unwrap());
         core.tty_fd = None;
 
         let pids = if self.pipelines.len() == 1 {
-            if self.pipelines[0].commands.len() == 1 {
-                self.pipelines[0].commands[0].set_force_fork();
-            }
             self.pipelines[0].exec(core, pgid).0
         } else {
             match self.exec_fork_bg(core, pgid) {
@@ -133,7 +138,7 @@ impl Job {
         core.tty_fd = backup;
     }
 
-    fn exec_fork_bg(&mut self, core: &mut ShellCore, pgid: Pid) -> Result<Option<Pid>, ExecError> {
+    fn exec_fork_bg(&self, core: &mut ShellCore, pgid: Pid) -> Result<Option<Pid>, ExecError> {
         match unsafe { unistd::fork()? } {
             ForkResult::Child => {
                 core.initialize_as_subshell(Pid::from_raw(0), pgid);
@@ -150,7 +155,7 @@ impl Job {
     }
 
     pub fn pretty_print(
-        &mut self,
+        &self,
         indent_num: usize,
         semicolon: &mut bool,
         printed: &mut bool,
@@ -8,6 +8,7 @@ use crate::error::exec::ExecError;
 use crate::error::parse::ParseError;
 use crate::{Feeder, ShellCore};
 use nix::sys::resource;
+use nix::sys::time::TimeVal;
 use nix::time;
 use nix::time::ClockId;
 use nix::unistd::Pid;
@@ -19,11 +20,12 @@ pub struct Pipeline {
     pub text: String,
     exclamation: bool,
     pub time: bool,
+    time_posix: bool,
 }
 
 impl Pipeline {
     pub fn exec(
-        &mut self,
+        &self,
         core: &mut ShellCore,
         pgid: Pid,
     ) -> (Vec<Option<Pid>>, bool, bool, Option<ExecError>) {
@@ -39,10 +41,12 @@ impl Pipeline {
 
         self.set_time(core);
 
-        for (i, p) in self.pipes.iter_mut().enumerate() {
+        /* the fds of the pipes belong to this execution */
+        for (i, parsed) in self.pipes.iter().enumerate() {
+            let mut p = Pipe::new(parsed.text.clone());
             p.set(prev, pgid, core);
 
-            match self.commands[i].exec(core, p) {
+            match self.commands[i].exec(core, &mut p) {
                 Ok(pid) => pids.push(pid),
                 Err(e) => return (pids, self.exclamation, self.time, Some(e)),
             }
@@ -95,11 +99,21 @@ impl Pipeline {
     }*/
 
 
-    fn set_time(&mut self, core: &mut ShellCore) {
+    fn set_time(&self, core: &mut ShellCore) {
         if !self.time {
             return;
         }
 
+        core.measured_time.posix = self.time_posix;
+
+        /* time without a pipeline reports the totals of the shell */
+        if self.commands.is_empty() {
+            core.measured_time.user = TimeVal::new(0, 0);
+            core.measured_time.sys = TimeVal::new(0, 0);
+            core.measured_time.real = core.measured_time.shell_start;
+            return;
+        }
+
         let self_usage = resource::getrusage(resource::UsageWho::RUSAGE_SELF).unwrap();
         let children_usage = resource::getrusage(resource::UsageWho::RUSAGE_CHILDREN).unwrap();
 
@@ -147,15 +161,29 @@ impl Pipeline {
         true
     }
 
+    fn starts_with_word(feeder: &Feeder, word: &str) -> bool {
+        feeder.len() == word.len() && feeder.starts_with(word)
+            || [" ", "\t", "\n", ";", "&", "|"]
+                .iter()
+                .any(|s| feeder.starts_with(&(word.to_string() + s)))
+    }
+
     fn eat_time(feeder: &mut Feeder, ans: &mut Self, core: &mut ShellCore) -> bool {
-        match feeder.starts_with("time ") || feeder.starts_with("time\t") {
-            true => ans.text += &feeder.consume(4),
-            false => return false,
+        if !Self::starts_with_word(feeder, "time") {
+            return false;
         }
 
+        ans.text += &feeder.consume(4);
         ans.time = true;
         let blank_len = feeder.scanner_blank(core);
         ans.text += &feeder.consume(blank_len);
+
+        if Self::starts_with_word(feeder, "-p") {
+            ans.text += &feeder.consume(2);
+            ans.time_posix = true;
+            let blank_len = feeder.scanner_blank(core);
+            ans.text += &feeder.consume(blank_len);
+        }
         true
     }
 
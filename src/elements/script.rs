@@ -20,8 +20,12 @@ pub struct Script {
 }
 
 impl Script {
-    pub fn exec(&mut self, core: &mut ShellCore) -> Result<(), ExecError> {
-        for (job, end) in self.jobs.iter_mut().zip(self.job_ends.iter()) {
+    pub fn exec(&self, core: &mut ShellCore) -> Result<(), ExecError> {
+        for (job, end) in self.jobs.iter().zip(self.job_ends.iter()) {
+            /* the rest is skipped after return, break or continue */
+            if core.return_flag || core.break_counter > 0 || core.continue_counter > 0 {
+                break;
+            }
             job.exec(core, end == "&")?;
         }
 
@@ -32,19 +36,19 @@ impl Script {
         self.text.clone()
     }
 
-    pub fn pretty_print(&mut self, indent_num: usize) {
+    pub fn pretty_print(&self, indent_num: usize) {
         let mut semicolon = false;
         let mut printed = false;
         let mut end_pos = self.jobs.len() - 1;
 
-        for job in self.jobs.iter_mut().rev() {
+        for job in self.jobs.iter().rev() {
             if job.pipelines.is_empty() {
                 end_pos -= 1;
                 break;
             }
         }
 
-        for (i, job) in self.jobs.iter_mut().enumerate() {
+        for (i, job) in self.jobs.iter().enumerate() {
             job.pretty_print(
                 indent_num,
                 &mut semicolon,
@@ -88,6 +92,11 @@ impl Script {
         }
         let len = feeder.scanner_job_end();
         let end = &feeder.consume(len);
+        if end == "&"
+            && let Some(job) = ans.jobs.last_mut()
+        {
+            job.set_background();
+        }
         ans.job_ends.push(end.clone());
         ans.text += end;
         len != 0
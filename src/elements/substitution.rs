@@ -6,11 +6,13 @@ pub mod subscript;
 pub mod value;
 pub mod variable;
 
-use self::value::Value;
+use self::value::{EvaluatedValue, Value};
 use self::variable::Variable;
 use crate::error::exec::ExecError;
 use crate::error::parse::ParseError;
+use crate::i18n::{fl, fl_args};
 use crate::{Feeder, ShellCore};
+use std::borrow::Cow;
 
 #[derive(Debug, Clone, Default)]
 pub struct Substitution {
@@ -25,47 +27,49 @@ pub struct Substitution {
 
 impl Substitution {
     pub fn eval(
-        &mut self,
+        &self,
         core: &mut ShellCore,
         scope: Option<usize>,
         declare: bool,
     ) -> Result<(), ExecError> {
         core.db.set_param("LINENO", &self.lineno.to_string(), None)?;
-        if self.right_hand.is_none() {
-            return Ok(());
-        }
+        let r = match &self.right_hand {
+            Some(r) => r,
+            None => return Ok(()),
+        };
 
-        if core.db.exist_nameref(&self.left_hand.name) && ! self.reset_nameref {
+        /* a nameref is resolved for this evaluation only */
+        let mut left = Cow::Borrowed(&self.left_hand);
+        if core.db.exist_nameref(&left.name) && ! self.reset_nameref {
             let mut circular_check_vec = vec![];
-            let org_name = self.left_hand.name.clone();
+            let org_name = left.name.clone();
             loop {
-                self.left_hand.check_nameref(core)?;
+                left.to_mut().check_nameref(core)?;
 
-                if circular_check_vec.is_empty() && org_name == self.left_hand.name {
+                if circular_check_vec.is_empty() && org_name == left.name {
                     break;
                 }
 
-                if circular_check_vec.contains(&self.left_hand.name) {
+                if circular_check_vec.contains(&left.name) {
                     return Err(ExecError::CircularNameRef(org_name));
                 }
-                if ! core.db.exist_nameref(&self.left_hand.name) {
+                if ! core.db.exist_nameref(&left.name) {
                     break;
                 }
-                circular_check_vec.push(self.left_hand.name.clone());
+                circular_check_vec.push(left.name.clone());
             }
         }
 
-        let r = self.right_hand.as_mut().unwrap();
-        r.eval(core, &self.left_hand.name, self.append)?;
+        let value = r.eval(core, &left.name, self.append)?;
 
         if r.is_obj() {
             return Ok(());
         }
 
-        if declare && r.evaluated_array.is_some() {
-            self.left_hand.index = None;
+        if declare && value.array.is_some() {
+            left.to_mut().index = None;
         }
-        self.set_to_shell(core, scope)
+        self.set_to_shell(&left, &value, core, scope)
     }
 
     pub fn reparse(&mut self, core: &mut ShellCore) -> Result<(), ExecError> {
@@ -105,14 +109,19 @@ impl Substitution {
         }
     }
 
-    fn set_whole_array(&mut self, core: &mut ShellCore, scope: usize) -> Result<(), ExecError> {
-        let r = self.right_hand.as_mut().unwrap();
-        if r.evaluated_array.is_none() {
-            return Err(ExecError::Other("no array and no index".to_string()));
-        }
+    fn set_whole_array(
+        &self,
+        left: &Variable,
+        value: &EvaluatedValue,
+        core: &mut ShellCore,
+        scope: usize,
+    ) -> Result<(), ExecError> {
+        let a = match &value.array {
+            Some(a) => a,
+            None => return Err(ExecError::Other(fl("no-array-and-no-index"))),
+        };
 
-        let a = r.evaluated_array.as_ref().unwrap();
-        let name = &self.left_hand.name;
+        let name = &left.name;
         let old_flags = core.db.get_flags(&name).to_string();
 
         if a.is_empty() && !self.append {
@@ -143,76 +152,81 @@ impl Substitution {
     }
 
     fn set_array_elem(
-        &mut self,
+        &self,
+        left: &Variable,
+        value: &EvaluatedValue,
         core: &mut ShellCore,
         scope: usize,
         index: &str,
     ) -> Result<(), ExecError> {
         if index.is_empty() {
-            return Err(ExecError::ArrayIndexInvalid(self.left_hand.text.clone()));
+            return Err(ExecError::ArrayIndexInvalid(left.text.clone()));
         }
 
-        let r = self.right_hand.as_mut().unwrap();
-        if let Some(v) = &r.evaluated_string {
+        if let Some(v) = &value.string {
             if self.append {
                 return core
                     .db
-                    .append_param2(&self.left_hand.name, index, v, Some(scope));
+                    .append_param2(&left.name, index, v, Some(scope));
             } else {
                 return core
                     .db
-                    .set_param2(&self.left_hand.name, index, v, Some(scope));
+                    .set_param2(&left.name, index, v, Some(scope));
             }
         }
 
-        let msg = format!(
-            "{}: cannot assign list to array member",
-            &self.left_hand.text
-        );
+        let msg = fl_args("cannot-assign-list", &[("name", &left.text)]);
         Err(ExecError::Other(msg))
     }
 
-    fn init_array(&mut self, core: &mut ShellCore, scope: usize) -> Result<(), ExecError> {
-        let rhs_is_array = match self.right_hand.as_mut() {
-            Some(r) => r.evaluated_array.is_some(),
-            None => false,
-        };
+    fn init_array(
+        &self,
+        left: &Variable,
+        value: &EvaluatedValue,
+        core: &mut ShellCore,
+        scope: usize,
+    ) -> Result<(), ExecError> {
+        let rhs_is_array = value.array.is_some();
 
-        match self.left_hand.get_index(core, rhs_is_array, self.append)? {
-            Some(index) => self.set_array_elem(core, scope, &index),
-            None => self.set_whole_array(core, scope),
+        match left.get_index(core, rhs_is_array, self.append)? {
+            Some(index) => self.set_array_elem(left, value, core, scope, &index),
+            None => self.set_whole_array(left, value, core, scope),
         }
     }
 
-    fn set_single(&mut self, core: &mut ShellCore, scope: usize) -> Result<(), ExecError> {
-        let data = match self.right_hand.as_mut() {
-            Some(r) => r.evaluated_string.clone().unwrap(),
-            None => String::new(),
-        };
+    fn set_single(
+        &self,
+        left: &Variable,
+        value: &EvaluatedValue,
+        core: &mut ShellCore,
+        scope: usize,
+    ) -> Result<(), ExecError> {
+        let data = value.string.clone().unwrap();
 
         if self.append {
             core.db
-                .append_param(&self.left_hand.name, &data, Some(scope))
+                .append_param(&left.name, &data, Some(scope))
         } else if self.reset_nameref {
-            core.db.set_nameref(&self.left_hand.name, &data, Some(scope))
+            core.db.set_nameref(&left.name, &data, Some(scope))
         } else {
-            core.db.set_param(&self.left_hand.name, &data, Some(scope))
+            core.db.set_param(&left.name, &data, Some(scope))
         }
     }
 
     fn set_to_shell(
-        &mut self,
+        &self,
+        left: &Variable,
+        value: &EvaluatedValue,
         core: &mut ShellCore,
         scope: Option<usize>,
     ) -> Result<(), ExecError> {
-        let scope = core.db.get_target_scope(&self.left_hand.name, scope);
-        let r = self.right_hand.as_mut().unwrap();
+        let scope = core.db.get_target_scope(&left.name, scope);
 
-        if r.evaluated_string.is_some()
-        && self.left_hand.index.is_none() {
-            self.set_single(core, scope)
+        if value.string.is_some()
+        && left.index.is_none() {
+            self.set_single(left, value, core, scope)
         } else {
-            self.init_array(core, scope)
+            self.init_array(left, value, core, scope)
         }
     }
 
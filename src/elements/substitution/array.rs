@@ -17,7 +17,7 @@ pub struct Array {
 
 impl Array {
     pub fn eval(
-        &mut self,
+        &self,
         core: &mut ShellCore,
         as_int: bool,
         as_assoc: bool,
@@ -29,11 +29,11 @@ impl Array {
         let mut ans = vec![];
 
         if as_int {
-            for (s, append, w) in &mut self.words {
+            for (s, append, w) in &self.words {
                 ans.push((s.clone(), *append, w.eval_as_integer(core)?));
             }
         } else {
-            for (s, append, w) in &mut self.words {
+            for (s, append, w) in &self.words {
                 if as_assoc {
                     ans.push((s.clone(), *append, w.eval_as_value(core)?));
                 } else {
@@ -28,7 +28,7 @@ impl Subscript {
             return Ok(a.clone());
         }
 
-        if let SubscriptType::Arith(mut a) = self.data.clone() {
+        if let SubscriptType::Arith(a) = self.data.clone() {
             if a.text.is_empty() {
                 return Err(ExecError::ArrayIndexInvalid(a.text.clone()));
             }
@@ -7,6 +7,7 @@ use crate::elements::word::Word;
 use crate::elements::word::WordMode;
 use crate::error::exec::ExecError;
 use crate::error::parse::ParseError;
+use crate::i18n::fl_args;
 use crate::{Feeder, ShellCore};
 
 #[derive(Debug, Clone, Default)]
@@ -22,8 +23,13 @@ pub enum ParsedDataType {
 pub struct Value {
     pub text: String,
     pub value: ParsedDataType,
-    pub evaluated_string: Option<String>,
-    pub evaluated_array: Option<Vec<(String, bool, String)>>, //bool: true if append
+}
+
+/* the result of one evaluation; the parsed value is shared */
+#[derive(Debug, Default)]
+pub struct EvaluatedValue {
+    pub string: Option<String>,
+    pub array: Option<Vec<(String, bool, String)>>, //bool: true if append
 }
 
 impl From<Box::<dyn Data>> for Value {
@@ -31,27 +37,25 @@ impl From<Box::<dyn Data>> for Value {
         Self {
             text: (*d.get_fmt_string()).to_string(),
             value: ParsedDataType::Obj(d),
-            ..Default::default()
         }
     }
 }
 
 impl Value {
     pub fn eval(
-        &mut self,
+        &self,
         core: &mut ShellCore,
         name: &str,
         append: bool,
-    ) -> Result<(), ExecError> {
-        match self.value.clone() {
-            ParsedDataType::Single(v) => self.eval_as_value(&v, core, name),
-            ParsedDataType::Array(mut a) => self.eval_as_array(&mut a, core, name, append),
-            ParsedDataType::Obj(_) => {Ok(())},
-            ParsedDataType::None => {
-                self.evaluated_string = Some("".to_string());
-                Ok(())
-            }
+    ) -> Result<EvaluatedValue, ExecError> {
+        let mut ans = EvaluatedValue::default();
+        match &self.value {
+            ParsedDataType::Single(v) => Self::eval_as_value(&mut ans, v, core, name)?,
+            ParsedDataType::Array(a) => Self::eval_as_array(&mut ans, a, core, name, append)?,
+            ParsedDataType::Obj(_) => {},
+            ParsedDataType::None => ans.string = Some("".to_string()),
         }
+        Ok(ans)
     }
 
     pub fn is_obj(&self) -> bool {
@@ -62,12 +66,12 @@ impl Value {
     }
 
     fn eval_as_value(
-        &mut self,
+        ans: &mut EvaluatedValue,
         w: &Word,
         core: &mut ShellCore,
         name: &str,
     ) -> Result<(), ExecError> {
-        self.evaluated_string = match core.db.has_flag(name, 'i') {
+        ans.string = match core.db.has_flag(name, 'i') {
             true => Some(w.eval_as_integer(core)?),
             false => Some(w.eval_as_value(core)?),
         };
@@ -76,8 +80,8 @@ impl Value {
     }
 
     fn eval_as_array(
-        &mut self,
-        a: &mut Array,
+        ans: &mut EvaluatedValue,
+        a: &Array,
         core: &mut ShellCore,
         name: &str,
         append: bool,
@@ -105,9 +109,9 @@ impl Value {
                     assoc_no_index_mode = true;
                     vec_assoc.push((v, append));
                 } else if assoc {
-                    let msg = format!(
-                        "{}: {}: must use subscript when assigning associative array",
-                        &name, &a.words[pos].2.text
+                    let msg = fl_args(
+                        "must-use-subscript",
+                        &[("name", name), ("word", &a.words[pos].2.text)],
                     );
                     ExecError::Other(msg).print(core);
                 } else {
@@ -122,7 +126,7 @@ impl Value {
             let index = match s.unwrap().eval(core, name) {
                 Ok(i) => i,
                 Err(ExecError::ArithError(a, b)) => {
-                    self.evaluated_array = Some(vec![]);
+                    ans.array = Some(vec![]);
                     ExecError::ArithError(a, b).print(core);
                     return Ok(());
                 }
@@ -161,7 +165,7 @@ impl Value {
             }
         }
 
-        self.evaluated_array = Some(hash);
+        ans.array = Some(hash);
         Ok(())
     }
 
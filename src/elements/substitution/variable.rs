@@ -38,7 +38,7 @@ impl Variable {
     }
 
     pub fn get_index(
-        &mut self,
+        &self,
         core: &mut ShellCore,
         right_is_array: bool,
         append: bool,
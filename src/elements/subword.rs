@@ -13,6 +13,7 @@ pub mod escaped_char;
 mod ext_glob;
 mod file_input;
 pub mod filler;
+mod locale_quoted;
 pub mod parameter;
 mod paren;
 mod process_sub;
@@ -33,6 +34,7 @@ use self::escaped_char::EscapedChar;
 use self::ext_glob::ExtGlob;
 use self::file_input::FileInput;
 use self::filler::FillerSubword;
+use self::locale_quoted::LocaleQuoted;
 use self::parameter::Parameter;
 use self::paren::EvalLetParen;
 use self::process_sub::ProcessSubstitution;
@@ -141,32 +143,6 @@ pub trait Subword {
     fn set_pipe(&mut self, _: &mut ShellCore) {}
 }
 
-fn replace_history_expansion(feeder: &mut Feeder, core: &mut ShellCore) -> bool {
-    let len = feeder.scanner_history_expansion(core);
-    if len == 0 {
-        return false;
-    }
-
-    let history_len = core.history.len();
-    if history_len < 2 {
-        feeder.replace(len, "");
-        return true;
-    }
-
-    let mut his = String::new();
-    for h in &core.history[1..] {
-        let last = h.split(" ").last().unwrap();
-
-        if !last.starts_with("!$") {
-            his = last.to_string();
-            break;
-        }
-    }
-
-    feeder.replace(len, &his);
-    true
-}
-
 fn last_resort(
     feeder: &mut Feeder,
     core: &mut ShellCore,
@@ -240,10 +216,6 @@ pub fn parse(
     core: &mut ShellCore,
     mode: &Option<WordMode>,
 ) -> Result<Option<Box<dyn Subword>>, ParseError> {
-    if replace_history_expansion(feeder, core) {
-        return parse(feeder, core, mode);
-    }
-
     if let Some(a) = BracedParam::parse(feeder, core)? {
         Ok(Some(Box::new(a)))
     } else if let Some(a) = AnsiCQuoted::parse(feeder, core)? {
@@ -260,6 +232,8 @@ pub fn parse(
         Ok(Some(Box::new(a)))
     } else if let Some(a) = SingleQuoted::parse(feeder, core, mode) {
         Ok(Some(Box::new(a)))
+    } else if let Some(a) = LocaleQuoted::parse(feeder, core, mode)? {
+        Ok(Some(Box::new(a)))
     } else if let Some(a) = DoubleQuoted::parse(feeder, core, mode)? {
         Ok(Some(Box::new(a)))
     } else if let Some(a) = ExtGlob::parse(feeder, core)? {
@@ -16,7 +16,18 @@ impl OptionalOperation for Escape {
     fn get_text(&self) -> String {
         self.text.clone()
     }
-    fn exec(&mut self, _: &Variable, text: &str, _: &mut ShellCore) -> Result<String, ExecError> {
+    fn exec(
+        &mut self,
+        _: &Variable,
+        text: &str,
+        core: &mut ShellCore,
+    ) -> Result<String, ExecError> {
+        if self.symbol == "P" {
+            return Ok(core
+                .decode_prompt(text)
+                .replace("\\[", "")
+                .replace("\\]", ""));
+        }
         self.replace_single_data(text)
     }
 
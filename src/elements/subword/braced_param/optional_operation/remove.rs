@@ -6,6 +6,7 @@ use super::OptionalOperation;
 use crate::elements::word::{Word, WordMode};
 use crate::error::exec::ExecError;
 use crate::error::parse::ParseError;
+use crate::i18n::fl;
 use crate::utils::glob;
 use crate::{Feeder, ShellCore};
 
@@ -66,7 +67,7 @@ impl Remove {
             .as_mut()
             .unwrap()
             .eval_for_case_word(core)
-            .ok_or(ExecError::Other("evaluation error".to_string()))?;
+            .ok_or(ExecError::Other(fl("evaluation-error")))?;
         let extglob = core.shopts.query("extglob");
 
         if self.remove_symbol.starts_with("##") {
@@ -80,7 +81,7 @@ impl Remove {
         } else if self.remove_symbol.starts_with("%") {
             self.percent(&mut text, &pattern, extglob);
         } else {
-            return Err(ExecError::Other("unknown symbol".to_string()));
+            return Err(ExecError::Other(fl("unknown-symbol")));
         }
 
         Ok(text)
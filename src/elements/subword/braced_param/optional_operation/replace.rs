@@ -6,6 +6,7 @@ use super::super::Variable;
 use crate::elements::word::{Word, WordMode};
 use crate::error::exec::ExecError;
 use crate::error::parse::ParseError;
+use crate::i18n::fl;
 use crate::utils::glob;
 use crate::utils::glob::GlobElem;
 use crate::{Feeder, ShellCore};
@@ -81,7 +82,7 @@ impl Replace {
                 Some(s) => return Ok(s),
                 None => match w.subwords.len() {
                     0 => return Ok("".to_string()),
-                    _ => return Err(ExecError::Other("parse error".to_string())),
+                    _ => return Err(ExecError::Other(fl("parse-error"))),
                 },
             }
         }
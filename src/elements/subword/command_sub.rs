@@ -7,6 +7,7 @@ use crate::elements::subword::Subword;
 use crate::elements::Pipe;
 use crate::error::exec::ExecError;
 use crate::error::parse::ParseError;
+use crate::utils::c_string;
 use crate::{proc_ctrl, Feeder, ShellCore};
 use nix::unistd;
 use std::io::{BufRead, BufReader, Error};
@@ -33,7 +34,11 @@ impl Subword for CommandSubstitution {
         pipe.set(-1, unistd::getpgrp(), core);
         let pid = self.command.exec(core, &mut pipe)?;
         let result = self.read(pipe.recv, core);
+        /* ERR and -e are for the command using the substitution */
+        let susp_e_option = core.suspend_e_option;
+        core.suspend_e_option = true;
         proc_ctrl::wait_pipeline(core, vec![pid], false, false);
+        core.suspend_e_option = susp_e_option;
         result?;
         self.text = self.text.trim_end_matches("\n").to_string();
         Ok(())
@@ -41,9 +46,9 @@ impl Subword for CommandSubstitution {
 }
 
 impl CommandSubstitution {
-    fn set_line(&mut self, line: Result<String, Error>) -> bool {
+    fn set_line(&mut self, line: Result<Vec<u8>, Error>) -> bool {
         if let Ok(ln) = line {
-            self.text.push_str(&ln);
+            self.text.push_str(&c_string::from_bytes(&ln));
             self.text.push('\n');
             return true;
         }
@@ -66,7 +71,7 @@ impl CommandSubstitution {
         let f = core.fds.get_file(fd);
         let reader = BufReader::new(f);
         self.text.clear();
-        for (i, line) in reader.lines().enumerate() {
+        for (i, line) in reader.split(b'\n').enumerate() {
             self.interrupted(i, core)?;
             if !self.set_line(line) {
                 break;
@@ -218,7 +218,7 @@ impl DoubleQuoted {
         core: &mut ShellCore,
         mode: &Option<WordMode>,
     ) -> Result<Option<Self>, ParseError> {
-        if !feeder.starts_with("\"") && !feeder.starts_with("$\"") {
+        if !feeder.starts_with("\"") {
             return Ok(None);
         }
         if let Some(WordMode::Heredoc) = mode {
@@ -233,8 +233,7 @@ impl DoubleQuoted {
 
         feeder.nest.push(("\"".to_string(), vec!["\"".to_string()]));
 
-        let len = if feeder.starts_with("\"") { 1 } else { 2 };
-        ans.text = feeder.consume(len);
+        ans.text = feeder.consume(1);
 
         while Self::eat_element(feeder, &mut ans, core)? || Self::eat_char(feeder, &mut ans, core)?
         {
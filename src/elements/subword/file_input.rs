@@ -6,6 +6,7 @@ use crate::elements::io::redirect::Redirect;
 use crate::elements::subword::Subword;
 use crate::error::exec::ExecError;
 use crate::error::parse::ParseError;
+use crate::utils::{c_string, file};
 use crate::{Feeder, ShellCore};
 use std::fs::File;
 use std::io::{BufRead, BufReader};
@@ -32,19 +33,19 @@ impl Subword for FileInput {
             ));
         }
 
-        let file = match File::open(&args[0]) {
+        let file = match File::open(file::name_to_path(&args[0])) {
             Ok(f) => f,
-            Err(e) => return Err(ExecError::Other(e.to_string())),
+            Err(e) => return Err(ExecError::from_io(&args[0], &e)),
         };
         let reader = BufReader::new(file);
         self.text.clear();
-        for line in reader.lines() {
+        for line in reader.split(b'\n') {
             match line {
                 Ok(ln) => {
-                    self.text += &ln;
+                    self.text += &c_string::from_bytes(&ln);
                     self.text += " ";
                 }
-                Err(e) => return Err(ExecError::Other(e.to_string())),
+                Err(e) => return Err(ExecError::from_io(&args[0], &e)),
             }
         }
 
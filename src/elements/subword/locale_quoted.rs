@@ -0,0 +1,90 @@
+//SPDX-FileCopyrightText: 2024 Ryuichi Ueda ryuichiueda@gmail.com
+//SPDX-License-Identifier: BSD-3-Clause
+
+use super::double_quoted::DoubleQuoted;
+use super::Subword;
+use crate::elements::word::WordMode;
+use crate::error::exec::ExecError;
+use crate::error::parse::ParseError;
+use crate::i18n::gettext;
+use crate::{Feeder, ShellCore};
+
+#[derive(Debug, Clone, Default)]
+pub struct LocaleQuoted {
+    text: String,
+    msgid: String,
+    quoted: DoubleQuoted,
+}
+
+impl Subword for LocaleQuoted {
+    fn get_text(&self) -> &str {
+        self.text.as_ref()
+    }
+    fn boxed_clone(&self) -> Box<dyn Subword> {
+        Box::new(self.clone())
+    }
+
+    fn substitute(&mut self, core: &mut ShellCore) -> Result<(), ExecError> {
+        /* the translation is expanded in the same way as the original */
+        if let Some(t) = gettext::translate(&mut core.db, &self.msgid) {
+            let mut feeder = Feeder::new(&format!("\"{t}\""));
+            if let Ok(Some(q)) = DoubleQuoted::parse(&mut feeder, core, &None)
+                && feeder.is_empty()
+            {
+                self.quoted = q;
+            }
+        }
+
+        self.quoted.substitute(core)?;
+        self.text = self.quoted.get_text().to_string();
+        Ok(())
+    }
+
+    fn make_glob_string(&mut self) -> String {
+        self.quoted.make_glob_string()
+    }
+
+    fn make_unquoted_string(&mut self) -> Option<String> {
+        self.quoted.make_unquoted_string()
+    }
+
+    fn split(&self, ifs: &str, prev_char: Option<char>) -> Vec<(Box<dyn Subword>, bool)> {
+        self.quoted.split(ifs, prev_char)
+    }
+}
+
+impl LocaleQuoted {
+    pub fn parse(
+        feeder: &mut Feeder,
+        core: &mut ShellCore,
+        mode: &Option<WordMode>,
+    ) -> Result<Option<Self>, ParseError> {
+        if !feeder.starts_with("$\"") {
+            return Ok(None);
+        }
+        if let Some(WordMode::Heredoc) = mode {
+            return Ok(None);
+        }
+
+        let lineno = feeder.lineno;
+        let dollar = feeder.consume(1);
+        let Some(quoted) = DoubleQuoted::parse(feeder, core, mode)? else {
+            return Ok(None);
+        };
+        let text = quoted.get_text();
+        let ans = Self {
+            text: dollar + text,
+            msgid: text[1..].strip_suffix('"').unwrap_or_default().to_string(),
+            quoted,
+        };
+
+        if let Some(format) = core.dump_strings {
+            let source = match core.db.flags.contains('c') {
+                true => "-c",
+                false => &core.script_name,
+            };
+            gettext::dump_string(format, source, lineno, &ans.msgid);
+        }
+        Ok(Some(ans))
+    }
+}
@@ -44,6 +44,9 @@ impl SingleQuoted {
                 return None;
             }
         }
+        if let Some(WordMode::Heredoc) = mode {
+            return None;
+        }
         
         match feeder.scanner_single_quoted_subword(core) {
             0 => None,
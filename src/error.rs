@@ -6,6 +6,7 @@ pub mod exec;
 pub mod input;
 pub mod parse;
 
+use crate::i18n::fl_args;
 use crate::ShellCore;
 use nix::sys::signal::Signal;
 use nix::unistd::Pid;
@@ -16,23 +17,26 @@ pub fn print(s: &str, core: &mut ShellCore) {
         eprintln!("{}: {}", &name, &s);
     } else {
         let lineno = core.db.get_param("LINENO").unwrap_or("".to_string());
-        eprintln!("{}: line {}: {}", &name, &lineno, s);
+        let line = fl_args("at-line", &[("lineno", &lineno)]);
+        eprintln!("{}: {}: {}", &name, &line, s);
     }
 }
 
 pub fn internal(s: &str) -> String {
-    format!("SUSH INTERNAL ERROR: {s}")
+    fl_args("internal-error", &[("msg", s)])
 }
 
 pub fn exponent(s: &str) -> String {
-    format!("exponent less than 0 (error token is \"{s}\")")
+    fl_args("arith-exponent-less-than-zero", &[("token", s)])
 }
 
 /* error at wait */
 pub fn signaled(pid: Pid, signal: Signal, coredump: bool) -> i32 {
+    let (p, sig) = (format!("{pid:?}"), format!("{signal:?}"));
+    let args = [("pid", p.as_str()), ("signal", sig.as_str())];
     match coredump {
-        true => eprintln!("Pid: {pid:?}, Signal: {signal:?} (core dumped)"),
-        false => eprintln!("Pid: {pid:?}, Signal: {signal:?}"),
+        true => eprintln!("{}", fl_args("signaled-core-dumped", &args)),
+        false => eprintln!("{}", fl_args("signaled", &args)),
     }
     128 + signal as i32
 }
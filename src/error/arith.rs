@@ -1,6 +1,8 @@
 //SPDX-FileCopyrightText: 2024 Ryuichi Ueda ryuichiueda@gmail.com
 //SPDX-License-Identifier: BSD-3-Clause
 
+use crate::i18n::fl_args;
+
 #[derive(Debug, Clone)]
 pub enum ArithError {
     AssignmentToNonVariable(String),
@@ -28,28 +30,33 @@ impl From<&ArithError> for String {
     fn from(e: &ArithError) -> String {
         match e {
             ArithError::AssignmentToNonVariable(right) => {
-                error_msg("attempted assignment to non-variable", right)
+                fl_args("arith-assignment-to-non-variable", &[("token", right)])
+            }
+            ArithError::DivZero(token) => fl_args("arith-division-by-zero", &[("token", token)]),
+            ArithError::Exponent(s) => fl_args(
+                "arith-exponent-less-than-zero",
+                &[("token", &s.to_string())],
+            ),
+            ArithError::NoColon(token) => fl_args("arith-colon-expected", &[("token", token)]),
+            ArithError::ExpressionExpected(token) => {
+                fl_args("arith-expression-expected", &[("token", token)])
+            }
+            ArithError::InvalidBase(b) => fl_args("arith-invalid-base", &[("token", b)]),
+            ArithError::ValueTooGreatForBase(num) => {
+                fl_args("arith-value-too-great-for-base", &[("token", num)])
             }
-            ArithError::DivZero(token) => error_msg("division by 0", token),
-            ArithError::Exponent(s) => error_msg("exponent less than 0", &s.to_string()),
-            ArithError::NoColon(token) => {
-                error_msg("`:' expected for conditional expression", token)
+            ArithError::InvalidNumber(name) => fl_args("arith-invalid-number", &[("token", name)]),
+            ArithError::InvalidIntConst(tok) => {
+                fl_args("arith-invalid-integer-constant", &[("token", tok)])
+            }
+            ArithError::InvalidOperator(tok) => {
+                fl_args("arith-invalid-operator", &[("token", tok)])
             }
-            ArithError::ExpressionExpected(token) => error_msg("expression expected", token),
-            ArithError::InvalidBase(b) => error_msg("invalid arithmetic base", b),
-            ArithError::ValueTooGreatForBase(num) => error_msg("value too great for base", num),
-            ArithError::InvalidNumber(name) => error_msg("invalid number", name),
-            ArithError::InvalidIntConst(tok) => error_msg("invalid integer constant", tok),
-            ArithError::InvalidOperator(tok) => error_msg("invalid arithmetic operator", tok),
             ArithError::OperandExpected(token) => {
-                error_msg("syntax error: operand expected", token)
+                fl_args("arith-operand-expected", &[("token", token)])
             }
-            ArithError::Recursion(token) => error_msg("expression recursion level exceeded", token),
-            ArithError::SyntaxError(token) => error_msg("syntax error in expression", token),
+            ArithError::Recursion(token) => fl_args("arith-recursion", &[("token", token)]),
+            ArithError::SyntaxError(token) => fl_args("arith-syntax-error", &[("token", token)]),
         }
     }
 }
-
-fn error_msg(msg: &str, token: &str) -> String {
-    msg.to_string() + &format!(" (error token is \"{token}\")")
-}
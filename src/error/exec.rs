@@ -3,10 +3,12 @@
 
 use crate::error::arith::ArithError;
 use crate::error::parse::ParseError;
+use crate::i18n::{fl, fl_args};
 use crate::ShellCore;
 use nix::errno::Errno;
 use nix::sys::wait::WaitStatus;
-use std::num::ParseIntError;
+use std::io;
+use std::num::{IntErrorKind, ParseIntError};
 use std::os::fd::RawFd;
 
 #[derive(Debug, Clone)]
@@ -54,7 +56,12 @@ impl From<Errno> for ExecError {
 
 impl From<ParseIntError> for ExecError {
     fn from(e: ParseIntError) -> ExecError {
-        ExecError::ParseIntError(e.to_string())
+        let msg = match e.kind() {
+            IntErrorKind::Empty => fl("int-empty"),
+            IntErrorKind::PosOverflow | IntErrorKind::NegOverflow => fl("int-overflow"),
+            _ => fl("int-invalid-digit"),
+        };
+        ExecError::ParseIntError(msg)
     }
 }
 
@@ -83,40 +90,40 @@ impl From<ExecError> for String {
 impl From<&ExecError> for String {
     fn from(e: &ExecError) -> String {
         match e {
-            ExecError::Internal => "INTERNAL ERROR".to_string(),
-            ExecError::AmbiguousRedirect(name) => format!("{name}: ambiguous redirect"),
-            ExecError::ArrayIndexInvalid(name) => format!("[{name}]: bad array subscript"),
-            ExecError::ArgListTooLong(name) => format!("{name}: Arg list too long"),
-            ExecError::BadSubstitution(s) => format!("`{s}': bad substitution"),
-            ExecError::BadFd(fd) => format!("{fd}: bad file descriptor"),
+            ExecError::Internal => fl("exec-internal"),
+            ExecError::AmbiguousRedirect(name) => fl_args("exec-ambiguous-redirect", &[("name", name)]),
+            ExecError::ArrayIndexInvalid(name) => fl_args("exec-bad-array-subscript", &[("name", name)]),
+            ExecError::ArgListTooLong(name) => fl_args("exec-arg-list-too-long", &[("name", name)]),
+            ExecError::BadSubstitution(s) => fl_args("exec-bad-substitution", &[("word", s)]),
+            ExecError::BadFd(fd) => fl_args("exec-bad-fd", &[("fd", &fd.to_string())]),
             ExecError::CannotOverwriteExistingFile(file) => {
-                format!("{file}: cannot overwrite existing file")
+                fl_args("exec-cannot-overwrite", &[("file", file)])
             }
-            ExecError::CircularNameRef(name) => format!("{name}: circular name reference"),
-            ExecError::CommandNotFound(name) => format!("{name}: command not found"),
-            ExecError::InvalidIndirectExpansion(name) => format!("{name}: invalid indirect expansion"),
-            ExecError::InvalidName(name) => format!("`{name}': not a valid identifier"),
-            ExecError::InvalidNameRef(name) => format!("`{name}': invalid variable name for name reference"),
-            ExecError::InvalidOption(opt) => format!("{opt}: invalid option"),
-            ExecError::Interrupted => "interrupted".to_string(),
-            ExecError::ValidOnlyInFunction => "can only be used in a function".to_string(),
-            ExecError::VariableReadOnly(name) => format!("{name}: readonly variable"),
-            ExecError::VariableInvalid(name) => format!("`{name}': not a valid identifier"),
+            ExecError::CircularNameRef(name) => fl_args("exec-circular-nameref", &[("name", name)]),
+            ExecError::CommandNotFound(name) => fl_args("exec-command-not-found", &[("name", name)]),
+            ExecError::InvalidIndirectExpansion(name) => fl_args("exec-invalid-indirect-expansion", &[("name", name)]),
+            ExecError::InvalidName(name) => fl_args("exec-invalid-identifier", &[("name", name)]),
+            ExecError::InvalidNameRef(name) => fl_args("exec-invalid-nameref", &[("name", name)]),
+            ExecError::InvalidOption(opt) => fl_args("invalid-option", &[("opt", opt)]),
+            ExecError::Interrupted => fl("exec-interrupted"),
+            ExecError::ValidOnlyInFunction => fl("exec-only-in-function"),
+            ExecError::VariableReadOnly(name) => fl_args("exec-readonly-variable", &[("name", name)]),
+            ExecError::VariableInvalid(name) => fl_args("exec-invalid-identifier", &[("name", name)]),
             ExecError::ParseIntError(e) => e.to_string(),
-            ExecError::PermissionDenied(name) => format!("{name}: Permission denied"),
+            ExecError::PermissionDenied(name) => fl_args("exec-permission-denied", &[("name", name)]),
             ExecError::SelfRef(name) => {
-                format!("{name}: nameref variable self references not allowed")
+                fl_args("exec-nameref-self-reference", &[("name", name)])
             },
             ExecError::SyntaxError(near) => {
-                format!("syntax error near unexpected token `{}'", &near)
+                fl_args("exec-syntax-error", &[("token", near)])
             }
-            ExecError::Restricted(com) => format!("{com}: restricted"),
-            ExecError::RefCannotBeArray(name) => format!("{name}: reference variable cannot be an array"),
-            ExecError::SubstringMinus(n) => format!("{n}: substring expression < 0"),
-            ExecError::UnsupportedWaitStatus(ws) => format!("Unsupported wait status: {ws:?}"),
-            ExecError::UnboundVariable(name) => format!("{name}: unbound variable"),
-            ExecError::Errno(e) => format!("system error {e:?}"),
-            ExecError::Bug(msg) => format!("INTERNAL BUG: {msg}"),
+            ExecError::Restricted(com) => fl_args("exec-restricted", &[("name", com)]),
+            ExecError::RefCannotBeArray(name) => fl_args("exec-nameref-array", &[("name", name)]),
+            ExecError::SubstringMinus(n) => fl_args("exec-substring-minus", &[("num", &n.to_string())]),
+            ExecError::UnsupportedWaitStatus(ws) => fl_args("exec-unsupported-wait-status", &[("status", &format!("{ws:?}"))]),
+            ExecError::UnboundVariable(name) => fl_args("exec-unbound-variable", &[("name", name)]),
+            ExecError::Errno(e) => fl_args("exec-system-error", &[("errno", &format!("{e:?}"))]),
+            ExecError::Bug(msg) => fl_args("exec-bug", &[("msg", msg)]),
             ExecError::Other(name) => name.to_string(),
 
             ExecError::ArithError(s, a) => format!("{}: {}", s, String::from(a)),
@@ -127,6 +134,20 @@ impl From<&ExecError> for String {
 }
 
 impl ExecError {
+    pub fn from_io(name: &str, e: &io::Error) -> ExecError {
+        match e.kind() {
+            io::ErrorKind::NotFound => ExecError::Other(fl_args("no-such-file", &[("name", name)])),
+            io::ErrorKind::PermissionDenied => ExecError::PermissionDenied(name.to_string()),
+            io::ErrorKind::IsADirectory => {
+                ExecError::Other(fl_args("is-a-directory", &[("name", name)]))
+            }
+            kind => {
+                let err = kind.to_string();
+                ExecError::Other(fl_args("io-error", &[("name", name), ("err", &err)]))
+            }
+        }
+    }
+
     pub fn print(&self, core: &mut ShellCore) {
         let name = core.db.get_param("0").unwrap();
         let s: String = From::<&ExecError>::from(self);
@@ -138,7 +159,8 @@ impl ExecError {
             eprintln!("{}: {}", &name, &s);
         } else {
             let lineno = core.db.get_param("LINENO").unwrap_or("".to_string());
-            eprintln!("{}: line {}: {}", &name, &lineno, s);
+            let line = fl_args("at-line", &[("lineno", &lineno)]);
+            eprintln!("{}: {}: {}", &name, &line, s);
         }
     }
 }
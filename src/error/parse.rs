@@ -2,6 +2,7 @@
 //SPDX-License-Identifier: BSD-3-Clause
 
 use super::input::InputError;
+use crate::i18n::fl_args;
 use crate::ShellCore;
 
 #[derive(Debug, Clone)]
@@ -16,9 +17,9 @@ impl From<&ParseError> for String {
     fn from(e: &ParseError) -> String {
         match e {
             //ParseError::UnexpectedSymbol(s) => format!("Unexpected token: {}", s),
-            ParseError::UnexpectedSymbol(s) => format!("syntax error near unexpected token: {s}"),
+            ParseError::UnexpectedSymbol(s) => fl_args("parse-unexpected-token", &[("token", s)]),
             ParseError::Input(e) => From::from(e),
-            ParseError::WrongAlias(msg) => format!("Someting wrong alias: {msg}"),
+            ParseError::WrongAlias(msg) => fl_args("parse-wrong-alias", &[("msg", msg)]),
         }
     }
 }
@@ -31,7 +32,8 @@ impl ParseError {
             eprintln!("{}: {}", &name, &s);
         } else {
             let lineno = core.db.get_param("LINENO").unwrap_or("".to_string());
-            eprintln!("{}: line {}: {}", &name, &lineno, s);
+            let line = fl_args("at-line", &[("lineno", &lineno)]);
+            eprintln!("{}: {}: {}", &name, &line, s);
         }
     }
 }
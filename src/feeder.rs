@@ -2,13 +2,15 @@
 //SPDX-License-Identifier: BSD-3-Clause
 
 mod scanner;
-mod terminal;
+pub mod terminal;
 
 use crate::error::input::InputError;
 use crate::error::parse::ParseError;
+use crate::utils::{c_string, file};
 use crate::{utils, ShellCore};
 use std::fs::File;
-use std::io::{BufRead, BufReader, Lines};
+use std::io::{BufRead, BufReader, Split};
+use std::os::fd::{AsRawFd, FromRawFd};
 use std::sync::atomic::Ordering::Relaxed;
 
 #[derive(Debug, Default)]
@@ -18,7 +20,7 @@ pub struct Feeder {
     pub nest: Vec<(String, Vec<String>)>,
     pub lineno: usize,
     pub lineno_addition: usize,
-    script_lines: Option<Lines<BufReader<File>>>,
+    script_lines: Option<Split<BufReader<File>>>,
     pub main_feeder: bool,
     c_mode_buffer: Vec<String>,
     c_mode: bool,
@@ -45,11 +47,18 @@ impl Feeder {
     }
 
     pub fn set_file(&mut self, s: &str) -> Result<(), InputError> {
-        let file = match File::open(s) {
+        let file = match File::open(file::name_to_path(s)) {
             Ok(f) => f,
             Err(_) => return Err(InputError::NoSuchFile(s.to_string())),
         };
-        self.script_lines = Some(BufReader::new(file).lines());
+        /* moved away from small numbers that scripts use with exec 3>file etc. */
+        let fd = unsafe { libc::fcntl(file.as_raw_fd(), libc::F_DUPFD_CLOEXEC, 255) };
+        let file = match fd {
+            -1 => file,
+            _ => unsafe { File::from_raw_fd(fd) },
+        };
+        /* lines are not required to be UTF-8 */
+        self.script_lines = Some(BufReader::new(file).split(b'\n'));
         Ok(())
     }
 
@@ -114,7 +123,7 @@ impl Feeder {
 
         if let Some(lines) = self.script_lines.as_mut() {
             match lines.next() {
-                Some(Ok(line)) => return Ok(line + "\n"),
+                Some(Ok(line)) => return Ok(c_string::from_bytes(&line) + "\n"),
                 _ => return Err(InputError::Eof),
             }
         }
@@ -158,7 +167,14 @@ impl Feeder {
 
     pub fn feed_line(&mut self, core: &mut ShellCore) -> Result<(), InputError> {
         let line = match core.db.flags.contains('i') && self.script_lines.is_none() {
-            true => terminal::read_line(core, "PS1"),
+            true => {
+                core.run_prompt_command();
+                let line = terminal::read_line(core, "PS1");
+                if line.is_ok() {
+                    core.command_number += 1;
+                }
+                line
+            }
             false => self.read_script(),
         };
 
@@ -2,7 +2,7 @@
 //SPDX-License-Identifier: BSD-3-Clause
 
 use super::Feeder;
-use crate::ShellCore;
+use crate::{utils, ShellCore};
 
 impl Feeder {
     fn feed_and_connect(&mut self, core: &mut ShellCore) {
@@ -177,13 +177,6 @@ impl Feeder {
         self.scanner_chars(judge, core, 2) + 2
     }
 
-    pub fn scanner_history_expansion(&mut self, _: &mut ShellCore) -> usize {
-        match self.starts_with("!$") {
-            true => 2,
-            false => 0,
-        }
-    }
-
     pub fn scanner_dollar_special_and_positional_param(&mut self, core: &mut ShellCore) -> usize {
         if !self.starts_with("$") {
             return 0;
@@ -329,6 +322,21 @@ impl Feeder {
         self.scanner_chars(judge, core, 0)
     }
 
+    /* {varname} just before a redirect symbol */
+    pub fn scanner_redirect_var(&mut self) -> usize {
+        let Some(rest) = self.remaining.strip_prefix('{') else {
+            return 0;
+        };
+        let Some(len) = rest.find('}') else {
+            return 0;
+        };
+
+        match utils::is_var(&rest[..len]) && rest[len + 1..].starts_with(['<', '>']) {
+            true => len + 2,
+            false => 0,
+        }
+    }
+
     pub fn scanner_name_and_equal(&mut self, core: &mut ShellCore) -> usize {
         let name_len = self.scanner_name(core);
         if name_len == 0 {
@@ -411,7 +419,7 @@ impl Feeder {
 
     pub fn scanner_escape_directive_in_braced_param(&mut self, core: &mut ShellCore) -> usize {
         self.backslash_check_and_feed(vec!["@"], core);
-        self.scanner_one_of(&["@k", "@Q", "@K"])
+        self.scanner_one_of(&["@k", "@Q", "@K", "@P"])
     }
 
     pub fn scanner_test_compare_op(&mut self, core: &mut ShellCore) -> usize {
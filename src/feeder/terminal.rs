@@ -2,17 +2,17 @@
 //SPDX-License-Identifier: BSD-3-Clause
 
 mod completion;
+mod emacs;
 mod key;
+mod search;
+mod vi;
 
+use crate::core::history::expansion;
 use crate::error::input::InputError;
-use crate::utils::{arg, file};
-use crate::{file_check, ShellCore};
-use nix::unistd;
-use nix::unistd::User;
-use std::fs::File;
+use crate::utils::{arg, c_string};
+use crate::{signal, ShellCore};
 use std::io;
 use std::io::{Stdout, Write};
-use std::path::Path;
 use std::sync::atomic::Ordering::Relaxed;
 use termion::cursor::DetectCursorPos;
 use termion::event;
@@ -37,61 +37,27 @@ struct Terminal {
     tab_row: i32,
     tab_col: i32,
     escape_at_completion: bool,
-}
-
-fn oct_string(s: &str) -> bool {
-    if !s.starts_with('\\') {
-        return false;
-    }
-
-    for i in 1..4 {
-        match s.chars().nth(i) {
-            Some(c) => {
-                if !c.is_ascii_digit() {
-                    return false;
-                }
-            }
-            _ => return false,
-        }
-    }
-
-    true
-}
-
-fn oct_to_hex_in_str(from: &str) -> String {
-    let mut i = 0;
-    let mut pos = vec![];
-
-    for ch in from.chars() {
-        if oct_string(&from[i..]) {
-            pos.push(i);
-        }
-        i += ch.len_utf8();
-    }
-
-    let mut prev = 0;
-    let mut ans = String::new();
-    for p in pos {
-        ans += &from[prev..p];
-        if let Ok(n) = u32::from_str_radix(&from[p + 1..p + 4], 8) {
-            ans += &char::from_u32(n).unwrap().to_string();
-        }
-        prev = p + 4;
-    }
-    ans += &from[prev..];
-    ans
+    /* for incremental search */
+    search: Option<search::Search>,
+    /* for yank and undo */
+    yank_range: Option<(usize, usize)>,
+    undo_stack: Vec<(Vec<char>, usize)>,
+    undone: bool,
+    /* for vi mode */
+    vi: Option<vi::Vi>,
 }
 
 impl Terminal {
     pub fn new(core: &mut ShellCore, ps: &str) -> Self {
-        let raw_prompt = core.db.get_param(ps).unwrap_or_default();
-        let ansi_on_prompt = oct_to_hex_in_str(&raw_prompt);
-
-        let replaced_prompt = Self::make_prompt_string(&ansi_on_prompt);
+        let replaced_prompt = core.make_prompt(ps);
         let prompt = replaced_prompt
             .replace("\\[", "")
             .replace("\\]", "")
             .to_string();
+        Self::with_prompt(&prompt, Self::make_width_map(&replaced_prompt))
+    }
+
+    fn with_prompt(prompt: &str, prompt_width_map: Vec<usize>) -> Self {
         print!("{prompt}");
         io::stdout().flush().unwrap();
 
@@ -106,69 +72,21 @@ impl Terminal {
             head: prompt.chars().count(),
             hist_ptr: 0,
             size: Terminal::size(),
-            prompt_width_map: Self::make_width_map(&replaced_prompt),
+            prompt_width_map,
             prev_key: event::Key::Char('a'),
             tab_num: 0,
             completion_candidate: String::new(),
             tab_row: -1,
             tab_col: -1,
             escape_at_completion: true,
+            search: None,
+            yank_range: None,
+            undo_stack: vec![],
+            undone: false,
+            vi: None,
         }
     }
 
-    fn get_branch(cwd: &str) -> String {
-        let mut dirs: Vec<String> = cwd.split("/").map(|s| s.to_string()).collect();
-        while !dirs.is_empty() {
-            let path = dirs.join("/") + "/.git/HEAD";
-            dirs.pop();
-
-            if !file_check::is_regular_file(&path) {
-                continue;
-            }
-
-            if let Ok(mut f) = File::open(Path::new(&path)) {
-                return match f.read_line() {
-                    Ok(Some(s)) => s.replace("ref: refs/heads/", "") + "🌵",
-                    _ => "".to_string(),
-                };
-            }
-        }
-
-        "".to_string()
-    }
-
-    fn make_prompt_string(raw: &str) -> String {
-        let uid = unistd::getuid();
-        let user = match User::from_uid(uid) {
-            Ok(Some(u)) => u.name,
-            _ => "".to_string(),
-        };
-        let hostname = match unistd::gethostname() {
-            Ok(h) => file::oss_to_name(&h),
-            _ => "".to_string(),
-        };
-
-        let homedir = match User::from_uid(uid) {
-            Ok(Some(u)) => file::buf_to_name(&u.dir),
-            _ => "".to_string(),
-        };
-        let mut cwd = match unistd::getcwd() {
-            Ok(p) => file::buf_to_name(&p),
-            _ => "".to_string(),
-        };
-        let branch = Self::get_branch(&cwd);
-
-        if cwd.starts_with(&homedir) {
-            cwd = cwd.replacen(&homedir, "~", 1);
-        }
-
-        raw.replace("\\u", &user)
-            .replace("\\h", &hostname)
-            .replace("\\w", &cwd)
-            .replace("\\b", &branch)
-            .to_string()
-    }
-
     fn make_width_map(prompt: &str) -> Vec<usize> {
         let tmp = prompt
             .replace("\\[", "\x01")
@@ -348,7 +266,8 @@ impl Terminal {
     pub fn call_history(&mut self, inc: i32, core: &mut ShellCore) {
         let prev = self.hist_ptr;
         let prev_str = self.get_string(self.prompt.chars().count());
-        Self::shift_in_range(&mut self.hist_ptr, inc, 0, isize::MAX as usize);
+        let max = core.history.len().saturating_sub(1);
+        Self::shift_in_range(&mut self.hist_ptr, inc, 0, max);
 
         self.chars = self.prompt.chars().collect();
         self.chars.extend(
@@ -391,7 +310,7 @@ impl Terminal {
 }
 
 fn signal_check(core: &mut ShellCore, term: &mut Terminal) -> Result<bool, InputError> {
-    if core.sigint.load(Relaxed) || core.trapped.iter_mut().any(|t| t.0.load(Relaxed)) {
+    if core.sigint.load(Relaxed) || signal::trap_pending(core) {
         term.write("\r\n");
         return Err(InputError::Interrupt);
     }
@@ -399,35 +318,98 @@ fn signal_check(core: &mut ShellCore, term: &mut Terminal) -> Result<bool, Input
 }
 
 pub fn read_line(core: &mut ShellCore, prompt: &str) -> Result<String, InputError> {
+    read_line_with_history(core, prompt, "")
+}
+
+fn read_line_with_history(
+    core: &mut ShellCore,
+    prompt: &str,
+    text: &str,
+) -> Result<String, InputError> {
     let mut term = Terminal::new(core, prompt);
+    text.chars().for_each(|c| term.insert(c));
+    let mut ans = edit(core, term)?;
+
+    if core.db.flags.contains('H') {
+        match expansion::expand(core, &ans) {
+            Ok((expanded, print_only)) if expanded != ans => {
+                print!("{}", &expanded);
+                if core.shopts.query("histverify") {
+                    return read_line_with_history(core, prompt, expanded.trim_end());
+                }
+                ans = expanded;
+                if print_only {
+                    core.add_history(ans.trim_end());
+                    return Ok("\n".to_string());
+                }
+            }
+            Ok(_) => {}
+            Err(msg) => {
+                eprintln!("sush: {}", &msg);
+                if core.shopts.query("histreedit") {
+                    return read_line_with_history(core, prompt, ans.trim_end());
+                }
+                return Ok("\n".to_string());
+            }
+        }
+    }
+
+    if core.options.query("history") {
+        core.add_history(ans.trim_end());
+    }
+    Ok(ans)
+}
+
+/* for read -e: the prompt is not expanded and the line is not recorded in the history */
+pub fn read_line_with_text(
+    core: &mut ShellCore,
+    prompt: &str,
+    text: &str,
+) -> Result<String, InputError> {
+    let mut term = Terminal::with_prompt(prompt, Terminal::make_width_map(prompt));
+    text.chars().for_each(|c| term.insert(c));
+
+    edit(core, term)
+}
+
+fn edit(core: &mut ShellCore, mut term: Terminal) -> Result<String, InputError> {
+    if core.options.query("vi") {
+        term.vi = Some(vi::Vi::default());
+    }
     signal_check(core, &mut term)?;
 
-    core.history.insert(0, String::new());
+    core.start_history_editing();
 
     for c in io::stdin().keys() {
         let c = c.unwrap();
 
         if let Err(e) = signal_check(core, &mut term) {
-            core.history.remove(0);
+            core.cancel_history_editing();
             return Err(e);
         }
 
         term.check_terminal_size();
+        let before = (term.chars.clone(), term.head);
         match key::action(core, &mut term, &c) {
             Ok(true) => break,
             Ok(false) => term.prev_key = c,
             Err(e) => {
-                core.history.remove(0);
+                core.cancel_history_editing();
                 return Err(e);
             }
         }
 
+        let undone = std::mem::take(&mut term.undone);
+        if term.chars != before.0 && !undone {
+            term.undo_stack.push(before);
+        }
+
         term.completion_finish_check();
         term.check_scroll();
     }
 
-    let ans = term.get_string(term.prompt.chars().count());
-    core.history[0] = ans.trim_end().to_string();
+    let ans = c_string::from_chars(&term.get_string(term.prompt.chars().count()));
+    core.cancel_history_editing();
     Ok(ans)
 }
 
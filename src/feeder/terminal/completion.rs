@@ -7,6 +7,7 @@ use crate::elements::command::simple::SimpleCommand;
 use crate::elements::io::pipe::Pipe;
 use crate::error::exec::ExecError;
 use crate::feeder::terminal::Terminal;
+use crate::i18n::fl;
 use crate::utils::arg;
 use crate::{file_check, utils, Feeder, ShellCore};
 use unicode_width::UnicodeWidthStr;
@@ -122,7 +123,7 @@ impl Terminal {
         );
         let mut feeder = Feeder::new(&command);
 
-        if let Ok(Some(mut a)) = SimpleCommand::parse(&mut feeder, core) {
+        if let Ok(Some(a)) = SimpleCommand::parse(&mut feeder, core) {
             let mut dummy = Pipe::new("".to_string());
             a.exec(core, &mut dummy)?;
         }
@@ -139,7 +140,7 @@ impl Terminal {
         );
         let mut feeder = Feeder::new(&command);
 
-        if let Ok(Some(mut a)) = SimpleCommand::parse(&mut feeder, core) {
+        if let Ok(Some(a)) = SimpleCommand::parse(&mut feeder, core) {
             let mut dummy = Pipe::new("".to_string());
             a.exec(core, &mut dummy)?;
         }
@@ -152,7 +153,7 @@ impl Terminal {
         let word_num = core.db.get_var_len("COMP_WORDS") as i32;
 
         if prev_pos < 0 || prev_pos >= word_num {
-            return Err(ExecError::Other("pos error".to_string()));
+            return Err(ExecError::Other(fl("completion-position-error")));
         }
 
         let org_word = core.db.get_elem("COMP_WORDS", "0")?;
@@ -173,7 +174,7 @@ impl Terminal {
         }
 
         match core.db.get_var_len("COMPREPLY") {
-            0 => Err(ExecError::Other("no completion cand".to_string())),
+            0 => Err(ExecError::Other(fl("no-completion-candidate"))),
             _ => Ok(()),
         }
     }
@@ -203,7 +204,7 @@ impl Terminal {
 
         let list = self.make_default_compreply(core, &args, &com, &pos);
         if list.is_empty() {
-            return Err(ExecError::Other("empty list".to_string()));
+            return Err(ExecError::Other(fl("empty-completion-list")));
         }
 
         let tmp: Vec<String> = list
@@ -0,0 +1,173 @@
+//SPDX-FileCopyrightText: 2025 Ryuichi Ueda ryuichiueda@gmail.com
+//SPDX-License-Identifier: BSD-3-Clause
+
+use crate::feeder::terminal::Terminal;
+use crate::ShellCore;
+use termion::event::Key;
+
+const KILL_RING_MAX: usize = 10;
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric()
+}
+
+fn is_kill_key(key: &Key) -> bool {
+    matches!(
+        key,
+        Key::Ctrl('k') | Key::Ctrl('u') | Key::Ctrl('w') | Key::Alt('d') | Key::Alt('\x7f')
+    )
+}
+
+impl Terminal {
+    fn prompt_len(&self) -> usize {
+        self.prompt.chars().count()
+    }
+
+    /* the end of the next word as forward-word of readline */
+    fn next_word_end(&self) -> usize {
+        let mut pos = self.head;
+        while pos < self.chars.len() && !is_word_char(self.chars[pos]) {
+            pos += 1;
+        }
+        while pos < self.chars.len() && is_word_char(self.chars[pos]) {
+            pos += 1;
+        }
+        pos
+    }
+
+    fn prev_word_start(&self, word_char: fn(char) -> bool) -> usize {
+        let min = self.prompt_len();
+        let mut pos = self.head;
+        while pos > min && !word_char(self.chars[pos - 1]) {
+            pos -= 1;
+        }
+        while pos > min && word_char(self.chars[pos - 1]) {
+            pos -= 1;
+        }
+        pos
+    }
+
+    fn kill(&mut self, core: &mut ShellCore, from: usize, to: usize, backward: bool) {
+        if from >= to {
+            self.cloop();
+            return;
+        }
+
+        let killed: String = self.chars.drain(from..to).collect();
+        self.head = from;
+
+        match (is_kill_key(&self.prev_key), core.kill_ring.last_mut()) {
+            (true, Some(last)) if backward => last.insert_str(0, &killed),
+            (true, Some(last)) => last.push_str(&killed),
+            _ => {
+                core.kill_ring.push(killed);
+                if core.kill_ring.len() > KILL_RING_MAX {
+                    core.kill_ring.remove(0);
+                }
+            }
+        }
+        self.rewrite(true);
+    }
+
+    pub fn kill_line(&mut self, core: &mut ShellCore) {
+        self.kill(core, self.head, self.chars.len(), false);
+    }
+
+    pub fn unix_line_discard(&mut self, core: &mut ShellCore) {
+        self.kill(core, self.prompt_len(), self.head, true);
+    }
+
+    pub fn unix_word_rubout(&mut self, core: &mut ShellCore) {
+        let from = self.prev_word_start(|c| !c.is_whitespace());
+        self.kill(core, from, self.head, true);
+    }
+
+    pub fn kill_word(&mut self, core: &mut ShellCore) {
+        self.kill(core, self.head, self.next_word_end(), false);
+    }
+
+    pub fn backward_kill_word(&mut self, core: &mut ShellCore) {
+        let from = self.prev_word_start(is_word_char);
+        self.kill(core, from, self.head, true);
+    }
+
+    pub fn yank(&mut self, core: &mut ShellCore) {
+        let text = match core.kill_ring.last() {
+            Some(t) => t.clone(),
+            None => {
+                self.cloop();
+                return;
+            }
+        };
+
+        let from = self.head;
+        self.chars.splice(from..from, text.chars());
+        self.head += text.chars().count();
+        self.yank_range = Some((from, self.head));
+        self.rewrite(true);
+    }
+
+    /* replaces the yanked text with the previous one in the kill ring */
+    pub fn yank_pop(&mut self, core: &mut ShellCore) {
+        let yanked = matches!(self.prev_key, Key::Ctrl('y') | Key::Alt('y'));
+        let (from, to) = match self.yank_range {
+            Some(r) if yanked && core.kill_ring.len() > 1 => r,
+            _ => {
+                self.cloop();
+                return;
+            }
+        };
+
+        core.kill_ring.rotate_right(1);
+        let text = core.kill_ring.last().unwrap().clone();
+        self.chars.splice(from..to, text.chars());
+        self.head = from + text.chars().count();
+        self.yank_range = Some((from, self.head));
+        self.rewrite(true);
+    }
+
+    pub fn forward_word(&mut self) {
+        self.head = self.next_word_end();
+        self.goto(self.head);
+        self.flush();
+    }
+
+    pub fn backward_word(&mut self) {
+        self.head = self.prev_word_start(is_word_char);
+        self.goto(self.head);
+        self.flush();
+    }
+
+    pub fn transpose_chars(&mut self) {
+        let min = self.prompt_len();
+        if self.head == min || self.chars.len() < min + 2 {
+            self.cloop();
+            return;
+        }
+
+        if self.head == self.chars.len() {
+            self.head -= 1;
+        }
+        self.chars.swap(self.head - 1, self.head);
+        self.head += 1;
+        self.rewrite(true);
+    }
+
+    pub fn clear_screen(&mut self) {
+        self.write(termion::clear::All.as_ref());
+        self.prompt_row = 1;
+        self.rewrite(true);
+    }
+
+    pub fn undo(&mut self) {
+        match self.undo_stack.pop() {
+            Some((chars, head)) => {
+                self.chars = chars;
+                self.head = head;
+                self.undone = true;
+                self.rewrite(true);
+            }
+            None => self.cloop(),
+        }
+    }
+}
@@ -9,11 +9,21 @@ use termion::event;
 use termion::event::Key;
 
 pub fn action(core: &mut ShellCore, term: &mut Terminal, c: &Key) -> Result<bool, InputError> {
+    if term.search_key(c) {
+        return Ok(false);
+    }
+    if let Some(done) = term.vi_key(core, c)? {
+        return Ok(done);
+    }
+
     match c {
         event::Key::Ctrl(ch) => ctrl(core, term, *ch)?,
+        event::Key::Alt(ch) => alt(core, term, *ch),
         event::Key::Down | event::Key::Left | event::Key::Right | event::Key::Up => {
             arrow(term, core, c)
         }
+        event::Key::AltLeft | event::Key::CtrlLeft => term.backward_word(),
+        event::Key::AltRight | event::Key::CtrlRight => term.forward_word(),
         event::Key::Backspace => term.backspace(),
         event::Key::Delete => term.delete(),
         event::Key::Char(c) => return char_key(term, core, c),
@@ -42,11 +52,31 @@ fn ctrl(core: &mut ShellCore, term: &mut Terminal, c: char) -> Result<(), InputE
         }
         'e' => term.goto_end(),
         'f' => term.shift_cursor(1),
+        'k' => term.kill_line(core),
+        'l' => term.clear_screen(),
+        'r' => term.start_search(core, false),
+        's' => term.start_search(core, true),
+        't' => term.transpose_chars(),
+        'u' => term.unix_line_discard(core),
+        'w' => term.unix_word_rubout(core),
+        'y' => term.yank(core),
+        '7' => term.undo(), // Ctrl-_
         _ => {}
     }
     Ok(())
 }
 
+fn alt(core: &mut ShellCore, term: &mut Terminal, c: char) {
+    match c {
+        'b' => term.backward_word(),
+        'd' => term.kill_word(core),
+        'f' => term.forward_word(),
+        'y' => term.yank_pop(core),
+        '\x7f' => term.backward_kill_word(core),
+        _ => {}
+    }
+}
+
 fn arrow(term: &mut Terminal, core: &mut ShellCore, key: &event::Key) {
     if term.tab_num > 1 {
         match key {
@@ -0,0 +1,196 @@
+//SPDX-FileCopyrightText: 2025 Ryuichi Ueda ryuichiueda@gmail.com
+//SPDX-License-Identifier: BSD-3-Clause
+
+use crate::feeder::terminal::Terminal;
+use crate::ShellCore;
+use termion::event::Key;
+use termion::style;
+
+pub struct Search {
+    query: String,
+    forward: bool,
+    failed: bool,
+    entries: Vec<String>,
+    pos: Option<usize>,
+    original: Vec<char>,
+    original_head: usize,
+}
+
+impl Search {
+    fn find(&mut self, from: usize) -> bool {
+        let found = match self.forward {
+            true => (0..=from)
+                .rev()
+                .find(|i| self.entries[*i].contains(&self.query)),
+            false => (from..self.entries.len()).find(|i| self.entries[*i].contains(&self.query)),
+        };
+
+        self.failed = found.is_none();
+        if found.is_some() {
+            self.pos = found;
+        }
+        !self.failed
+    }
+
+    fn matched(&self) -> Option<&String> {
+        self.pos.map(|p| &self.entries[p])
+    }
+
+    fn label(&self) -> String {
+        let failed = if self.failed { "failed " } else { "" };
+        let reverse = if self.forward { "" } else { "reverse-" };
+        format!("({}{}i-search)`{}': ", failed, reverse, self.query)
+    }
+}
+
+impl Terminal {
+    pub fn start_search(&mut self, core: &mut ShellCore, forward: bool) {
+        if let Some(s) = self.search.as_mut() {
+            Self::step_search(s, forward);
+        } else {
+            self.search = Some(Search {
+                query: String::new(),
+                forward,
+                failed: false,
+                entries: core.history_entries(),
+                pos: None,
+                original: self.chars.clone(),
+                original_head: self.head,
+            });
+        }
+        self.draw_search();
+    }
+
+    /* repeated Ctrl-R and Ctrl-S */
+    fn step_search(s: &mut Search, forward: bool) {
+        s.forward = forward;
+        if s.entries.is_empty() {
+            s.failed = true;
+            return;
+        }
+
+        let from = match (s.pos, forward) {
+            (None, true) => return,
+            (None, false) => 0,
+            (Some(0), true) => {
+                s.failed = true;
+                return;
+            }
+            (Some(p), true) => p - 1,
+            (Some(p), false) => p + 1,
+        };
+        if from < s.entries.len() {
+            s.find(from);
+        } else {
+            s.failed = true;
+        }
+    }
+
+    /* returns false when the key should be processed as usual */
+    pub fn search_key(&mut self, c: &Key) -> bool {
+        let s = match self.search.as_mut() {
+            Some(s) => s,
+            None => return false,
+        };
+
+        match c {
+            Key::Char('\t') | Key::Char('\n') => {}
+            Key::Char(ch) => {
+                s.query.push(*ch);
+                let from = s.pos.unwrap_or(0);
+                if !s.entries.is_empty() {
+                    s.find(from);
+                }
+            }
+            Key::Backspace => {
+                s.query.pop();
+                s.pos = None;
+                s.failed = false;
+                let from = match s.forward {
+                    true => s.entries.len().saturating_sub(1),
+                    false => 0,
+                };
+                if !s.query.is_empty() && !s.entries.is_empty() {
+                    s.find(from);
+                }
+            }
+            Key::Ctrl('g') => {
+                self.end_search(false);
+                return true;
+            }
+            Key::Ctrl('r') | Key::Ctrl('s') => return false,
+            _ => {}
+        }
+
+        match c {
+            Key::Char('\t') | Key::Char('\n') => {
+                self.end_search(true);
+                false
+            }
+            Key::Char(_) | Key::Backspace => {
+                self.draw_search();
+                true
+            }
+            _ => {
+                self.end_search(true);
+                false
+            }
+        }
+    }
+
+    pub fn end_search(&mut self, accept: bool) {
+        let s = match self.search.take() {
+            Some(s) => s,
+            None => return,
+        };
+
+        let prompt_len = self.prompt.chars().count();
+        match (accept, s.matched()) {
+            (true, Some(m)) => {
+                let line = m.replace("↵ \0", "\n");
+                let offset = match line.find(&s.query) {
+                    Some(p) => line[..p].chars().count(),
+                    None => 0,
+                };
+                self.chars = self.prompt.chars().chain(line.chars()).collect();
+                self.head = prompt_len + offset;
+            }
+            _ => {
+                self.chars = s.original;
+                self.head = s.original_head;
+            }
+        }
+        self.rewrite(true);
+    }
+
+    fn draw_search(&mut self) {
+        let s = match self.search.as_ref() {
+            Some(s) => s,
+            None => return,
+        };
+
+        let mut text = s.label();
+        if let Some(m) = s.matched() {
+            let m = m.replace("↵ \0", "\n");
+            match m.find(&s.query).filter(|_| !s.query.is_empty()) {
+                Some(p) => {
+                    let end = p + s.query.len();
+                    text += &format!(
+                        "{}{}{}{}{}",
+                        &m[..p],
+                        style::Invert,
+                        &m[p..end],
+                        style::Reset,
+                        &m[end..]
+                    );
+                }
+                None => text += &m,
+            }
+        }
+
+        self.goto(0);
+        self.write(termion::clear::AfterCursor.as_ref());
+        self.write(&text.replace("\n", "\n\r"));
+        self.flush();
+    }
+}
@@ -0,0 +1,450 @@
+//SPDX-FileCopyrightText: 2025 Ryuichi Ueda ryuichiueda@gmail.com
+//SPDX-License-Identifier: BSD-3-Clause
+
+use crate::error::input::InputError;
+use crate::feeder::terminal::Terminal;
+use crate::utils::c_string;
+use crate::ShellCore;
+use std::{fs, process};
+use termion::cursor::DetectCursorPos;
+use termion::event::Key;
+
+const CHANGE_COMMANDS: &str = "xXdcpPiaIAsSCD";
+
+#[derive(Default)]
+pub struct Vi {
+    normal: bool,
+    count: String,
+    operator: Option<(char, usize)>,
+    insert_undo_len: usize,
+    keys: Vec<Key>,
+    last_change: Vec<Key>,
+    replaying: bool,
+    search_input: Option<String>,
+    search_word: String,
+    search_pos: usize,
+}
+
+impl Vi {
+    fn idle(&self) -> bool {
+        self.normal
+            && self.operator.is_none()
+            && self.count.is_empty()
+            && self.search_input.is_none()
+    }
+
+    fn take_count(&mut self) -> usize {
+        let n = self.count.parse::<usize>().unwrap_or(1);
+        self.count.clear();
+        std::cmp::max(n, 1)
+    }
+}
+
+/* 0: blank, 1: word, 2: others */
+fn class(c: char) -> u8 {
+    match c {
+        _ if c.is_whitespace() => 0,
+        _ if c.is_alphanumeric() || c == '_' => 1,
+        _ => 2,
+    }
+}
+
+impl Terminal {
+    /* returns None when the key should be processed as usual */
+    pub fn vi_key(&mut self, core: &mut ShellCore, c: &Key) -> Result<Option<bool>, InputError> {
+        let mut vi = match self.vi.take() {
+            Some(vi) => vi,
+            None => return Ok(None),
+        };
+
+        vi.keys.push(*c);
+        let ans = self.vi_dispatch(core, &mut vi, c);
+
+        if vi.idle() {
+            let command = vi
+                .keys
+                .iter()
+                .find(|k| !matches!(k, Key::Char(d) if d.is_ascii_digit()));
+            if let Some(Key::Char(ch)) = command
+                && CHANGE_COMMANDS.contains(*ch)
+            {
+                vi.last_change = vi.keys.clone();
+            }
+            vi.keys.clear();
+        }
+
+        self.vi = Some(vi);
+        ans
+    }
+
+    fn vi_dispatch(
+        &mut self,
+        core: &mut ShellCore,
+        vi: &mut Vi,
+        c: &Key,
+    ) -> Result<Option<bool>, InputError> {
+        if vi.search_input.is_some() {
+            self.vi_search_input(core, vi, c);
+            return Ok(Some(false));
+        }
+
+        if !vi.normal {
+            return Ok(self.vi_insert_key(vi, c));
+        }
+
+        let ch = match c {
+            Key::Char('\n') => return Ok(None),
+            Key::Char(ch) => *ch,
+            Key::Backspace => 'h',
+            Key::Esc => {
+                vi.count.clear();
+                vi.operator = None;
+                return Ok(Some(false));
+            }
+            _ => return Ok(None),
+        };
+
+        if ch.is_ascii_digit() && (ch != '0' || !vi.count.is_empty()) {
+            vi.count.push(ch);
+            return Ok(Some(false));
+        }
+
+        let count = vi.take_count();
+        if let Some((op, op_count)) = vi.operator.take() {
+            self.vi_operate(core, vi, op, ch, op_count * count);
+            return Ok(Some(false));
+        }
+
+        match ch {
+            'h' | 'l' | 'w' | 'b' | 'e' | '0' | '^' | '$' | ' ' => {
+                self.head = self.vi_motion(ch, count);
+                self.vi_clamp();
+            }
+            'i' => self.vi_start_insert(vi),
+            'a' => {
+                self.head = std::cmp::min(self.head + 1, self.chars.len());
+                self.vi_start_insert(vi);
+            }
+            'I' => {
+                self.head = self.vi_motion('^', 1);
+                self.vi_start_insert(vi);
+            }
+            'A' => {
+                self.head = self.chars.len();
+                self.vi_start_insert(vi);
+            }
+            'x' => self.vi_operate(core, vi, 'd', 'l', count),
+            'X' => self.vi_operate(core, vi, 'd', 'h', count),
+            's' => self.vi_operate(core, vi, 'c', 'l', count),
+            'S' => self.vi_operate(core, vi, 'c', 'c', 1),
+            'D' => self.vi_operate(core, vi, 'd', '$', 1),
+            'C' => self.vi_operate(core, vi, 'c', '$', 1),
+            'd' | 'c' | 'y' => vi.operator = Some((ch, count)),
+            'p' | 'P' => self.vi_put(core, ch == 'p', count),
+            'u' => self.undo(),
+            '.' => self.vi_repeat(core, vi)?,
+            'k' | '-' => self.vi_history(core, count as i32),
+            'j' | '+' => self.vi_history(core, -(count as i32)),
+            '/' => {
+                vi.search_input = Some(String::new());
+                self.vi_draw_search(vi);
+                return Ok(Some(false));
+            }
+            'n' => self.vi_search(core, vi, true),
+            'N' => self.vi_search(core, vi, false),
+            'v' => return self.vi_edit_with_editor(core).map(Some),
+            _ => self.cloop(),
+        }
+
+        self.rewrite(true);
+        Ok(Some(false))
+    }
+
+    fn vi_insert_key(&mut self, vi: &mut Vi, c: &Key) -> Option<bool> {
+        match c {
+            Key::Esc => {
+                vi.normal = true;
+                self.undo_stack.truncate(vi.insert_undo_len + 1);
+                if self.head > self.prompt.chars().count() {
+                    self.head -= 1;
+                }
+                self.rewrite(true);
+                Some(false)
+            }
+            Key::Char('\n') | Key::Char('\t') => None,
+            Key::Char(ch) => {
+                self.insert(*ch);
+                Some(false)
+            }
+            Key::Backspace => {
+                self.backspace();
+                Some(false)
+            }
+            _ => None,
+        }
+    }
+
+    fn vi_start_insert(&mut self, vi: &mut Vi) {
+        vi.normal = false;
+        vi.insert_undo_len = self.undo_stack.len();
+    }
+
+    /* the cursor in normal mode stays on a character */
+    fn vi_clamp(&mut self) {
+        let min = self.prompt.chars().count();
+        let max = std::cmp::max(min, self.chars.len().saturating_sub(1));
+        self.head = self.head.clamp(min, max);
+    }
+
+    fn vi_motion(&self, motion: char, count: usize) -> usize {
+        let min = self.prompt.chars().count();
+        let len = self.chars.len();
+        let mut pos = self.head;
+
+        for _ in 0..count {
+            pos = match motion {
+                'h' => std::cmp::max(pos.saturating_sub(1), min),
+                'l' | ' ' => std::cmp::min(pos + 1, len),
+                'w' => self.vi_next_word(pos),
+                'b' => self.vi_prev_word(pos),
+                'e' => self.vi_word_end(pos),
+                '0' => min,
+                '^' => (min..len)
+                    .find(|i| !self.chars[*i].is_whitespace())
+                    .unwrap_or(len),
+                '$' => len,
+                _ => pos,
+            };
+        }
+        pos
+    }
+
+    fn vi_next_word(&self, mut pos: usize) -> usize {
+        let len = self.chars.len();
+        if pos < len {
+            let cls = class(self.chars[pos]);
+            while pos < len && cls != 0 && class(self.chars[pos]) == cls {
+                pos += 1;
+            }
+        }
+        while pos < len && class(self.chars[pos]) == 0 {
+            pos += 1;
+        }
+        pos
+    }
+
+    fn vi_prev_word(&self, mut pos: usize) -> usize {
+        let min = self.prompt.chars().count();
+        while pos > min && class(self.chars[pos - 1]) == 0 {
+            pos -= 1;
+        }
+        if pos > min {
+            let cls = class(self.chars[pos - 1]);
+            while pos > min && class(self.chars[pos - 1]) == cls {
+                pos -= 1;
+            }
+        }
+        pos
+    }
+
+    fn vi_word_end(&self, mut pos: usize) -> usize {
+        let len = self.chars.len();
+        pos += 1;
+        while pos < len && class(self.chars[pos]) == 0 {
+            pos += 1;
+        }
+        if pos < len {
+            let cls = class(self.chars[pos]);
+            while pos + 1 < len && class(self.chars[pos + 1]) == cls {
+                pos += 1;
+            }
+        }
+        std::cmp::min(pos, len.saturating_sub(1))
+    }
+
+    fn vi_operate(
+        &mut self,
+        core: &mut ShellCore,
+        vi: &mut Vi,
+        op: char,
+        motion: char,
+        count: usize,
+    ) {
+        let min = self.prompt.chars().count();
+        let (from, to) = match motion {
+            _ if motion == op => (min, self.chars.len()),
+            'w' if op == 'c' => {
+                let end = self.vi_motion('e', count);
+                (self.head, std::cmp::min(end + 1, self.chars.len()))
+            }
+            'e' => {
+                let end = self.vi_motion('e', count);
+                (self.head, std::cmp::min(end + 1, self.chars.len()))
+            }
+            'h' | 'l' | ' ' | 'w' | 'b' | '0' | '^' | '$' => {
+                let target = self.vi_motion(motion, count);
+                (
+                    std::cmp::min(self.head, target),
+                    std::cmp::max(self.head, target),
+                )
+            }
+            _ => {
+                self.cloop();
+                return;
+            }
+        };
+
+        let text: String = self.chars[from..to].iter().collect();
+        if !text.is_empty() {
+            core.kill_ring.push(text);
+        }
+
+        match op {
+            'y' => self.head = from,
+            _ => {
+                self.chars.drain(from..to);
+                self.head = from;
+            }
+        }
+
+        match op {
+            'c' => self.vi_start_insert(vi),
+            _ => self.vi_clamp(),
+        }
+        self.rewrite(true);
+    }
+
+    fn vi_put(&mut self, core: &mut ShellCore, after: bool, count: usize) {
+        let text = match core.kill_ring.last() {
+            Some(t) => t.repeat(count),
+            None => {
+                self.cloop();
+                return;
+            }
+        };
+
+        if after && self.head < self.chars.len() {
+            self.head += 1;
+        }
+        self.chars.splice(self.head..self.head, text.chars());
+        self.head += text.chars().count();
+        self.head = self.head.saturating_sub(1);
+        self.vi_clamp();
+    }
+
+    fn vi_repeat(&mut self, core: &mut ShellCore, vi: &mut Vi) -> Result<(), InputError> {
+        if vi.last_change.is_empty() || vi.replaying {
+            self.cloop();
+            return Ok(());
+        }
+
+        vi.replaying = true;
+        for k in vi.last_change.clone() {
+            self.vi_dispatch(core, vi, &k)?;
+        }
+        if !vi.normal {
+            self.vi_insert_key(vi, &Key::Esc);
+        }
+        vi.replaying = false;
+        Ok(())
+    }
+
+    fn vi_history(&mut self, core: &mut ShellCore, inc: i32) {
+        self.call_history(inc, core);
+        self.head = self.prompt.chars().count();
+    }
+
+    fn vi_draw_search(&mut self, vi: &Vi) {
+        let input = vi.search_input.clone().unwrap_or_default();
+        self.goto(0);
+        self.write(termion::clear::AfterCursor.as_ref());
+        self.write(&format!("/{}", input));
+        self.flush();
+    }
+
+    fn vi_search_input(&mut self, core: &mut ShellCore, vi: &mut Vi, c: &Key) {
+        let input = vi.search_input.as_mut().unwrap();
+        match c {
+            Key::Char('\n') => {
+                if !input.is_empty() {
+                    vi.search_word = input.clone();
+                }
+                vi.search_input = None;
+                vi.search_pos = 0;
+                self.vi_search(core, vi, true);
+                self.rewrite(true);
+            }
+            Key::Char(ch) => input.push(*ch),
+            Key::Backspace if !input.is_empty() => {
+                input.pop();
+            }
+            _ => {
+                vi.search_input = None;
+                self.rewrite(true);
+            }
+        }
+
+        if vi.search_input.is_some() {
+            self.vi_draw_search(vi);
+        }
+    }
+
+    /* searches older entries when backward is true */
+    fn vi_search(&mut self, core: &mut ShellCore, vi: &mut Vi, backward: bool) {
+        let entries = core.history_entries();
+        let word = &vi.search_word;
+        let found = match backward {
+            true => (vi.search_pos..entries.len()).find(|i| entries[*i].contains(word)),
+            false => (0..vi.search_pos.saturating_sub(1))
+                .rev()
+                .find(|i| entries[*i].contains(word)),
+        };
+
+        match found.filter(|_| !word.is_empty()) {
+            Some(i) => {
+                let line = entries[i].replace("↵ \0", "\n");
+                self.chars = self.prompt.chars().chain(line.chars()).collect();
+                self.head = self.prompt.chars().count();
+                vi.search_pos = i + 1;
+            }
+            None => self.cloop(),
+        }
+    }
+
+    fn vi_edit_with_editor(&mut self, core: &mut ShellCore) -> Result<bool, InputError> {
+        let editor = ["VISUAL", "EDITOR"]
+            .iter()
+            .map(|v| core.db.get_param(v).unwrap_or_default())
+            .find(|e| !e.is_empty())
+            .unwrap_or("vi".to_string());
+
+        let path = format!("/tmp/sush-edit-{}.sh", process::id());
+        let line = self.get_string(self.prompt.chars().count());
+        if fs::write(&path, c_string::to_bytes(&(line + "\n"))).is_err() {
+            self.cloop();
+            return Ok(false);
+        }
+
+        self.goto(self.chars.len());
+        self.write("\r\n");
+        self.flush();
+        let _ = self.stdout.suspend_raw_mode();
+        let _ = process::Command::new("sh")
+            .args(["-c", &format!("{} \"$1\"", editor), "sh", &path])
+            .status();
+        let _ = self.stdout.activate_raw_mode();
+
+        let edited = c_string::from_bytes(&fs::read(&path).unwrap_or_default());
+        let _ = fs::remove_file(&path);
+
+        self.prompt_row = self.stdout.cursor_pos().unwrap_or((1, 1)).1 as usize;
+        self.chars = self.prompt.chars().collect();
+        self.chars.extend(edited.trim_end_matches('\n').chars());
+        self.head = self.chars.len();
+        self.rewrite(true);
+
+        self.write("\r\n");
+        self.chars.push('\n');
+        Ok(true)
+    }
+}
@@ -3,15 +3,24 @@
 
 ///// Internationalization with Fluent and system language detection /////
 
-use fluent_bundle::{FluentBundle, FluentResource};
-use once_cell::unsync::OnceCell;
+pub mod gettext;
+
+use crate::core::database::DataBase;
+use fluent_bundle::{FluentArgs, FluentBundle, FluentResource};
+use fluent_syntax::ast::{Entry, Expression, InlineExpression, Pattern, PatternElement};
+use std::cell::RefCell;
+use std::fs;
+use std::path::PathBuf;
 use std::thread_local;
 use unic_langid::LanguageIdentifier;
 
 thread_local! {
-    pub static FLUENT_BUNDLE: OnceCell<FluentBundle<FluentResource>> = const { OnceCell::new() };
+    /* bundles in the order of lookup, which always ends with en */
+    pub static FLUENT_BUNDLES: RefCell<Vec<FluentBundle<FluentResource>>> = const { RefCell::new(vec![]) };
 }
 
+pub const LOCALE_VARS: [&str; 4] = ["LANGUAGE", "LC_ALL", "LC_MESSAGES", "LANG"];
+
 #[cfg(feature = "lang_ar")]
 pub static FTL_AR: &str = include_str!("../i18n/ar.ftl");
 #[cfg(feature = "lang_da")]
@@ -200,57 +209,336 @@ const LANGS: &[LangEntry] = &[
     },
 ];
 
-pub fn get_system_language() -> String {
-    fn extract_lang(s: &str) -> Option<String> {
-        let first_part = s.split(&['_', '.']).next()?;
-        if first_part.is_empty() {
-            None
-        } else {
-            Some(first_part.to_string())
-        }
+fn extract_lang(s: &str) -> Option<String> {
+    let first_part = s.split(&['_', '.', '@']).next()?;
+    if first_part.is_empty() {
+        None
+    } else {
+        Some(first_part.to_string())
     }
+}
 
-    for var in &["LC_ALL", "LC_MESSAGES", "LANG"] {
-        if let Ok(val) = std::env::var(var) {
-            if let Some(lang) = extract_lang(&val) {
-                return lang;
+/* the locale for messages, or None for C and POSIX */
+fn message_locale(values: &[String]) -> Option<&String> {
+    values[1..]
+        .iter()
+        .find(|v| !v.is_empty())
+        .filter(|l| *l != "C" && *l != "POSIX" && !l.starts_with("C."))
+}
+
+/* values of LANGUAGE, LC_ALL, LC_MESSAGES and LANG in this order,
+ * chosen in the same way as gettext */
+pub fn select_languages(values: &[String]) -> Vec<String> {
+    let Some(locale) = message_locale(values) else {
+        return vec!["en".to_string()];
+    };
+
+    let mut ans: Vec<String> = values[0].split(':').filter_map(extract_lang).collect();
+    ans.extend(extract_lang(locale));
+    ans.push("en".to_string());
+    ans.dedup();
+    ans
+}
+
+pub fn get_system_languages() -> Vec<String> {
+    let values: Vec<String> = LOCALE_VARS
+        .iter()
+        .map(|v| std::env::var(v).unwrap_or_default())
+        .collect();
+    select_languages(&values)
+}
+
+/* directories searched for <lang>.ftl, in the order of priority */
+pub fn search_path() -> Vec<PathBuf> {
+    let var = |name: &str| std::env::var(name).unwrap_or_default();
+    let mut ans: Vec<PathBuf> = var("SUSH_LOCALE_PATH")
+        .split(':')
+        .filter(|d| !d.is_empty())
+        .map(PathBuf::from)
+        .collect();
+
+    let data_home = match var("XDG_DATA_HOME").as_str() {
+        "" if var("HOME").is_empty() => None,
+        "" => Some(PathBuf::from(var("HOME")).join(".local/share")),
+        d => Some(PathBuf::from(d)),
+    };
+    ans.extend(data_home.map(|d| d.join("sush/i18n")));
+
+    let data_dirs = match var("XDG_DATA_DIRS").as_str() {
+        "" => "/usr/local/share:/usr/share".to_string(),
+        d => d.to_string(),
+    };
+    for d in data_dirs.split(':').filter(|d| !d.is_empty()) {
+        ans.push(PathBuf::from(d).join("sush/i18n"));
+    }
+    ans
+}
+
+/* resources in the order of lowest priority first */
+fn load_resources(lang: &str, broken: &mut Vec<PathBuf>) -> Vec<FluentResource> {
+    let mut ans = vec![];
+    if let Some(entry) = LANGS.iter().find(|e| e.code == lang) {
+        ans.extend(FluentResource::try_new(entry.ftl.to_string()).ok());
+    }
+
+    for dir in search_path().iter().rev() {
+        let file = dir.join(format!("{lang}.ftl"));
+        let Ok(src) = fs::read_to_string(&file) else {
+            continue;
+        };
+        match FluentResource::try_new(src) {
+            Ok(res) => ans.push(res),
+            Err((res, _)) => {
+                broken.push(file);
+                ans.push(res);
             }
         }
     }
+    ans
+}
 
-    "en".to_string()
+fn load_bundle(lang: &str, broken: &mut Vec<PathBuf>) -> Option<FluentBundle<FluentResource>> {
+    let resources = load_resources(lang, broken);
+    if resources.is_empty() {
+        return None;
+    }
+
+    let langid: LanguageIdentifier = lang.parse().ok()?;
+    let mut bundle = FluentBundle::new(vec![langid]);
+    bundle.set_use_isolating(false);
+    for res in resources {
+        bundle.add_resource_overriding(res);
+    }
+    Some(bundle)
 }
 
-pub fn load_fluent_bundle() -> Option<FluentBundle<FluentResource>> {
-    let lang = get_system_language();
+pub fn load_fluent_bundles(langs: &[String]) -> Vec<FluentBundle<FluentResource>> {
+    let mut ans = vec![];
+    let mut loaded: Vec<&str> = vec![];
+    let mut broken = vec![];
+    for lang in langs {
+        if loaded.contains(&lang.as_str()) {
+            continue;
+        }
+        if let Some(b) = load_bundle(lang, &mut broken) {
+            ans.push(b);
+            loaded.push(lang);
+        }
+    }
 
-    let entry = LANGS
-        .iter()
-        .find(|e| e.code == lang)
-        .or_else(|| LANGS.iter().find(|e| e.code == "en"))?;
+    /* reported with the bundles just loaded since the global ones may not be ready */
+    for file in broken {
+        let file = file.to_string_lossy();
+        let msg = format_message(&ans, "broken-ftl-entries", &[("file", &file)]);
+        eprintln!("sush: {msg}");
+    }
+    ans
+}
 
-    let res = FluentResource::try_new(entry.ftl.to_string()).ok()?;
-    let langid: LanguageIdentifier = entry.code.parse().ok()?;
-    let mut bundle = FluentBundle::new(vec![langid]);
-    bundle.add_resource(res).ok()?;
+pub fn set_languages(langs: &[String]) -> bool {
+    let bundles = load_fluent_bundles(langs);
+    if bundles.is_empty() {
+        return false;
+    }
+    FLUENT_BUNDLES.with(|cell| *cell.borrow_mut() = bundles);
+    true
+}
 
-    Some(bundle)
+/* called when one of LOCALE_VARS is changed in the shell */
+pub fn update_languages(db: &mut DataBase) {
+    let values: Vec<String> = LOCALE_VARS
+        .iter()
+        .map(|v| db.get_param(v).unwrap_or_default())
+        .collect();
+    set_languages(&select_languages(&values));
 }
 
 pub fn fl(key: &str) -> String {
-    FLUENT_BUNDLE.with(|cell| {
-        let bundle =
-            cell.get_or_init(|| load_fluent_bundle().expect("Could not load translation bundle"));
+    fl_args(key, &[])
+}
 
-        let mut errors = vec![];
-        bundle
-            .get_message(key)
-            .and_then(|msg| msg.value())
-            .map(|pattern| {
-                bundle
-                    .format_pattern(pattern, None, &mut errors)
-                    .to_string()
-            })
-            .unwrap_or_else(|| format!("{{{key}}}"))
+pub fn fl_args(key: &str, args: &[(&str, &str)]) -> String {
+    FLUENT_BUNDLES.with(|cell| {
+        if cell.borrow().is_empty() {
+            *cell.borrow_mut() = load_fluent_bundles(&get_system_languages());
+        }
+        format_message(&cell.borrow(), key, args)
     })
 }
+
+/* the first bundle having the key is used */
+fn format_message(
+    bundles: &[FluentBundle<FluentResource>],
+    key: &str,
+    args: &[(&str, &str)],
+) -> String {
+    let mut fargs = FluentArgs::new();
+    for (name, value) in args {
+        fargs.set(*name, value.to_string());
+    }
+
+    for bundle in bundles {
+        let pattern = match bundle.get_message(key).and_then(|msg| msg.value()) {
+            Some(p) => p,
+            None => continue,
+        };
+        let mut errors = vec![];
+        return bundle
+            .format_pattern(pattern, Some(&fargs), &mut errors)
+            .to_string();
+    }
+    format!("{{{key}}}")
+}
+
+fn inline_text(expr: &InlineExpression<&str>) -> String {
+    match expr {
+        InlineExpression::StringLiteral { value } => format!("\"{value}\""),
+        InlineExpression::NumberLiteral { value } => value.to_string(),
+        InlineExpression::VariableReference { id } => format!("${}", id.name),
+        InlineExpression::MessageReference { id, .. } => id.name.to_string(),
+        InlineExpression::TermReference { id, .. } => format!("-{}", id.name),
+        InlineExpression::FunctionReference { id, .. } => format!("{}()", id.name),
+        InlineExpression::Placeable { expression } => expression_text(expression),
+    }
+}
+
+fn expression_text(expr: &Expression<&str>) -> String {
+    match expr {
+        Expression::Inline(e) => format!("{{ {} }}", inline_text(e)),
+        Expression::Select { selector, .. } => format!("{{ {} -> ... }}", inline_text(selector)),
+    }
+}
+
+fn pattern_text(pattern: &Pattern<&str>) -> String {
+    pattern
+        .elements
+        .iter()
+        .map(|e| match e {
+            PatternElement::TextElement { value } => value.to_string(),
+            PatternElement::Placeable { expression } => expression_text(expression),
+        })
+        .collect()
+}
+
+fn po_quote(s: &str) -> String {
+    let s = s
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n");
+    format!("\"{s}\"")
+}
+
+/* messages of en (built-in and external) in the PO format for translators */
+pub fn dump_po_strings() {
+    let mut broken = vec![];
+    let resources = load_resources("en", &mut broken);
+    if !broken.is_empty() {
+        /* loading the bundles reports the broken files */
+        set_languages(&get_system_languages());
+    }
+    let mut messages: Vec<(String, String)> = vec![];
+    for res in &resources {
+        for entry in res.entries() {
+            let Entry::Message(msg) = entry else {
+                continue;
+            };
+            let Some(value) = &msg.value else {
+                continue;
+            };
+            let text = pattern_text(value);
+            match messages.iter_mut().find(|(id, _)| id == msg.id.name) {
+                Some(m) => m.1 = text,
+                None => messages.push((msg.id.name.to_string(), text)),
+            }
+        }
+    }
+
+    for (id, text) in messages {
+        println!("msgctxt {}", po_quote(&id));
+        println!("msgid {}", po_quote(&text));
+        println!("msgstr \"\"\n");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use regex::Regex;
+    use std::fs;
+    use std::path::Path;
+
+    fn collect_ids(dir: &Path, re: &Regex, ids: &mut Vec<String>) {
+        for entry in fs::read_dir(dir).unwrap().map_while(Result::ok) {
+            let path = entry.path();
+            if path.is_dir() {
+                collect_ids(&path, re, ids);
+            } else if path.extension().is_some_and(|e| e == "rs") {
+                let src = fs::read_to_string(&path).unwrap();
+                for cap in re.captures_iter(&src) {
+                    ids.push(cap[1].to_string());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn all_ftl_files_parse() {
+        for entry in LANGS {
+            if let Err((_, errors)) = FluentResource::try_new(entry.ftl.to_string()) {
+                panic!("{}.ftl: {:?}", entry.code, errors);
+            }
+        }
+    }
+
+    #[test]
+    fn every_message_id_exists_in_en() {
+        let re = Regex::new(r#"\b(?:fl(?:_args)?\(\s*|format_message\(&\w+,\s*)"([a-z0-9-]+)""#)
+            .unwrap();
+        let mut ids = vec![];
+        collect_ids(
+            &Path::new(env!("CARGO_MANIFEST_DIR")).join("src"),
+            &re,
+            &mut ids,
+        );
+        assert!(!ids.is_empty());
+
+        let bundle = load_bundle("en", &mut vec![]).expect("en.ftl is not available");
+        let missing: Vec<&String> = ids.iter().filter(|id| !bundle.has_message(id)).collect();
+        assert!(missing.is_empty(), "missing in en.ftl: {:?}", missing);
+    }
+
+    #[test]
+    fn fallback_to_en() {
+        let values = [
+            "ja".to_string(),
+            String::new(),
+            String::new(),
+            "C".to_string(),
+        ];
+        assert_eq!(select_languages(&values), vec!["en"]);
+
+        let values = [
+            "fr:ja".to_string(),
+            String::new(),
+            "ja_JP.UTF-8".to_string(),
+            String::new(),
+        ];
+        assert_eq!(select_languages(&values), vec!["fr", "ja", "en"]);
+
+        let bundle = |lang: &str, src: &str| {
+            let res = FluentResource::try_new(src.to_string()).unwrap();
+            let mut b = FluentBundle::new(vec![lang.parse().unwrap()]);
+            b.set_use_isolating(false);
+            b.add_resource(res).unwrap();
+            b
+        };
+        let bundles = [
+            bundle("ja", "greeting = こんにちは\n"),
+            bundle("en", "greeting = hello\nfarewell = bye { $name }\n"),
+        ];
+        let msg = |key, args| format_message(&bundles, key, args);
+        assert_eq!(msg("greeting", &[]), "こんにちは");
+        assert_eq!(msg("farewell", &[("name", "sush")]), "bye sush");
+        assert_eq!(msg("nothing", &[]), "{nothing}");
+    }
+}
@@ -0,0 +1,124 @@
+//SPDX-FileCopyrightText: 2024 Ryuichi Ueda ryuichiueda@gmail.com
+//SPDX-License-Identifier: BSD-3-Clause
+
+///// Translation of $"..." strings with gettext .mo catalogs /////
+
+use super::LOCALE_VARS;
+use crate::core::database::DataBase;
+use std::fs;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DumpFormat {
+    Strings,
+    PoStrings,
+}
+
+/* ja_JP.UTF-8@mod -> ja_JP.UTF-8@mod, ja_JP@mod, ja_JP, ja */
+fn expand_locale(name: &str) -> Vec<String> {
+    let (base, modifier) = match name.split_once('@') {
+        Some((b, m)) => (b, format!("@{m}")),
+        None => (name, String::new()),
+    };
+    let no_codeset = base.split('.').next().unwrap_or_default();
+    let lang = no_codeset.split('_').next().unwrap_or_default();
+
+    let mut ans = vec![
+        name.to_string(),
+        format!("{no_codeset}{modifier}"),
+        no_codeset.to_string(),
+        lang.to_string(),
+    ];
+    ans.dedup();
+    ans.retain(|n| !n.is_empty());
+    ans
+}
+
+fn catalog_locales(db: &mut DataBase) -> Vec<String> {
+    let values: Vec<String> = LOCALE_VARS
+        .iter()
+        .map(|v| db.get_param(v).unwrap_or_default())
+        .collect();
+    let Some(locale) = super::message_locale(&values) else {
+        return vec![];
+    };
+
+    let mut ans = vec![];
+    for name in values[0].split(':').chain([locale.as_str()]) {
+        for n in expand_locale(name) {
+            if !ans.contains(&n) {
+                ans.push(n);
+            }
+        }
+    }
+    ans
+}
+
+/* looks up msgid in a GNU .mo file */
+fn lookup(mo: &[u8], msgid: &str) -> Option<String> {
+    let word = |pos: usize, le: bool| -> Option<usize> {
+        let b: [u8; 4] = mo.get(pos..pos + 4)?.try_into().ok()?;
+        Some(match le {
+            true => u32::from_le_bytes(b),
+            false => u32::from_be_bytes(b),
+        } as usize)
+    };
+    let le = match word(0, true)? {
+        0x950412de => true,
+        0xde120495 => false,
+        _ => return None,
+    };
+    let string = |table: usize, i: usize| -> Option<&[u8]> {
+        let len = word(table + i * 8, le)?;
+        let pos = word(table + i * 8 + 4, le)?;
+        mo.get(pos..pos + len)
+    };
+
+    let num = word(8, le)?;
+    let originals = word(12, le)?;
+    let translations = word(16, le)?;
+    for i in 0..num {
+        /* the singular form comes before the NUL of plural entries */
+        let orig = string(originals, i)?;
+        if orig.split(|b| *b == 0).next() != Some(msgid.as_bytes()) {
+            continue;
+        }
+        let tran = string(translations, i)?;
+        let tran = tran.split(|b| *b == 0).next().unwrap_or_default();
+        return match tran.is_empty() {
+            true => None,
+            false => Some(String::from_utf8_lossy(tran).to_string()),
+        };
+    }
+    None
+}
+
+/* $TEXTDOMAINDIR/<locale>/LC_MESSAGES/$TEXTDOMAIN.mo */
+pub fn translate(db: &mut DataBase, msgid: &str) -> Option<String> {
+    let domain = db.get_param("TEXTDOMAIN").unwrap_or_default();
+    if domain.is_empty() {
+        return None;
+    }
+    let dir = match db.get_param("TEXTDOMAINDIR").unwrap_or_default() {
+        d if d.is_empty() => "/usr/share/locale".to_string(),
+        d => d,
+    };
+
+    for locale in catalog_locales(db) {
+        let file = format!("{dir}/{locale}/LC_MESSAGES/{domain}.mo");
+        if let Some(t) = fs::read(file).ok().and_then(|mo| lookup(&mo, msgid)) {
+            return Some(t);
+        }
+    }
+    None
+}
+
+pub fn dump_string(format: DumpFormat, source: &str, lineno: usize, msgid: &str) {
+    match format {
+        DumpFormat::Strings => println!("\"{msgid}\""),
+        DumpFormat::PoStrings => {
+            println!("#: {source}:{lineno}");
+            println!("msgid {}", super::po_quote(msgid));
+            println!("msgstr \"\"");
+        }
+    }
+}
@@ -20,9 +20,9 @@ use crate::core::builtins::source;
 use crate::core::{builtins, ShellCore};
 use crate::elements::script::Script;
 use crate::feeder::Feeder;
-use crate::i18n::FLUENT_BUNDLE;
 use builtins::option;
 use error::input::InputError;
+use i18n::gettext::DumpFormat;
 use utils::{arg, exit, file_check};
 
 ///// Main program entry point /////
@@ -42,6 +42,7 @@ fn main() {
 
     let command = args.first().cloned().unwrap_or_else(|| "sush".to_string());
     let script_parts = consume_file_and_subsequents(&mut args);
+    let dump_strings = consume_dump_options(&mut args);
 
     let mut c_opt = false;
     if let Some(opt) = args.last() {
@@ -51,20 +52,28 @@ fn main() {
         }
     }
 
-    let mut core = ShellCore::new();
+    if dump_strings == Some(DumpFormat::PoStrings) && script_parts.is_empty() && !c_opt {
+        i18n::dump_po_strings();
+        return;
+    }
 
-    let bundle = match i18n::load_fluent_bundle() {
-        Some(b) => b,
-        None => {
-            eprintln!("No resources found for language");
-            std::process::exit(1);
-        }
-    };
+    let mut core = ShellCore::new();
+    if dump_strings.is_some() {
+        core.dump_strings = dump_strings;
+        core.db.flags += "n";
+    }
 
-    FLUENT_BUNDLE.with(|cell| {
-        cell.set(bundle).ok();
-    });
+    if !i18n::set_languages(&i18n::get_system_languages()) {
+        eprintln!("sush: {}", i18n::fl("no-resources-for-language"));
+        std::process::exit(1);
+    }
 
+    if command.starts_with('-')
+        || arg::consume_arg("-l", &mut args)
+        || arg::consume_arg("--login", &mut args)
+    {
+        let _ = core.shopts.set("login_shell", true);
+    }
     set_o_options(&mut args, &mut core);
     set_short_options(&mut args, &mut core);
 
@@ -80,6 +89,7 @@ fn main() {
 
     if core.script_name == "-" {
         read_rc_file(&mut core);
+        core.load_history();
     }
     main_loop(&mut core, &command);
 }
@@ -116,6 +126,17 @@ fn consume_file_and_subsequents(args: &mut Vec<String>) -> Vec<String> {
     args.split_off(pos.unwrap())
 }
 
+fn consume_dump_options(args: &mut Vec<String>) -> Option<DumpFormat> {
+    let po = arg::consume_arg("--dump-po-strings", args);
+    let plain = arg::consume_arg("--dump-strings", args) | arg::consume_arg("-D", args);
+
+    match (po, plain) {
+        (true, _) => Some(DumpFormat::PoStrings),
+        (false, true) => Some(DumpFormat::Strings),
+        _ => None,
+    }
+}
+
 fn set_o_options(args: &mut Vec<String>, core: &mut ShellCore) {
     let mut options = vec![];
     loop {
@@ -197,10 +218,8 @@ fn main_loop(core: &mut ShellCore, command: &str) {
     if core.script_name != "-" {
         core.db.flags.retain(|f| f != 'i');
         if feeder.set_file(&core.script_name).is_err() {
-            eprintln!(
-                "{}: {}: No such file or directory",
-                command, &core.script_name
-            );
+            let msg = i18n::fl_args("no-such-file", &[("name", &core.script_name)]);
+            eprintln!("{command}: {msg}");
             process::exit(2);
         }
     }
@@ -248,16 +267,19 @@ fn feed_script(feeder: &mut Feeder, core: &mut ShellCore) -> (bool, bool) {
 fn parse_and_exec(feeder: &mut Feeder, core: &mut ShellCore, set_hist: bool) {
     core.sigint.store(false, Relaxed);
     match Script::parse(feeder, core, false) {
-        Ok(Some(mut s)) => {
+        Ok(Some(s)) => {
             if core.db.flags.contains('n') {
                 return;
             }
-            if let Err(e) = s.exec(core) {
-                e.print(core);
-            }
             if set_hist {
                 set_history(core, &s.get_text());
             }
+            if core.db.flags.contains('i') {
+                core.print_ps0();
+            }
+            if let Err(e) = s.exec(core) {
+                e.print(core);
+            }
         }
         Err(e) => {
             e.print(core);
@@ -273,15 +295,11 @@ fn parse_and_exec(feeder: &mut Feeder, core: &mut ShellCore, set_hist: bool) {
 }
 
 fn set_history(core: &mut ShellCore, s: &str) {
-    if core.db.flags.contains('i') || core.history.is_empty() {
+    if core.db.flags.contains('i') || !core.options.query("history") {
         return;
     }
 
-    core.history[0] = s.trim_end().replace("\n", "↵ \0").to_string();
-    if core.history[0].is_empty() || (core.history.len() > 1 && core.history[0] == core.history[1])
-    {
-        core.history.remove(0);
-    }
+    core.add_history(&s.trim_end().replace("\n", "↵ \0"));
 }
 
 ///// Text related functions /////
@@ -4,6 +4,7 @@
 use crate::core::{builtins, ShellCore};
 use crate::feed_script;
 use crate::feeder::Feeder;
+use crate::i18n::fl_args;
 use crate::parse_and_exec;
 use crate::signal;
 use crate::utils::exit;
@@ -28,7 +29,8 @@ pub fn run_and_exit(args: &[String], c_parts: &[String], core: &mut ShellCore) {
     let _ = core.configure_c_mode();
 
     if c_parts.is_empty() {
-        println!("{}: -c: option requires an argument", &args[0]);
+        let msg = fl_args("option-requires-argument", &[("opt", "-c")]);
+        eprintln!("{}: {}", &args[0], msg);
         process::exit(2);
     }
 
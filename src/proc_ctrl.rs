@@ -2,6 +2,7 @@
 //SPDX-License-Identifier: BSD-3-Clause
 
 use crate::error::exec::ExecError;
+use crate::i18n::fl_args;
 use crate::utils::c_string;
 use crate::{error, exit, signal, Feeder, Script, ShellCore};
 use nix::errno::Errno;
@@ -12,7 +13,6 @@ use nix::sys::{resource, wait};
 use nix::time::{clock_gettime, ClockId};
 use nix::unistd;
 use nix::unistd::Pid;
-use std::ffi::CString;
 use std::process;
 use std::sync::atomic::Ordering::Relaxed;
 
@@ -26,11 +26,11 @@ pub fn wait_pipeline(
         if time {
             show_time(core);
         }
-        if exclamation {
-            core.flip_exit_status();
-        }
         close_proc_sub(core);
-        exit::check_e_option(core);
+        match exclamation {
+            true => core.flip_exit_status(),
+            false => exit::check_e_option(core),
+        }
         return vec![];
     }
 
@@ -68,29 +68,40 @@ pub fn wait_pipeline(
         }
     }
 
-    if exclamation {
-        core.flip_exit_status();
-    }
-
     close_proc_sub(core);
-    exit::check_e_option(core);
+    match exclamation {
+        true => core.flip_exit_status(),
+        false => exit::check_e_option(core),
+    }
 
     ans
 }
 
 fn wait_process(core: &mut ShellCore, child: Pid) -> WaitStatus {
     let waitflags = match core.is_subshell {
-        true => None,
-        false => Some(WaitPidFlag::WUNTRACED | WaitPidFlag::WCONTINUED),
+        true => WaitPidFlag::WNOHANG,
+        false => WaitPidFlag::WNOHANG | WaitPidFlag::WUNTRACED | WaitPidFlag::WCONTINUED,
     };
 
-    let ws = wait::waitpid(child, waitflags);
+    let ws = loop {
+        match wait::waitpid(child, Some(waitflags)) {
+            Ok(WaitStatus::StillAlive) => {
+                signal::wait_signal();
+                signal::check_trap(core);
+            }
+            ws => break ws,
+        }
+    };
 
     core.db.exit_status = match ws {
         Ok(WaitStatus::Exited(_pid, status)) => status,
         Ok(WaitStatus::Signaled(pid, signal, coredump)) => error::signaled(pid, signal, coredump),
         Ok(WaitStatus::Stopped(pid, signal)) => {
-            eprintln!("Stopped Pid: {pid:?}, Signal: {signal:?}");
+            let (pid, signal) = (format!("{pid:?}"), format!("{signal:?}"));
+            eprintln!(
+                "{}",
+                fl_args("stopped", &[("pid", &pid), ("signal", &signal)])
+            );
             148
         }
         Ok(unsupported) => {
@@ -140,53 +151,129 @@ pub fn set_pgid(core: &mut ShellCore, pid: Pid, pgid: Pid) {
     }
 }
 
-fn show_time(core: &ShellCore) {
+const DEFAULT_TIMEFORMAT: &str = "\nreal\t%3lR\nuser\t%3lU\nsys\t%3lS";
+const POSIX_TIMEFORMAT: &str = "real %2R\nuser %2U\nsys %2S";
+
+/* seconds and microseconds */
+fn format_seconds(sec: i64, usec: i64, precision: usize, long: bool) -> String {
+    let mut ans = match long {
+        true => format!("{}m{}", sec / 60, sec % 60),
+        false => sec.to_string(),
+    };
+
+    if precision > 0 {
+        let frac = format!("{:06}", usec);
+        ans += &format!(".{}", &frac[..precision]);
+    }
+    if long {
+        ans.push('s');
+    }
+    ans
+}
+
+fn format_time(format: &str, times: &[(i64, i64); 3]) -> Result<String, char> {
+    let mut ans = String::new();
+    let mut chars = format.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            ans.push(c);
+            continue;
+        }
+
+        let mut precision = 3;
+        if let Some(p) = chars.peek().and_then(|d| d.to_digit(10)) {
+            precision = std::cmp::min(p as usize, 3);
+            chars.next();
+        }
+        let long = chars.next_if_eq(&'l').is_some();
+
+        let (sec, usec) = match chars.next() {
+            None => {
+                ans.push('%');
+                break;
+            }
+            Some('%') => {
+                ans.push('%');
+                continue;
+            }
+            Some('R') => times[0],
+            Some('U') => times[1],
+            Some('S') => times[2],
+            Some('P') => {
+                let real = times[0].0 * 1_000_000 + times[0].1;
+                let cpu = (times[1].0 + times[2].0) * 1_000_000 + times[1].1 + times[2].1;
+                let percent = match real {
+                    0 => 0,
+                    _ => std::cmp::min(cpu * 10000 / real, 10000),
+                };
+                ans += &format!("{}.{:02}", percent / 100, percent % 100);
+                continue;
+            }
+            Some(c) => return Err(c),
+        };
+        ans += &format_seconds(sec, usec, precision, long);
+    }
+
+    Ok(ans)
+}
+
+fn show_time(core: &mut ShellCore) {
     let real_end_time = clock_gettime(ClockId::CLOCK_MONOTONIC).unwrap();
 
     let core_usage = resource::getrusage(UsageWho::RUSAGE_SELF).unwrap();
     let children_usage = resource::getrusage(UsageWho::RUSAGE_CHILDREN).unwrap();
 
     let real_diff = real_end_time - core.measured_time.real;
-    eprintln!(
-        "\nreal\t{}m{}.{:06}s",
-        real_diff.tv_sec() / 60,
-        real_diff.tv_sec() % 60,
-        real_diff.tv_nsec() / 1000
-    );
     let user_diff = core_usage.user_time() + children_usage.user_time() - core.measured_time.user;
-    eprintln!(
-        "user\t{}m{}.{:06}s",
-        user_diff.tv_sec() / 60,
-        user_diff.tv_sec() % 60,
-        user_diff.tv_usec()
-    );
     let sys_diff = core_usage.system_time() + children_usage.system_time() - core.measured_time.sys;
-    eprintln!(
-        "sys \t{}m{}.{:06}s",
-        sys_diff.tv_sec() / 60,
-        sys_diff.tv_sec() % 60,
-        sys_diff.tv_usec()
-    );
+    let times = [
+        (real_diff.tv_sec(), real_diff.tv_nsec() / 1000),
+        (user_diff.tv_sec(), user_diff.tv_usec()),
+        (sys_diff.tv_sec(), sys_diff.tv_usec()),
+    ];
+
+    let format = match (core.measured_time.posix, core.db.exist("TIMEFORMAT")) {
+        (true, _) => POSIX_TIMEFORMAT.to_string(),
+        (false, true) => core.db.get_param("TIMEFORMAT").unwrap_or_default(),
+        (false, false) => DEFAULT_TIMEFORMAT.to_string(),
+    };
+
+    if format.is_empty() {
+        return;
+    }
+
+    match format_time(&format, &times) {
+        Ok(s) => eprintln!("{}", s),
+        Err(c) => {
+            let msg = fl_args("invalid-timeformat-char", &[("char", &c.to_string())]);
+            error::print(&msg, core);
+        }
+    }
 }
 
 pub fn exec_command(args: &[String], core: &mut ShellCore, fullpath: &str) -> ! {
     let cargs = c_string::to_cargs(args);
-    let cfullpath = CString::new(fullpath.to_string()).unwrap();
 
     if !fullpath.is_empty() {
-        let _ = unistd::execv(&cfullpath, &cargs);
+        let _ = unistd::execv(&c_string::to_carg(fullpath), &cargs);
     }
-    let result = unistd::execvp(&cargs[0], &cargs);
+    let Err(err) = unistd::execvp(&cargs[0], &cargs);
+    exit_on_exec_error(err, &args[0], core)
+}
 
-    match result {
-        Err(Errno::E2BIG) => exit::arg_list_too_long(&args[0], core),
-        Err(Errno::EACCES) => exit::permission_denied(&args[0], core),
-        Err(Errno::ENOENT) => run_command_not_found(&args[0], core),
-        Err(err) => {
-            eprintln!("Failed to execute. {err:?}");
+pub fn exit_on_exec_error(err: Errno, command: &str, core: &mut ShellCore) -> ! {
+    match err {
+        Errno::E2BIG => exit::arg_list_too_long(command, core),
+        Errno::EACCES => exit::permission_denied(command, core),
+        Errno::ENOENT => run_command_not_found(command, core),
+        _ => {
+            eprintln!(
+                "{}",
+                fl_args("failed-to-execute", &[("err", &format!("{err:?}"))])
+            );
             process::exit(127)
         }
-        _ => exit::internal("never come here"),
     }
 }
 
@@ -195,7 +282,7 @@ fn run_command_not_found(arg: &str, core: &mut ShellCore) -> ! {
         let s = "command_not_found_handle ".to_owned() + arg;
         let mut f = Feeder::new(&s);
         match Script::parse(&mut f, core, false) {
-            Ok(Some(mut script)) => {
+            Ok(Some(script)) => {
                 let _ = script.exec(core);
             }
             Err(e) => e.print(core),
@@ -2,16 +2,20 @@
 //SPDX-License-Identifier: BSD-3-Clause
 
 extern crate libc;
-use libc::{dup2, close};
+use libc::{fcntl, read, write, F_GETFL, F_SETFL, O_NONBLOCK};
 
 use crate::core::ShellCore;
+use crate::error::exec::ExecError;
 use crate::feeder::Feeder;
 use crate::Script;
+use nix::errno::Errno;
+use nix::sys::resource::{self, Resource};
 use nix::sys::signal;
-use nix::sys::signal::{SigHandler, Signal};
+use nix::sys::signal::{SaFlags, SigAction, SigHandler, SigSet, Signal};
 use signal_hook::consts;
-use signal_hook::iterator::Signals;
+use std::os::fd::RawFd;
 use std::sync::atomic::Ordering::Relaxed;
+use std::sync::atomic::{AtomicBool, AtomicI32};
 use std::sync::Arc;
 use std::{thread, time};
 
@@ -24,39 +28,19 @@ pub fn restore(sig: Signal) {
 }
 
 pub fn run_signal_check(core: &mut ShellCore) {
-    for fd in 3..10 {
-        //use FD 3~9 to prevent signal-hool from using these FDs
-            let _ = unsafe{dup2(2, fd)};
-    }
-
-    core.sigint.store(true, Relaxed);
+    /* a flag is used instead of an iterator, which occupies two FDs */
+    let caught = Arc::new(AtomicBool::new(false));
+    signal_hook::flag::register(consts::SIGINT, Arc::clone(&caught))
+        .expect("sush(fatal): cannot prepare signal data");
     let sigint = Arc::clone(&core.sigint);
 
-    thread::spawn(move || {
-        let mut signals =
-            Signals::new(vec![consts::SIGINT]).expect("sush(fatal): cannot prepare signal data");
-
-        for fd in 3..10 {
-            // release FD 3~9
-            //nix::unistd::close(fd).expect("sush(fatal): init error");
-            let _ = unsafe{close(fd)};
-        }
-        sigint.store(false, Relaxed);
-
-        loop {
-            thread::sleep(time::Duration::from_millis(100)); //0.1秒周期に変更
-            for signal in signals.pending() {
-                if signal == consts::SIGINT {
-                    sigint.store(true, Relaxed);
-                    eprintln!("^C");
-                }
-            }
+    thread::spawn(move || loop {
+        thread::sleep(time::Duration::from_millis(100)); //0.1秒周期に変更
+        if caught.swap(false, Relaxed) {
+            sigint.store(true, Relaxed);
+            eprintln!("^C");
         }
     });
-
-    while core.sigint.load(Relaxed) {
-        thread::sleep(time::Duration::from_millis(1));
-    }
 } //thanks: https://dev.to/talzvon/handling-unix-kill-signals-in-rust-55g6
 
 pub fn input_interrupt_check(feeder: &mut Feeder, core: &mut ShellCore) -> bool {
@@ -71,20 +55,166 @@ pub fn input_interrupt_check(feeder: &mut Feeder, core: &mut ShellCore) -> bool
     true
 }
 
+/* Signals for traps are caught by one handler, which marks the signal as pending
+ * and writes a byte to a self-pipe. The pipe wakes up the shell waiting for children.
+ * The handlers run between commands or in wait_process. */
+static PENDING: [AtomicBool; 65] = [const { AtomicBool::new(false) }; 65];
+static WAKEUP_RECV: AtomicI32 = AtomicI32::new(-1);
+static WAKEUP_SEND: AtomicI32 = AtomicI32::new(-1);
+
+extern "C" fn dispatch(sig: libc::c_int) {
+    let errno = Errno::last_raw();
+    if let Some(p) = PENDING.get(sig as usize) {
+        p.store(true, Relaxed);
+    }
+
+    let fd = WAKEUP_SEND.load(Relaxed);
+    if fd >= 0 {
+        let byte = sig as u8;
+        unsafe { write(fd, &byte as *const u8 as *const libc::c_void, 1) };
+    }
+    Errno::set_raw(errno);
+}
+
+fn set_handler(sig: Signal, handler: SigHandler) -> Result<SigAction, ExecError> {
+    let action = SigAction::new(handler, SaFlags::SA_RESTART, SigSet::empty());
+    Ok(unsafe { signal::sigaction(sig, &action) }?)
+}
+
+/* the pipe is placed at the top of usable fds like fd 255 of Bash
+ * so that {var}> redirections get 10 and after */
+fn dup_to_high_fd(core: &mut ShellCore, fd: RawFd) -> Result<RawFd, ExecError> {
+    let limit = match resource::getrlimit(Resource::RLIMIT_NOFILE) {
+        Ok((soft, _)) => soft.min(252) as RawFd,
+        Err(_) => 252,
+    };
+
+    let mut ans = Err(ExecError::Errno(Errno::EMFILE));
+    for base in [limit - 2, 10, 3] {
+        if base < 3 {
+            continue;
+        }
+        ans = core.fds.dupfd_cloexec(fd, base);
+        if ans.is_ok() {
+            break;
+        }
+    }
+    ans
+}
+
+pub fn init_dispatcher(core: &mut ShellCore) -> Result<(), ExecError> {
+    for fd in [WAKEUP_RECV.load(Relaxed), WAKEUP_SEND.load(Relaxed)] {
+        if fd >= 0 {
+            core.fds.close(fd);
+        }
+    }
+
+    let (recv, send) = core.fds.pipe();
+    let recv_hi = dup_to_high_fd(core, recv);
+    let send_hi = dup_to_high_fd(core, send);
+    core.fds.close(recv);
+    core.fds.close(send);
+    let (recv_hi, send_hi) = match (recv_hi, send_hi) {
+        (Ok(r), Ok(s)) => (r, s),
+        (Ok(fd), Err(e)) | (Err(e), Ok(fd)) => {
+            core.fds.close(fd);
+            return Err(e);
+        }
+        (Err(e), Err(_)) => return Err(e),
+    };
+
+    for fd in [recv_hi, send_hi] {
+        let flags = unsafe { fcntl(fd, F_GETFL) };
+        unsafe { fcntl(fd, F_SETFL, flags | O_NONBLOCK) };
+    }
+
+    WAKEUP_RECV.store(recv_hi, Relaxed);
+    WAKEUP_SEND.store(send_hi, Relaxed);
+    PENDING.iter().for_each(|p| p.store(false, Relaxed));
+
+    set_handler(Signal::SIGCHLD, SigHandler::Handler(dispatch))?;
+    Ok(())
+}
+
+/* a subshell doesn't inherit caught signals but inherits ignored ones */
+pub fn init_dispatcher_as_subshell(core: &mut ShellCore) {
+    let caught: Vec<i32> = core
+        .traps
+        .iter()
+        .filter(|t| !t.1.is_empty())
+        .map(|t| *t.0)
+        .collect();
+
+    for n in caught {
+        reset_trap(core, n);
+    }
+
+    if let Err(e) = init_dispatcher(core) {
+        e.print(core);
+    }
+}
+
+pub fn set_trap(core: &mut ShellCore, n: i32, script: &str) -> Result<(), ExecError> {
+    let sig = Signal::try_from(n)?;
+    let handler = match script.is_empty() && sig != Signal::SIGCHLD {
+        true => SigHandler::SigIgn,
+        false => SigHandler::Handler(dispatch),
+    };
+
+    let original = set_handler(sig, handler)?;
+    core.trap_default_actions.entry(n).or_insert(original);
+    core.traps.insert(n, script.to_string());
+    Ok(())
+}
+
+pub fn reset_trap(core: &mut ShellCore, n: i32) {
+    core.traps.remove(&n);
+    if let (Some(original), Ok(sig)) = (core.trap_default_actions.remove(&n), Signal::try_from(n)) {
+        let _ = unsafe { signal::sigaction(sig, &original) };
+    }
+}
+
+pub fn trap_pending(core: &ShellCore) -> bool {
+    core.traps
+        .iter()
+        .any(|t| !t.1.is_empty() && PENDING[*t.0 as usize].load(Relaxed))
+}
+
+/* blocks until a signal arrives. SIGCHLD is always caught for this function. */
+pub fn wait_signal() {
+    let fd = WAKEUP_RECV.load(Relaxed);
+    if fd < 0 {
+        return;
+    }
+
+    let mut pfd = libc::pollfd {
+        fd,
+        events: libc::POLLIN,
+        revents: 0,
+    };
+    unsafe { libc::poll(&mut pfd, 1, -1) };
+
+    let mut buf = [0u8; 64];
+    while unsafe { read(fd, buf.as_mut_ptr() as *mut libc::c_void, buf.len()) } > 0 {}
+}
+
 pub fn check_trap(core: &mut ShellCore) {
     let bkup = core.db.exit_status;
 
     let mut scripts = vec![];
-    for t in &core.trapped {
-        if t.0.load(Relaxed) {
-            scripts.push(t.1.clone());
-            t.0.store(false, Relaxed);
+    for (n, p) in PENDING.iter().enumerate() {
+        if !p.load(Relaxed) || !p.swap(false, Relaxed) {
+            continue;
+        }
+        match core.traps.get(&(n as i32)) {
+            Some(s) if !s.is_empty() => scripts.push(s.clone()),
+            _ => {}
         }
     }
 
     for s in scripts {
         let mut feeder = Feeder::new(&s);
-        let mut script = match Script::parse(&mut feeder, core, true) {
+        let script = match Script::parse(&mut feeder, core, true) {
             Ok(None) => {
                 continue;
             }
@@ -102,3 +232,120 @@ pub fn check_trap(core: &mut ShellCore) {
 
     core.db.exit_status = bkup;
 }
+
+fn run_pseudo_trap(core: &mut ShellCore, script: String) {
+    if script.is_empty() || core.pseudo_trap_running {
+        return;
+    }
+
+    core.pseudo_trap_running = true;
+    let bkup = core.db.exit_status;
+    let return_flag = core.return_flag;
+    core.return_flag = false;
+    let lineno = core.db.get_param("LINENO").unwrap_or_default();
+
+    let mut feeder = Feeder::new(&script);
+    feeder.lineno = lineno.parse().unwrap_or(1); // the line of the command triggering the trap
+    match Script::parse(&mut feeder, core, true) {
+        Ok(Some(s)) => {
+            if let Err(e) = s.exec(core) {
+                e.print(core);
+            }
+        }
+        Err(e) => e.print(core),
+        Ok(None) => {}
+    }
+
+    core.return_flag = return_flag;
+    let _ = core.db.set_param("LINENO", &lineno, None);
+    core.db.exit_status = bkup;
+    core.pseudo_trap_running = false;
+}
+
+/* not for return and not again for the command enclosing the failed one */
+pub fn run_err_trap(core: &mut ShellCore) {
+    if core.return_flag || core.err_trap_fired || core.err_script.is_empty() {
+        return;
+    }
+    run_pseudo_trap(core, core.err_script.clone());
+    core.err_trap_fired = true;
+}
+
+pub fn run_debug_trap(core: &mut ShellCore) {
+    run_pseudo_trap(core, core.debug_script.clone());
+}
+
+pub fn run_return_trap(core: &mut ShellCore) {
+    run_pseudo_trap(core, core.return_script.clone());
+}
+
+/* ERR is inherited by functions only with set -E, DEBUG and RETURN with set -T */
+pub fn hide_traps_from_function(core: &mut ShellCore) -> [String; 3] {
+    let mut saved: [String; 3] = Default::default();
+    if !core.options.query("errtrace") {
+        saved[0] = std::mem::take(&mut core.err_script);
+    }
+    if !core.options.query("functrace") {
+        saved[1] = std::mem::take(&mut core.debug_script);
+        saved[2] = std::mem::take(&mut core.return_script);
+    }
+    saved
+}
+
+/* traps set in the function are left as they are */
+pub fn restore_traps_after_function(core: &mut ShellCore, saved: [String; 3]) {
+    let [err, debug, ret] = saved;
+    if core.err_script.is_empty() {
+        core.err_script = err;
+    }
+    if core.debug_script.is_empty() {
+        core.debug_script = debug;
+    }
+    if core.return_script.is_empty() {
+        core.return_script = ret;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::elements::script::Script;
+    use crate::{Feeder, ShellCore};
+
+    fn err_trap_count(script: &str) -> String {
+        let mut core = ShellCore::new();
+        core.set_builtins();
+        core.db.position_parameters[0] = vec!["sush".to_string()];
+        let script = format!("n=0; trap 'n=$((n+1))' ERR; {script}");
+        let mut feeder = Feeder::new(&script);
+        let script = Script::parse(&mut feeder, &mut core, false)
+            .unwrap()
+            .unwrap();
+        let _ = script.exec(&mut core);
+        core.db.get_param("n").unwrap()
+    }
+
+    #[test]
+    fn err_trap_once_for_substitution() {
+        assert_eq!(err_trap_count("x=$(false)"), "1");
+        assert_eq!(err_trap_count("echo $(false) > /dev/null"), "0");
+    }
+
+    #[test]
+    fn err_trap_not_for_return() {
+        assert_eq!(err_trap_count("set -E; g(){ return 3; }; g"), "1");
+        assert_eq!(err_trap_count("{ false; }"), "1");
+    }
+
+    #[test]
+    fn lineno_in_err_trap() {
+        let mut core = ShellCore::new();
+        core.set_builtins();
+        let mut feeder = Feeder::new("trap 'l=$LINENO' ERR\n\nfalse");
+        let script = Script::parse(&mut feeder, &mut core, false)
+            .unwrap()
+            .unwrap();
+        let _ = script.exec(&mut core);
+
+        assert_eq!(core.db.get_param("l").unwrap(), "3");
+    }
+}
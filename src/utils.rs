@@ -18,8 +18,8 @@ use crate::elements::expr::arithmetic::ArithmeticExpr;
 use crate::error::exec::ExecError;
 use crate::error::input::InputError;
 use faccess::PathExt;
-use io_streams::StreamReader;
-use std::io::Read;
+use nix::errno::Errno;
+use std::os::fd::{BorrowedFd, RawFd};
 use std::path::Path;
 
 pub fn reserved(w: &str) -> bool {
@@ -29,7 +29,9 @@ pub fn reserved(w: &str) -> bool {
             | "{"
             | "}"
             | "while"
+            | "until"
             | "for"
+            | "select"
             | "do"
             | "done"
             | "if"
@@ -159,17 +161,17 @@ pub fn is_var(s: &str) -> bool {
 }
 
 pub fn read_line_stdin_unbuffered(delim: &str) -> Result<String, InputError> {
+    let d = delim.bytes().next().unwrap_or(10); //\n
+    read_line_unbuffered(0, d)
+}
+
+pub fn read_line_unbuffered(fd: RawFd, d: u8) -> Result<String, InputError> {
     let mut line = vec![];
     let mut ch: [u8; 1] = Default::default();
-    let mut stdin = StreamReader::stdin().unwrap();
-
-    let mut d = 10; //\n
-    if let Some(Ok(c)) = delim.as_bytes().bytes().next() {
-        d = c;
-    }
+    let fd = unsafe { BorrowedFd::borrow_raw(fd) };
 
     loop {
-        match stdin.read(&mut ch) {
+        match nix::unistd::read(fd, &mut ch) {
             Ok(0) => {
                 if line.is_empty() {
                     return Err(InputError::Eof);
@@ -182,14 +184,12 @@ pub fn read_line_stdin_unbuffered(delim: &str) -> Result<String, InputError> {
                     break;
                 }
             }
+            Err(Errno::EINTR) => continue,
             Err(_) => return Err(InputError::Eof),
         }
     }
 
-    match String::from_utf8(line) {
-        Ok(s) => Ok(s),
-        Err(_) => Err(InputError::NotUtf8),
-    }
+    Ok(c_string::from_bytes(&line))
 }
 
 pub fn to_ansi_c(s: &str) -> String {
@@ -256,10 +256,10 @@ pub fn get_command_path(s: &str, core: &mut ShellCore) -> String {
 
 pub fn string_to_calculated_string(from: &str, core: &mut ShellCore) -> Result<String, ExecError> {
     let mut f = Feeder::new(from);
-    if let Some(mut a) = ArithmeticExpr::parse(&mut f, core, false, "")? {
-        if f.is_empty() {
-            return a.eval(core);
-        }
+    if let Some(a) = ArithmeticExpr::parse(&mut f, core, false, "")?
+        && f.is_empty()
+    {
+        return a.eval(core);
     }
 
     Err(ExecError::SyntaxError(f.consume(f.len())))
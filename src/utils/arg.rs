@@ -1,6 +1,8 @@
 //SPDX-FileCopyrightText: 2024 Ryuichi Ueda ryuichiueda@gmail.com
 //SPDX-License-Identifier: BSD-3-Clause
 
+use super::c_string;
+
 pub fn has_option(option: &str, args: &[String]) -> bool {
     args.iter().any(|arg| arg == option)
 }
@@ -99,7 +101,10 @@ pub fn dissolve_options(args: &[String]) -> Vec<String> {
 pub fn dissolve_options_main() -> Vec<String> {
     let mut ans = vec![];
     let mut stop = false;
-    for (i, a) in std::env::args().enumerate() {
+    for (i, a) in std::env::args_os()
+        .map(|a| c_string::from_os_str(&a))
+        .enumerate()
+    {
         if i != 0 && !a.starts_with("-") || a == "--" {
             stop = true;
         }
@@ -1,33 +1,127 @@
 //SPDX-FileCopyrightText: 2025 Ryuichi Ueda <ryuichiueda@gmail.com>
 //SPDX-License-Identifier: BSD-3-Clause
 
-use std::ffi::CString;
+use std::env;
+use std::ffi::{CString, OsStr, OsString};
+use std::os::unix::ffi::{OsStrExt, OsStringExt};
 
-pub fn to_carg(arg: &str) -> CString {
-    let mut tmp = String::new();
-    let mut unicode8num = 0;
+/* a byte that is not a part of UTF-8 is kept as U+10FF80..U+10FFFF like $'\xNN'.
+ * They are at the end of the last private use plane, which fonts leave unused. */
+pub fn from_bytes(bytes: &[u8]) -> String {
+    let mut ans = String::new();
+    for chunk in bytes.utf8_chunks() {
+        push_valid(&mut ans, chunk.valid());
+        ans.extend(chunk.invalid().iter().map(|b| from_byte(*b)));
+    }
+    ans
+}
+
+pub fn from_byte(b: u8) -> char {
+    char::from_u32(0x10FF00 + b as u32).unwrap()
+}
+
+fn to_byte(c: char) -> Option<u8> {
+    match c as u32 {
+        n @ 0x10FF80..=0x10FFFF => Some((n - 0x10FF00) as u8),
+        _ => None,
+    }
+}
+
+/* a real character in the range is kept byte by byte
+ * so that to_bytes never takes it for an escaped byte */
+fn push_valid(ans: &mut String, s: &str) {
+    if !s.as_bytes().contains(&0xF4) {
+        *ans += s;
+        return;
+    }
 
+    for c in s.chars() {
+        match to_byte(c) {
+            Some(_) => ans.extend(c.to_string().bytes().map(from_byte)),
+            None => ans.push(c),
+        }
+    }
+}
+
+/* for a string made of characters, e.g. a line from the terminal */
+pub fn from_chars(s: &str) -> String {
+    let mut ans = String::new();
+    push_valid(&mut ans, s);
+    ans
+}
+
+pub fn from_os_str(s: &OsStr) -> String {
+    from_bytes(s.as_bytes())
+}
+
+pub fn to_bytes(arg: &str) -> Vec<u8> {
+    let mut ans = vec![];
     for c in arg.chars() {
-        if c as u32 >= 0xE080 && c as u32 <= 0xE0FF {
-            let num: u8 = (c as u32 - 0xE000) as u8;
-            let ch = unsafe { String::from_utf8_unchecked(vec![num]) };
-            tmp.push_str(&ch);
-        } else if c as u32 >= 0xE200 && c as u32 <= 0xE4FF {
-            unicode8num <<= 8;
-            unicode8num += c as u32 & 0xFF;
-        } else if c as u32 >= 0xE100 && c as u32 <= 0xE1FF {
-            unicode8num <<= 8;
-            unicode8num += c as u32 & 0xFF;
-            let ch = unsafe { char::from_u32_unchecked(unicode8num) }.to_string();
-            unicode8num = 0; //　^ An error occurs on debug mode.
-            tmp.push_str(&ch);
-        } else {
-            tmp.push(c);
+        match to_byte(c) {
+            Some(b) => ans.push(b),
+            None => ans.extend(c.to_string().bytes()),
         }
     }
-    CString::new(tmp.to_string()).unwrap()
+    ans
+}
+
+pub fn to_os_string(arg: &str) -> OsString {
+    OsString::from_vec(to_bytes(arg))
+}
+
+/* a C string ends at NUL as in Bash */
+pub fn to_carg(arg: &str) -> CString {
+    let mut bytes = to_bytes(arg);
+    if let Some(pos) = bytes.iter().position(|b| *b == 0) {
+        bytes.truncate(pos);
+    }
+    CString::new(bytes).unwrap()
 }
 
 pub fn to_cargs(args: &[String]) -> Vec<CString> {
     args.iter().map(|s| to_carg(s)).collect()
 }
+
+pub fn getenv(name: &str) -> Option<String> {
+    env::var_os(name).map(|v| from_os_str(&v))
+}
+
+pub fn setenv(name: &str, val: &str) {
+    unsafe { env::set_var(name, to_os_string(val)) };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::script::Script;
+    use crate::{Feeder, ShellCore};
+
+    #[test]
+    fn invalid_bytes_round_trip() {
+        let bytes = b"a\xff\xc3\xa9\xe9b";
+        assert_eq!(to_bytes(&from_bytes(bytes)), bytes);
+    }
+
+    #[test]
+    fn powerline_glyph_unchanged() {
+        for glyph in ["\u{e0a0}", "\u{e0b0}", "\u{e0b3}", "\u{e200}"] {
+            let line = format!("echo {}", glyph);
+            assert_eq!(to_bytes(&from_bytes(line.as_bytes())), line.as_bytes());
+            assert_eq!(to_bytes(&from_chars(&line)), line.as_bytes());
+            assert_eq!(from_bytes(glyph.as_bytes()), glyph);
+        }
+    }
+
+    #[test]
+    fn powerline_glyph_length_and_substring() {
+        let mut core = ShellCore::new();
+        let mut feeder = Feeder::new("g=$'\\ue0b0'x; n=${#g}; s=${g:0:1}");
+        let script = Script::parse(&mut feeder, &mut core, false)
+            .unwrap()
+            .unwrap();
+        let _ = script.exec(&mut core);
+
+        assert_eq!(core.db.get_param("n").unwrap(), "2");
+        assert_eq!(core.db.get_param("s").unwrap(), "\u{e0b0}");
+    }
+}
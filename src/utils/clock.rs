@@ -3,6 +3,7 @@
 
 use nix::time;
 use nix::time::ClockId;
+use std::ffi::CString;
 use std::time::Duration;
 
 pub fn monotonic_time() -> Duration {
@@ -45,3 +46,27 @@ pub fn get_epochrealtime() -> String {
     let real = time::clock_gettime(ClockId::CLOCK_REALTIME).unwrap();
     format!("{}.{:06}", real.tv_sec(), real.tv_nsec() / 1000).to_string()
 }
+
+pub fn strftime(format: &str, epoch: i64) -> String {
+    let format = match CString::new(format) {
+        Ok(f) => f,
+        Err(_) => return String::new(),
+    };
+
+    let mut tm: libc::tm = unsafe { std::mem::zeroed() };
+    let t = epoch as libc::time_t;
+    if unsafe { libc::localtime_r(&t, &mut tm) }.is_null() {
+        return String::new();
+    }
+
+    let mut buf = vec![0u8; 1024];
+    let len = unsafe {
+        libc::strftime(
+            buf.as_mut_ptr() as *mut libc::c_char,
+            buf.len(),
+            format.as_ptr(),
+            &tm,
+        )
+    };
+    String::from_utf8_lossy(&buf[..len]).to_string()
+}
@@ -1,21 +1,20 @@
 //SPDX-FileCopyrightText: 2025 Ryuichi Ueda <ryuichiueda@gmail.com>
 //SPDX-License-Identifier: BSD-3-Clause
 
-use super::file_check;
 use super::glob;
+use super::{file, file_check};
 use crate::core::options::Options;
 use std::fs::DirEntry;
-use std::path::Path;
 
 pub fn files(dir: &str) -> Vec<String> {
     let dir = if dir.is_empty() { "." } else { dir };
 
-    let entries = match Path::new(dir).read_dir() {
+    let entries = match file::name_to_path(dir).read_dir() {
         Ok(es) => es,
         Err(_) => return vec![],
     };
 
-    let f = |e: DirEntry| e.file_name().to_string_lossy().to_string();
+    let f = |e: DirEntry| file::oss_to_name(&e.file_name());
 
     entries.map(|e| f(e.unwrap())).collect()
 }
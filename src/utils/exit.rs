@@ -1,7 +1,7 @@
 //SPDX-FileCopyrightText: 2024 Ryuichi Ueda ryuichiueda@gmail.com
 //SPDX-License-Identifier: BSD-3-Clause
 
-use crate::{Feeder, Script, ShellCore};
+use crate::{signal, Feeder, Script, ShellCore};
 use crate::utils::ExecError;
 use std::process;
 
@@ -24,7 +24,7 @@ fn run_script(core: &mut ShellCore) {
 
     let mut feeder = Feeder::new(&core.exit_script);
     match Script::parse(&mut feeder, core, true) {
-        Ok(Some(mut s)) => {
+        Ok(Some(s)) => {
             if let Err(e) = s.exec(core) {
                 e.print(core);
             }
@@ -64,7 +64,12 @@ pub fn internal(s: &str) -> ! {
 }
 
 pub fn check_e_option(core: &mut ShellCore) {
-    if core.db.exit_status != 0 && core.db.flags.contains("e") && !core.suspend_e_option {
+    if core.db.exit_status == 0 || core.suspend_e_option {
+        return;
+    }
+
+    signal::run_err_trap(core);
+    if core.db.flags.contains("e") {
         normal(core);
     }
 }
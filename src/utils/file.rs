@@ -2,18 +2,22 @@
 //SPDX-FileCopyrightText: 2023 @caro@mi.shellgei.org
 //SPDX-License-Identifier: BSD-3-Clause
 
-use crate::utils::file_check;
+use crate::utils::{c_string, file_check};
 use crate::ShellCore;
 use std::env;
 use std::ffi::OsString;
 use std::path::{Component, Path, PathBuf};
 
 pub fn oss_to_name(oss: &OsString) -> String {
-    oss.to_string_lossy().to_string()
+    c_string::from_os_str(oss)
 }
 
 pub fn buf_to_name(path: &Path) -> String {
-    path.to_string_lossy().to_string()
+    c_string::from_os_str(path.as_os_str())
+}
+
+pub fn name_to_path(name: &str) -> PathBuf {
+    PathBuf::from(c_string::to_os_string(name))
 }
 
 pub fn search_command(command: &str) -> Option<String> {
@@ -31,10 +35,10 @@ pub fn search_command(command: &str) -> Option<String> {
 }
 
 pub fn make_absolute_path(core: &mut ShellCore, path_str: &str) -> PathBuf {
-    let path = Path::new(&path_str);
+    let path = name_to_path(path_str);
     let mut absolute = PathBuf::new();
     if !path.is_relative() {
-        absolute.push(path);
+        absolute.push(&path);
         return absolute;
     }
 
@@ -42,18 +46,18 @@ pub fn make_absolute_path(core: &mut ShellCore, path_str: &str) -> PathBuf {
         // tilde -> $HOME
         let home_dir = core.db.get_param("HOME").unwrap_or_default();
         if !home_dir.is_empty() {
-            absolute.push(PathBuf::from(home_dir));
+            absolute.push(name_to_path(&home_dir));
             let num = match path_str.len() > 1 && path_str.starts_with("~/") {
                 true => 2,
                 false => 1,
             };
-            absolute.push(PathBuf::from(&path_str[num..]));
+            absolute.push(name_to_path(&path_str[num..]));
         }
     } else {
         // current
         if let Some(tcwd) = core.get_current_directory() {
             absolute.push(tcwd);
-            absolute.push(path);
+            absolute.push(&path);
         };
     }
 
@@ -75,3 +79,54 @@ pub fn make_canonical_path(core: &mut ShellCore, path_str: &str) -> PathBuf {
     }
     canonical
 }
+
+/* 0: same, 1: transposition, 2: one character changed, added or removed, 3: others */
+fn spell_distance(cur: &[char], new: &[char]) -> usize {
+    let same = cur.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let (cur, new) = (&cur[same..], &new[same..]);
+
+    if cur.is_empty() && new.is_empty() {
+        0
+    } else if cur.len() > 1
+        && new.len() > 1
+        && cur[0] == new[1]
+        && cur[1] == new[0]
+        && cur[2..] == new[2..]
+    {
+        1
+    } else if (!cur.is_empty() && !new.is_empty() && cur[1..] == new[1..])
+        || (!cur.is_empty() && cur[1..] == *new)
+        || (!new.is_empty() && *cur == new[1..])
+    {
+        2
+    } else {
+        3
+    }
+}
+
+/* corrects each component of the path with the nearest entry in its directory */
+pub fn spell_correct(path: &str) -> Option<String> {
+    let mut ans = String::new();
+    for (i, name) in path.split('/').enumerate() {
+        if i > 0 {
+            ans.push('/');
+        }
+        let candidate = ans.clone() + name;
+        if name.is_empty() || name == "." || name == ".." || file_check::exists(&candidate) {
+            ans = candidate;
+            continue;
+        }
+
+        let dir = if ans.is_empty() { "." } else { &ans };
+        let name: Vec<char> = name.chars().collect();
+        let (_, nearest) = std::fs::read_dir(name_to_path(dir))
+            .ok()?
+            .flatten()
+            .map(|e| oss_to_name(&e.file_name()))
+            .map(|e| (spell_distance(&name, &e.chars().collect::<Vec<char>>()), e))
+            .filter(|(d, _)| *d < 3)
+            .min_by_key(|(d, _)| *d)?;
+        ans += &nearest;
+    }
+    Some(ans)
+}
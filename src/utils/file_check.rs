@@ -21,7 +21,7 @@ use std::os::linux::fs::MetadataExt;
 use std::os::macos::fs::MetadataExt;
 use std::os::unix::fs::MetadataExt as UnixMetadataExt;
 
-use std::path::Path;
+use super::file;
 
 pub fn exists(name: &str) -> bool {
     if name.ends_with("/") {
@@ -29,19 +29,19 @@ pub fn exists(name: &str) -> bool {
         return is_dir(name);
     }
 
-    fs::metadata(name).is_ok()
+    fs::metadata(file::name_to_path(name)).is_ok()
 }
 
 pub fn is_regular_file(name: &str) -> bool {
-    Path::new(name).is_file()
+    file::name_to_path(name).is_file()
 }
 
 pub fn is_dir(name: &str) -> bool {
-    Path::new(name).is_dir()
+    file::name_to_path(name).is_dir()
 }
 
 pub fn metadata_comp(left: &str, right: &str, tp: &str) -> bool {
-    let (lmeta, rmeta) = match (fs::metadata(left), fs::metadata(right)) {
+    let (lmeta, rmeta) = match (fs::metadata(file::name_to_path(left)), fs::metadata(file::name_to_path(right))) {
         (Ok(lm), Ok(rm)) => (lm, rm),
         (Ok(_), Err(_)) => return tp == "-nt",
         (Err(_), Ok(_)) => return tp == "-ot",
@@ -57,7 +57,7 @@ pub fn metadata_comp(left: &str, right: &str, tp: &str) -> bool {
 }
 
 pub fn metadata_check(name: &str, tp: &str) -> bool {
-    let meta = match fs::metadata(name) {
+    let meta = match fs::metadata(file::name_to_path(name)) {
         Ok(m) => m,
         _ => return false,
     };
@@ -94,19 +94,19 @@ pub fn metadata_check(name: &str, tp: &str) -> bool {
 }
 
 pub fn is_symlink(name: &str) -> bool {
-    Path::new(name).is_symlink()
+    file::name_to_path(name).is_symlink()
 }
 
 pub fn is_readable(name: &str) -> bool {
-    Path::new(&name).readable()
+    file::name_to_path(name).readable()
 }
 
 pub fn is_executable(name: &str) -> bool {
-    Path::new(name).executable()
+    file::name_to_path(name).executable()
 }
 
 pub fn is_writable(name: &str) -> bool {
-    Path::new(&name).writable()
+    file::name_to_path(name).writable()
 }
 
 pub fn is_tty(fd: RawFd) -> bool {
@@ -4,6 +4,7 @@
 use crate::core::database::DataBase;
 use crate::error::exec::ExecError;
 use crate::file_check;
+use crate::i18n::fl_args;
 
 pub fn check(db: &mut DataBase, name: &str, value: &Option<Vec<String>>) -> Result<(), ExecError> {
     if !db.flags.contains('r') {
@@ -23,12 +24,12 @@ pub fn check(db: &mut DataBase, name: &str, value: &Option<Vec<String>>) -> Resu
 fn rsh_cmd_check(cmds: &[String]) -> Result<(), ExecError> {
     for c in cmds {
         if c.contains('/') {
-            let msg = format!("{}: restricted", &c);
+            let msg = fl_args("exec-restricted", &[("name", c)]);
             return Err(ExecError::Other(msg));
         }
 
         if file_check::is_executable(c) {
-            let msg = format!("{}: not found", &c);
+            let msg = fl_args("not-found", &[("name", c)]);
             return Err(ExecError::Other(msg));
         }
     }